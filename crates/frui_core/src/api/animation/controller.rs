@@ -0,0 +1,424 @@
+use std::{
+    cell::RefCell,
+    rc::{Rc, Weak},
+    time::Duration,
+};
+
+use slotmap::SlotMap;
+
+use crate::{
+    api::contexts::build_cx::BuildCx,
+    app::{ticker::Ticker, tree::NodeRef},
+};
+
+slotmap::new_key_type! { pub struct ListenerKey; }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnimationStatus {
+    /// Animation is stopped at the beginning (value `0.0`).
+    Dismissed,
+    /// Animation is running from the beginning towards the end.
+    Forward,
+    /// Animation is running from the end towards the beginning.
+    Reverse,
+    /// Animation is stopped at the end (value `1.0`).
+    Completed,
+}
+
+/// Drives an animation by producing values from `0.0` to `1.0` over given
+/// duration, once per frame.
+///
+/// Controller is usually created in [`WidgetState::create_state`] and
+/// attached to the widget in [`WidgetState::mount`], which makes that widget
+/// rebuild every time the value of the controller changes:
+///
+/// ```ignore
+/// impl WidgetState for Fade {
+///     type State = AnimationController;
+///
+///     fn create_state(&self) -> Self::State {
+///         AnimationController::new(Duration::from_millis(300))
+///     }
+///
+///     fn mount<'a>(&'a self, cx: BuildCx<'a, Self>) {
+///         cx.state().attach(cx);
+///         cx.state().forward();
+///     }
+/// }
+/// ```
+///
+/// Cloned controllers refer to the same animation. It is stopped when the
+/// last of them is dropped.
+///
+/// [`WidgetState::create_state`]: crate::prelude::WidgetState::create_state
+/// [`WidgetState::mount`]: crate::prelude::WidgetState::mount
+#[derive(Clone)]
+pub struct AnimationController {
+    inner: Rc<Inner>,
+}
+
+struct Inner {
+    state: RefCell<State>,
+    ticker: Ticker,
+    /// Widget rebuilt when the value changes.
+    node: RefCell<Option<NodeRef>>,
+    listeners: RefCell<SlotMap<ListenerKey, Rc<dyn Fn()>>>,
+    status_listeners: RefCell<SlotMap<ListenerKey, Rc<dyn Fn(AnimationStatus)>>>,
+}
+
+struct State {
+    value: f64,
+    status: AnimationStatus,
+    /// Direction of the last animation, used to pick status when it ends.
+    forward: bool,
+    duration: Duration,
+    simulation: Option<Simulation>,
+}
+
+impl AnimationController {
+    pub fn new(duration: Duration) -> Self {
+        let inner = Rc::new_cyclic(|weak: &Weak<Inner>| {
+            let weak = weak.clone();
+
+            Inner {
+                state: RefCell::new(State {
+                    value: 0.0,
+                    status: AnimationStatus::Dismissed,
+                    forward: true,
+                    duration,
+                    simulation: None,
+                }),
+                ticker: Ticker::new(move |elapsed| {
+                    if let Some(inner) = weak.upgrade() {
+                        AnimationController { inner }.tick(elapsed);
+                    }
+                }),
+                node: RefCell::new(None),
+                listeners: Default::default(),
+                status_listeners: Default::default(),
+            }
+        });
+
+        AnimationController { inner }
+    }
+
+    /// Rebuilds the widget of `cx` every time the value of this controller
+    /// changes. Replaces previously attached widget.
+    pub fn attach<T>(&self, cx: BuildCx<T>) {
+        *self.inner.node.borrow_mut() = Some(cx.node_ref());
+    }
+
+    /// Current value, between `0.0` and `1.0`.
+    pub fn value(&self) -> f64 {
+        self.inner.state.borrow().value
+    }
+
+    pub fn status(&self) -> AnimationStatus {
+        self.inner.state.borrow().status
+    }
+
+    pub fn is_animating(&self) -> bool {
+        self.inner.ticker.is_active()
+    }
+
+    pub fn is_completed(&self) -> bool {
+        self.status() == AnimationStatus::Completed
+    }
+
+    pub fn is_dismissed(&self) -> bool {
+        self.status() == AnimationStatus::Dismissed
+    }
+
+    /// Duration of animation from `0.0` to `1.0`.
+    pub fn duration(&self) -> Duration {
+        self.inner.state.borrow().duration
+    }
+
+    /// Changes the duration of following animations. Doesn't affect the
+    /// animation which is currently running.
+    pub fn set_duration(&self, duration: Duration) {
+        self.inner.state.borrow_mut().duration = duration;
+    }
+
+    /// Stops the animation and sets its value (clamped to `0.0..=1.0`).
+    pub fn set_value(&self, value: f64) {
+        self.stop();
+
+        let value = value.clamp(0.0, 1.0);
+
+        let status_changed = {
+            let mut state = self.inner.state.borrow_mut();
+            state.value = value;
+
+            let status = state.status_at_rest();
+            std::mem::replace(&mut state.status, status) != status
+        };
+
+        self.notify(status_changed);
+    }
+
+    /// Stops the animation and sets its value to `0.0`.
+    pub fn reset(&self) {
+        self.set_value(0.0);
+    }
+
+    /// Animates towards `1.0`.
+    pub fn forward(&self) {
+        self.animate(1.0, true);
+    }
+
+    /// Jumps to `from` (without notifying listeners) and animates towards
+    /// `1.0`.
+    ///
+    /// Unlike calling [`set_value`] first, this doesn't rebuild the attached
+    /// widget, so it can be used to restart animation during build.
+    ///
+    /// [`set_value`]: AnimationController::set_value
+    pub fn forward_from(&self, from: f64) {
+        self.inner.state.borrow_mut().value = from.clamp(0.0, 1.0);
+        self.forward();
+    }
+
+    /// Animates towards `0.0`.
+    pub fn reverse(&self) {
+        self.animate(0.0, false);
+    }
+
+    /// Animates from the current value towards `target`.
+    ///
+    /// Duration of that animation is proportional to the distance between
+    /// both values, so that animating from `0.0` to `0.5` takes half of the
+    /// duration of this controller.
+    pub fn animate_to(&self, target: f64) {
+        let forward = target >= self.value();
+        self.animate(target, forward);
+    }
+
+    /// Runs the animation from `0.0` to `1.0` over and over again. If
+    /// `reverse` is `true`, every other run goes back from `1.0` to `0.0`.
+    ///
+    /// Repeating starts at the current value and lasts until [`stop`] is
+    /// called.
+    ///
+    /// [`stop`]: AnimationController::stop
+    pub fn repeat(&self, reverse: bool) {
+        let (start, period) = {
+            let state = self.inner.state.borrow();
+            (state.value, state.duration)
+        };
+
+        if period.is_zero() {
+            return self.set_value(1.0);
+        }
+
+        self.start(Simulation::Repeat {
+            start,
+            reverse,
+            period,
+        });
+    }
+
+    /// Stops the animation, leaving its value and status as they are.
+    pub fn stop(&self) {
+        self.inner.ticker.stop();
+        self.inner.state.borrow_mut().simulation = None;
+    }
+
+    /// Registers a callback called every time the value of this controller
+    /// changes.
+    pub fn add_listener(&self, listener: impl Fn() + 'static) -> ListenerKey {
+        self.inner.listeners.borrow_mut().insert(Rc::new(listener))
+    }
+
+    pub fn remove_listener(&self, key: ListenerKey) {
+        self.inner.listeners.borrow_mut().remove(key);
+    }
+
+    /// Registers a callback called every time the status of this controller
+    /// changes.
+    pub fn add_status_listener(&self, listener: impl Fn(AnimationStatus) + 'static) -> ListenerKey {
+        self.inner
+            .status_listeners
+            .borrow_mut()
+            .insert(Rc::new(listener))
+    }
+
+    pub fn remove_status_listener(&self, key: ListenerKey) {
+        self.inner.status_listeners.borrow_mut().remove(key);
+    }
+
+    fn animate(&self, target: f64, forward: bool) {
+        let target = target.clamp(0.0, 1.0);
+
+        let (from, duration) = {
+            let mut state = self.inner.state.borrow_mut();
+            state.forward = forward;
+
+            let distance = (target - state.value).abs();
+            (state.value, state.duration.mul_f64(distance))
+        };
+
+        if duration.is_zero() {
+            return self.set_value(target);
+        }
+
+        self.start(Simulation::Tween {
+            from,
+            to: target,
+            duration,
+        });
+    }
+
+    fn start(&self, simulation: Simulation) {
+        let status_changed = {
+            let mut state = self.inner.state.borrow_mut();
+
+            let status = match simulation {
+                Simulation::Repeat { .. } => AnimationStatus::Forward,
+                Simulation::Tween { .. } if state.forward => AnimationStatus::Forward,
+                Simulation::Tween { .. } => AnimationStatus::Reverse,
+            };
+
+            state.simulation = Some(simulation);
+            std::mem::replace(&mut state.status, status) != status
+        };
+
+        self.inner.ticker.start();
+
+        if status_changed {
+            self.notify_status();
+        }
+    }
+
+    fn tick(&self, elapsed: Duration) {
+        let (status_changed, is_done) = {
+            let mut state = self.inner.state.borrow_mut();
+
+            let (value, status) = match &state.simulation {
+                Some(simulation) => simulation.sample(elapsed, state.forward),
+                None => return,
+            };
+
+            let is_done = status.is_none();
+
+            if is_done {
+                state.simulation = None;
+            }
+
+            state.value = value;
+
+            // Finished animation rests at its target, which isn't necessarily
+            // one of the ends.
+            let status = status.unwrap_or_else(|| state.status_at_rest());
+
+            (
+                std::mem::replace(&mut state.status, status) != status,
+                is_done,
+            )
+        };
+
+        if is_done {
+            self.inner.ticker.stop();
+        }
+
+        self.notify(status_changed);
+    }
+
+    fn notify(&self, status_changed: bool) {
+        if let Some(node) = &*self.inner.node.borrow() {
+            if node.is_alive() {
+                node.mark_dirty();
+            }
+        }
+
+        // Listeners may add or remove other listeners.
+        let listeners = {
+            let listeners = self.inner.listeners.borrow();
+            listeners.values().cloned().collect::<Vec<_>>()
+        };
+
+        for listener in listeners {
+            listener();
+        }
+
+        if status_changed {
+            self.notify_status();
+        }
+    }
+
+    fn notify_status(&self) {
+        let status = self.status();
+
+        let listeners = {
+            let listeners = self.inner.status_listeners.borrow();
+            listeners.values().cloned().collect::<Vec<_>>()
+        };
+
+        for listener in listeners {
+            listener(status);
+        }
+    }
+}
+
+impl State {
+    fn status_at_rest(&self) -> AnimationStatus {
+        if self.value == 0.0 {
+            AnimationStatus::Dismissed
+        } else if self.value == 1.0 {
+            AnimationStatus::Completed
+        } else if self.forward {
+            AnimationStatus::Forward
+        } else {
+            AnimationStatus::Reverse
+        }
+    }
+}
+
+enum Simulation {
+    Tween {
+        from: f64,
+        to: f64,
+        duration: Duration,
+    },
+    Repeat {
+        start: f64,
+        reverse: bool,
+        period: Duration,
+    },
+}
+
+impl Simulation {
+    /// Returns value and status of the animation at `elapsed` time. Status
+    /// is `None` if the animation has finished.
+    fn sample(&self, elapsed: Duration, forward: bool) -> (f64, Option<AnimationStatus>) {
+        match *self {
+            Simulation::Tween { from, to, duration } => {
+                if elapsed >= duration {
+                    (to, None)
+                } else {
+                    let status = match forward {
+                        true => AnimationStatus::Forward,
+                        false => AnimationStatus::Reverse,
+                    };
+
+                    let t = elapsed.as_secs_f64() / duration.as_secs_f64();
+                    (from + (to - from) * t, Some(status))
+                }
+            }
+            Simulation::Repeat {
+                start,
+                reverse,
+                period,
+            } => {
+                let t = start + elapsed.as_secs_f64() / period.as_secs_f64();
+                let (run, fract) = (t.floor() as u64, t.fract());
+
+                if reverse && run % 2 == 1 {
+                    (1.0 - fract, Some(AnimationStatus::Reverse))
+                } else {
+                    (fract, Some(AnimationStatus::Forward))
+                }
+            }
+        }
+    }
+}
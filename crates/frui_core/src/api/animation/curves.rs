@@ -0,0 +1,163 @@
+/// Maps progress of an animation (from `0.0` to `1.0`) to its eased value.
+///
+/// Curves should map `0.0` to `0.0` and `1.0` to `1.0`, but values in between
+/// may go outside of that range (e.g. to overshoot the target).
+pub trait Curve {
+    fn transform(&self, t: f64) -> f64;
+}
+
+impl<F: Fn(f64) -> f64> Curve for F {
+    fn transform(&self, t: f64) -> f64 {
+        self(t)
+    }
+}
+
+/// Commonly used curves.
+pub struct Curves;
+
+impl Curves {
+    pub const LINEAR: Linear = Linear;
+
+    pub const EASE: Cubic = Cubic::new(0.25, 0.1, 0.25, 1.0);
+
+    pub const EASE_IN: Cubic = Cubic::new(0.42, 0.0, 1.0, 1.0);
+
+    pub const EASE_OUT: Cubic = Cubic::new(0.0, 0.0, 0.58, 1.0);
+
+    pub const EASE_IN_OUT: Cubic = Cubic::new(0.42, 0.0, 0.58, 1.0);
+
+    /// Starts quickly and slows down towards the end. Used by most material
+    /// design animations.
+    pub const FAST_OUT_SLOW_IN: Cubic = Cubic::new(0.4, 0.0, 0.2, 1.0);
+
+    pub const BOUNCE_OUT: BounceOut = BounceOut;
+}
+
+/// Identity curve.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Linear;
+
+impl Curve for Linear {
+    fn transform(&self, t: f64) -> f64 {
+        t
+    }
+}
+
+/// Cubic Bézier curve from `(0, 0)` to `(1, 1)` with control points `(a, b)`
+/// and `(c, d)`, same as `cubic-bezier` timing function in CSS.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cubic {
+    pub a: f64,
+    pub b: f64,
+    pub c: f64,
+    pub d: f64,
+}
+
+impl Cubic {
+    const ERROR_BOUND: f64 = 0.001;
+
+    pub const fn new(a: f64, b: f64, c: f64, d: f64) -> Self {
+        Cubic { a, b, c, d }
+    }
+
+    fn evaluate(a: f64, b: f64, m: f64) -> f64 {
+        3. * a * (1. - m) * (1. - m) * m + 3. * b * (1. - m) * m * m + m * m * m
+    }
+}
+
+impl Curve for Cubic {
+    fn transform(&self, t: f64) -> f64 {
+        if t <= 0. || t >= 1. {
+            return t.clamp(0., 1.);
+        }
+
+        // Find parameter of the curve at which `x == t` with binary search.
+        let (mut start, mut end) = (0.0, 1.0);
+
+        loop {
+            let mid = (start + end) / 2.;
+            let x = Self::evaluate(self.a, self.c, mid);
+
+            if (t - x).abs() < Self::ERROR_BOUND || end - start < f64::EPSILON {
+                return Self::evaluate(self.b, self.d, mid);
+            }
+
+            if x < t {
+                start = mid;
+            } else {
+                end = mid;
+            }
+        }
+    }
+}
+
+/// Curve which is `0.0` until `begin`, then runs `curve` and stays at `1.0`
+/// after `end`. Useful for staggering several animations driven by a single
+/// controller.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Interval<C = Linear> {
+    pub begin: f64,
+    pub end: f64,
+    pub curve: C,
+}
+
+impl Interval {
+    pub fn new(begin: f64, end: f64) -> Self {
+        Interval {
+            begin,
+            end,
+            curve: Linear,
+        }
+    }
+}
+
+impl<C> Interval<C> {
+    pub fn with_curve(begin: f64, end: f64, curve: C) -> Self {
+        Interval { begin, end, curve }
+    }
+}
+
+impl<C: Curve> Curve for Interval<C> {
+    fn transform(&self, t: f64) -> f64 {
+        let t = ((t - self.begin) / (self.end - self.begin)).clamp(0., 1.);
+
+        if t == 0. || t == 1. {
+            t
+        } else {
+            self.curve.transform(t)
+        }
+    }
+}
+
+/// Curve which runs `C` backwards, e.g. turning ease-in into ease-out.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Flipped<C>(pub C);
+
+impl<C: Curve> Curve for Flipped<C> {
+    fn transform(&self, t: f64) -> f64 {
+        1. - self.0.transform(1. - t)
+    }
+}
+
+/// Curve which bounces off the target a few times, with decreasing height.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BounceOut;
+
+impl Curve for BounceOut {
+    fn transform(&self, t: f64) -> f64 {
+        const N: f64 = 7.5625;
+
+        if t < 1. / 2.75 {
+            N * t * t
+        } else if t < 2. / 2.75 {
+            let t = t - 1.5 / 2.75;
+            N * t * t + 0.75
+        } else if t < 2.5 / 2.75 {
+            let t = t - 2.25 / 2.75;
+            N * t * t + 0.9375
+        } else {
+            let t = t - 2.625 / 2.75;
+            N * t * t + 0.984375
+        }
+    }
+}
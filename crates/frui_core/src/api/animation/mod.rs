@@ -0,0 +1,11 @@
+//! Animations are driven by an [`AnimationController`], which produces values
+//! from `0.0` to `1.0` once per frame. Those values are eased by a [`Curve`]
+//! and mapped to values of other types (e.g. colors) by a [`Tween`].
+
+mod controller;
+mod curves;
+mod tween;
+
+pub use controller::{AnimationController, AnimationStatus, ListenerKey};
+pub use curves::{BounceOut, Cubic, Curve, Curves, Flipped, Interval, Linear};
+pub use tween::{Lerp, Tween};
@@ -0,0 +1,133 @@
+use druid_shell::{kurbo::Affine, piet::Color};
+
+use crate::api::contexts::render::{Offset, Radius, Size};
+
+use super::{AnimationController, Curve};
+
+/// Linear interpolation between two values of the same type.
+pub trait Lerp {
+    /// Returns value between `self` (`t == 0.0`) and `other` (`t == 1.0`).
+    ///
+    /// Values of `t` outside of `0.0..=1.0` extrapolate where it makes sense.
+    fn lerp(&self, other: &Self, t: f64) -> Self;
+}
+
+/// Range of values an animation goes through.
+///
+/// ```ignore
+/// let color = Tween::new(Color::RED, Color::BLUE).evaluate(&controller);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tween<T> {
+    pub begin: T,
+    pub end: T,
+}
+
+impl<T: Lerp> Tween<T> {
+    pub fn new(begin: T, end: T) -> Self {
+        Tween { begin, end }
+    }
+
+    /// Returns the value at `t`, where `0.0` is `begin` and `1.0` is `end`.
+    pub fn transform(&self, t: f64) -> T {
+        self.begin.lerp(&self.end, t)
+    }
+
+    /// Returns the value at the current value of `controller`.
+    pub fn evaluate(&self, controller: &AnimationController) -> T {
+        self.transform(controller.value())
+    }
+
+    /// Returns the value at the current value of `controller`, eased with
+    /// `curve`.
+    pub fn evaluate_curved(&self, controller: &AnimationController, curve: &dyn Curve) -> T {
+        self.transform(curve.transform(controller.value()))
+    }
+}
+
+impl Lerp for f64 {
+    fn lerp(&self, other: &Self, t: f64) -> Self {
+        self + (other - self) * t
+    }
+}
+
+/// Values are interpolated only if both of them are `Some`. Otherwise the
+/// result jumps from one to the other when `t` reaches `0.5`.
+impl<T: Lerp + Clone> Lerp for Option<T> {
+    fn lerp(&self, other: &Self, t: f64) -> Self {
+        match (self, other) {
+            (Some(a), Some(b)) => Some(a.lerp(b, t)),
+            _ if t < 0.5 => self.clone(),
+            _ => other.clone(),
+        }
+    }
+}
+
+impl<A: Lerp, B: Lerp> Lerp for (A, B) {
+    fn lerp(&self, other: &Self, t: f64) -> Self {
+        (self.0.lerp(&other.0, t), self.1.lerp(&other.1, t))
+    }
+}
+
+impl<A: Lerp, B: Lerp, C: Lerp> Lerp for (A, B, C) {
+    fn lerp(&self, other: &Self, t: f64) -> Self {
+        (
+            self.0.lerp(&other.0, t),
+            self.1.lerp(&other.1, t),
+            self.2.lerp(&other.2, t),
+        )
+    }
+}
+
+impl Lerp for Offset {
+    fn lerp(&self, other: &Self, t: f64) -> Self {
+        Offset::new(
+            Lerp::lerp(&self.x, &other.x, t),
+            Lerp::lerp(&self.y, &other.y, t),
+        )
+    }
+}
+
+impl Lerp for Size {
+    fn lerp(&self, other: &Self, t: f64) -> Self {
+        Size::new(
+            Lerp::lerp(&self.width, &other.width, t),
+            Lerp::lerp(&self.height, &other.height, t),
+        )
+    }
+}
+
+impl Lerp for Color {
+    fn lerp(&self, other: &Self, t: f64) -> Self {
+        let (r1, g1, b1, a1) = self.as_rgba();
+        let (r2, g2, b2, a2) = other.as_rgba();
+
+        // Channels are clamped, since curves may overshoot.
+        let channel = |a: f64, b: f64| Lerp::lerp(&a, &b, t).clamp(0., 1.);
+
+        Color::rgba(
+            channel(r1, r2),
+            channel(g1, g2),
+            channel(b1, b2),
+            channel(a1, a2),
+        )
+    }
+}
+
+impl Lerp for Radius {
+    fn lerp(&self, other: &Self, t: f64) -> Self {
+        Radius::elliptical(
+            Lerp::lerp(&self.x, &other.x, t),
+            Lerp::lerp(&self.y, &other.y, t),
+        )
+    }
+}
+
+/// Interpolates each coefficient of the matrix separately, which works well
+/// for translations and scales, but not for rotations by large angles.
+impl Lerp for Affine {
+    fn lerp(&self, other: &Self, t: f64) -> Self {
+        let (a, b) = (self.as_coeffs(), other.as_coeffs());
+        Affine::new(std::array::from_fn(|i| Lerp::lerp(&a[i], &b[i], t)))
+    }
+}
@@ -73,7 +73,19 @@ impl<'a, T> _BuildCx<'a, T> {
         })
     }
 
-    fn node_ref(&self) -> NodeRef {
+    /// Returns context of the same widget, which doesn't give access to its
+    /// state.
+    ///
+    /// It's meant for callbacks which build widgets on behalf of this widget
+    /// (e.g. item builders of lazily built lists), since their types can't
+    /// name the type of this widget.
+    pub fn erased(&'a self) -> BuildCx<'a, ()> {
+        // Safety: `_BuildCx` is `repr(transparent)` over `Node`, so its layout
+        // doesn't depend on `T`.
+        unsafe { &*(self as *const Self as *const _BuildCx<'a, ()>) }
+    }
+
+    pub(crate) fn node_ref(&self) -> NodeRef {
         NodeRef {
             ptr: self.node.inner.borrow().is_alive.clone(),
         }
@@ -0,0 +1,296 @@
+use std::cell::Cell;
+
+use druid_shell::{kurbo::Affine, piet::RenderContext};
+
+use crate::app::runner::Canvas;
+
+#[cfg(not(feature = "miri"))]
+use druid_shell::piet::{Device, ImageFormat, InterpolationMode, PietImage};
+#[cfg(not(feature = "miri"))]
+use std::cell::RefCell;
+
+use super::{Offset, PaintCxOS, RenderOSExt, Size};
+
+thread_local! {
+    /// Maps coordinates of the canvas currently painted to (after applying
+    /// its current transform) to window coordinates. Updated when painting
+    /// into a layer.
+    static CANVAS_TO_WINDOW: Cell<Affine> = Cell::new(Affine::IDENTITY);
+
+    /// Bounding box of the region being repainted, in window coordinates.
+    static INVALID_BOUNDS: Cell<Option<druid_shell::kurbo::Rect>> = Cell::new(None);
+}
+
+#[cfg(not(feature = "miri"))]
+thread_local! {
+    /// Device used to create offscreen bitmaps for layers.
+    static LAYER_DEVICE: RefCell<Option<Device>> = RefCell::new(None);
+}
+
+/// Painted output of a repaint boundary, reused as long as its subtree
+/// doesn't change.
+pub(crate) struct Layer {
+    /// Area covered by this layer during last paint, in window coordinates.
+    pub bounds: druid_shell::kurbo::Rect,
+    size: Size,
+    /// Scale, rotation and skew of the canvas this layer was painted for.
+    linear: [f64; 4],
+    #[cfg(not(feature = "miri"))]
+    image: Option<PietImage>,
+}
+
+impl Layer {
+    fn is_valid_for(&self, size: Size, transform: Affine) -> bool {
+        #[cfg(not(feature = "miri"))]
+        if self.image.is_none() {
+            return false;
+        }
+
+        self.size == size && self.linear == linear_coeffs(transform)
+    }
+}
+
+/// Prepares painting of a frame, see [`PaintCxOS::paint_layer`].
+pub(crate) fn begin_frame(piet: &Canvas, invalid: druid_shell::kurbo::Rect) {
+    CANVAS_TO_WINDOW.with(|c| c.set(piet.current_transform().inverse()));
+    INVALID_BOUNDS.with(|i| i.set(Some(invalid)));
+}
+
+impl PaintCxOS {
+    /// Paints this widget in a separate layer, making it a repaint boundary.
+    ///
+    /// Output of `paint` is cached and reused in following frames until this
+    /// widget or any of its descendants is rebuilt or laid out again. Changes
+    /// in that subtree also cause only the area of this widget to be redrawn.
+    ///
+    /// Painting is clipped to the size of this widget.
+    pub fn paint_layer(
+        &mut self,
+        piet: &mut Canvas,
+        offset: &Offset,
+        paint: impl FnOnce(&mut PaintCxOS, &mut Canvas),
+    ) {
+        let size = self.size();
+        let transform = piet.current_transform();
+        let canvas_to_window = CANVAS_TO_WINDOW.with(|c| c.get());
+
+        let local_rect = druid_shell::kurbo::Rect::from_origin_size(*offset, size);
+        let bounds = (canvas_to_window * transform).transform_rect_bbox(local_rect);
+
+        let (needs_paint, layer) = {
+            let mut node = self.node().borrow_mut();
+            let render_data = &mut node.render_data;
+
+            render_data.repaint_boundary = true;
+
+            let needs_paint = std::mem::replace(&mut render_data.needs_paint, false);
+            let layer = render_data.layer.take();
+
+            (needs_paint, layer)
+        };
+
+        // Substitute canvas used in Miri doesn't produce any output to cache.
+        let can_reuse = !needs_paint && !cfg!(feature = "miri");
+
+        let mut layer = match layer {
+            Some(layer) if can_reuse && layer.is_valid_for(size, transform) => layer,
+            _ => self.record_layer(piet, offset, size, paint),
+        };
+
+        layer.bounds = bounds;
+
+        #[cfg(not(feature = "miri"))]
+        {
+            let is_visible = INVALID_BOUNDS.with(|i| match i.get() {
+                Some(invalid) => invalid.intersect(bounds).area() > 0.,
+                None => true,
+            });
+
+            if let (Some(image), true) = (&layer.image, is_visible) {
+                piet.draw_image(image, local_rect, InterpolationMode::Bilinear);
+            }
+        }
+
+        self.node().borrow_mut().render_data.layer = Some(layer);
+    }
+
+    /// Paints this widget with given opacity (from `0.0` to `1.0`).
+    ///
+    /// Output of `paint` is first rendered to an offscreen bitmap, which is
+    /// then blended with the canvas. Painting is clipped to the size of this
+    /// widget.
+    pub fn paint_with_opacity(
+        &mut self,
+        piet: &mut Canvas,
+        offset: &Offset,
+        opacity: f64,
+        paint: impl FnOnce(&mut PaintCxOS, &mut Canvas),
+    ) {
+        let opacity = opacity.clamp(0., 1.);
+
+        if opacity == 0. {
+            return;
+        } else if opacity == 1. {
+            return paint(self, piet);
+        }
+
+        self.paint_translucent(piet, offset, opacity, paint);
+    }
+
+    #[cfg(not(feature = "miri"))]
+    fn paint_translucent(
+        &mut self,
+        piet: &mut Canvas,
+        offset: &Offset,
+        opacity: f64,
+        paint: impl FnOnce(&mut PaintCxOS, &mut Canvas),
+    ) {
+        let size = self.size();
+        let mut paint = Some(paint);
+
+        if let Some(image) = self.render_image(piet, offset, size, opacity, &mut paint) {
+            let rect = druid_shell::kurbo::Rect::from_origin_size(*offset, size);
+            piet.draw_image(&image, rect, InterpolationMode::Bilinear);
+        }
+
+        // Bitmap couldn't be created, paint directly to the canvas instead.
+        if let Some(paint) = paint {
+            paint(self, piet);
+        }
+    }
+
+    /// Substitute canvas used in Miri can't render offscreen.
+    #[cfg(feature = "miri")]
+    fn paint_translucent(
+        &mut self,
+        piet: &mut Canvas,
+        _: &Offset,
+        _: f64,
+        paint: impl FnOnce(&mut PaintCxOS, &mut Canvas),
+    ) {
+        paint(self, piet);
+    }
+
+    #[cfg(not(feature = "miri"))]
+    fn record_layer(
+        &mut self,
+        piet: &mut Canvas,
+        offset: &Offset,
+        size: Size,
+        paint: impl FnOnce(&mut PaintCxOS, &mut Canvas),
+    ) -> Layer {
+        let transform = piet.current_transform();
+        let mut paint = Some(paint);
+
+        let image = self.render_image(piet, offset, size, 1., &mut paint);
+
+        // Layer couldn't be created, paint directly to the canvas instead.
+        if let Some(paint) = paint {
+            paint(self, piet);
+        }
+
+        Layer {
+            bounds: druid_shell::kurbo::Rect::ZERO,
+            size,
+            linear: linear_coeffs(transform),
+            image,
+        }
+    }
+
+    /// Renders `paint` into an offscreen bitmap of given size (scaled to match
+    /// the current transform of `piet`) and multiplies its pixels by
+    /// `opacity`.
+    ///
+    /// `paint` is taken out of the option only if the bitmap was created.
+    #[cfg(not(feature = "miri"))]
+    fn render_image<F>(
+        &mut self,
+        piet: &mut Canvas,
+        offset: &Offset,
+        size: Size,
+        opacity: f64,
+        paint: &mut Option<F>,
+    ) -> Option<PietImage>
+    where
+        F: FnOnce(&mut PaintCxOS, &mut Canvas),
+    {
+        let transform = piet.current_transform();
+
+        let scale = transform.determinant().abs().sqrt();
+        let scale = if scale.is_normal() { scale } else { 1.0 };
+
+        let width = (size.width * scale).ceil() as usize;
+        let height = (size.height * scale).ceil() as usize;
+
+        if width == 0 || height == 0 {
+            return None;
+        }
+
+        LAYER_DEVICE.with(|device| {
+            let mut device = device.borrow_mut();
+
+            if device.is_none() {
+                *device = Device::new().ok();
+            }
+
+            let device = device.as_mut()?;
+            let mut target = device.bitmap_target(width, height, scale).ok()?;
+
+            {
+                let mut layer_piet = target.render_context();
+                layer_piet.transform(Affine::translate((-offset.x, -offset.y)));
+
+                // Descendant layers compute their bounds relative to this
+                // bitmap's canvas.
+                let outer = CANVAS_TO_WINDOW.with(|c| c.get());
+                let inner = outer * transform * layer_piet.current_transform().inverse();
+
+                CANVAS_TO_WINDOW.with(|c| c.set(inner));
+                (paint.take().unwrap())(self, &mut layer_piet);
+                CANVAS_TO_WINDOW.with(|c| c.set(outer));
+
+                if let Err(e) = layer_piet.finish() {
+                    log::error!("finishing layer failed: {:?}", e);
+                }
+            }
+
+            let mut pixels = vec![0; width * height * 4];
+
+            target
+                .copy_raw_pixels(ImageFormat::RgbaPremul, &mut pixels)
+                .ok()?;
+
+            // Pixels are premultiplied, so every channel is scaled.
+            if opacity < 1. {
+                for c in &mut pixels {
+                    *c = (*c as f64 * opacity).round() as u8;
+                }
+            }
+
+            piet.make_image(width, height, &pixels, ImageFormat::RgbaPremul)
+                .ok()
+        })
+    }
+
+    #[cfg(feature = "miri")]
+    fn record_layer(
+        &mut self,
+        piet: &mut Canvas,
+        _: &Offset,
+        size: Size,
+        paint: impl FnOnce(&mut PaintCxOS, &mut Canvas),
+    ) -> Layer {
+        paint(self, piet);
+
+        Layer {
+            bounds: druid_shell::kurbo::Rect::ZERO,
+            size,
+            linear: [0.; 4],
+        }
+    }
+}
+
+fn linear_coeffs(transform: Affine) -> [f64; 4] {
+    let [a, b, c, d, _, _] = transform.as_coeffs();
+    [a, b, c, d]
+}
@@ -1,13 +1,17 @@
 mod ext;
+mod layer;
 mod paint_cx;
 mod parent_data;
 mod render_cx;
 mod render_state;
+mod render_update;
 mod types;
 
 pub use ext::*;
+pub(crate) use layer::{begin_frame, Layer};
 pub use paint_cx::*;
 pub use parent_data::*;
 pub use render_cx::*;
 pub use render_state::*;
+pub use render_update::*;
 pub use types::*;
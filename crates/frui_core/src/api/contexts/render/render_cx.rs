@@ -4,10 +4,11 @@ use druid_shell::IdleToken;
 
 use super::{
     ext::{RenderExt, RenderOSExt},
-    Constraints, Size,
+    Constraints, IntrinsicDimension, Size,
 };
 
 use crate::{
+    api::local_key::LocalKeyAny,
     app::{runner::window_handler::APP_HANDLE, tree::NodeRef},
     prelude::{InheritedState, InheritedWidget, Widget, WidgetState},
 };
@@ -77,7 +78,23 @@ impl LayoutCxOS {
         Self { node }
     }
 
+    /// Lays out this widget with given constraints and returns its size.
+    ///
+    /// If this widget was already laid out with the same constraints and
+    /// nothing in its subtree changed since, size computed last time is
+    /// returned without calling into the widget.
     pub fn layout(&self, constraints: Constraints) -> Size {
+        {
+            let render_data = &mut self.node.borrow_mut().render_data;
+
+            if !render_data.needs_layout && render_data.constraints == constraints {
+                return render_data.size;
+            }
+
+            // Widget may still mark itself as a boundary during layout.
+            render_data.relayout_boundary = constraints.is_tight();
+        }
+
         let widget = self.node.widget();
 
         let size = widget.layout(self.clone(), constraints);
@@ -90,15 +107,71 @@ impl LayoutCxOS {
             }
         }
 
-        let render_data = &mut self.node.borrow_mut().render_data;
+        {
+            let render_data = &mut self.node.borrow_mut().render_data;
+
+            render_data.size = size;
+            render_data.laid_out = true;
+            render_data.needs_layout = false;
+            render_data.constraints = constraints;
+        }
 
-        render_data.size = size;
-        render_data.laid_out = true;
-        render_data.constraints = constraints;
+        self.node.mark_needs_paint();
 
         size
     }
 
+    /// Returns distance from the top of this widget to the baseline of its
+    /// first line of text, or `None` if it has no text. Call it after laying
+    /// out this widget.
+    pub fn baseline(&self) -> Option<f64> {
+        self.node.widget().baseline(self.clone())
+    }
+
+    /// Returns intrinsic size of this widget in given `dimension`, given
+    /// `extent` along the other axis (which may be infinite).
+    ///
+    /// Unlike [`layout`](Self::layout), it doesn't change the layout of this
+    /// widget, so it can be called on children before laying them out.
+    pub fn intrinsic_size(&self, dimension: IntrinsicDimension, extent: f64) -> f64 {
+        self.node
+            .widget()
+            .intrinsic_size(self.clone(), dimension, extent)
+    }
+
+    pub fn min_intrinsic_width(&self, height: f64) -> f64 {
+        self.intrinsic_size(IntrinsicDimension::MinWidth, height)
+    }
+
+    pub fn max_intrinsic_width(&self, height: f64) -> f64 {
+        self.intrinsic_size(IntrinsicDimension::MaxWidth, height)
+    }
+
+    pub fn min_intrinsic_height(&self, width: f64) -> f64 {
+        self.intrinsic_size(IntrinsicDimension::MinHeight, width)
+    }
+
+    pub fn max_intrinsic_height(&self, width: f64) -> f64 {
+        self.intrinsic_size(IntrinsicDimension::MaxHeight, width)
+    }
+
+    /// Makes this widget a relayout boundary, meaning that changes in its
+    /// subtree will cause layout to start at this widget instead of at its
+    /// closest boundary ancestor.
+    ///
+    /// Call this during `layout`. Widgets which received tight constraints
+    /// are boundaries already.
+    pub fn mark_relayout_boundary(&self) {
+        self.node.borrow_mut().render_data.relayout_boundary = true;
+    }
+
+    /// Returns `true` if this widget is a [`LocalKey`] with given `key`.
+    ///
+    /// [`LocalKey`]: crate::prelude::LocalKey
+    pub fn matches_key<K: 'static + PartialEq>(&self, key: &K) -> bool {
+        matches!(self.node.widget().local_key(), Some(k) if k == LocalKeyAny::new(key))
+    }
+
     pub fn child(&self, index: usize) -> LayoutCxOS {
         self.try_child(index)
             .expect("specified node didn't have any children")
@@ -117,13 +190,15 @@ impl LayoutCxOS {
         Some(LayoutCxOS::new(child))
     }
 
+    /// Lays out this widget again in the next frame, even if it receives the
+    /// same constraints.
     pub fn schedule_layout(&mut self) {
+        self.node.mark_needs_layout();
+
         APP_HANDLE.with(|handle| {
-            handle
-                .borrow_mut()
-                .as_mut()
-                .expect("APP_HANDLE wasn't set")
-                .schedule_idle(IdleToken::new(0));
+            if let Some(handle) = handle.borrow_mut().as_mut() {
+                handle.schedule_idle(IdleToken::new(0));
+            }
         });
     }
 }
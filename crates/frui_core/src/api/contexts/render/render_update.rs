@@ -0,0 +1,66 @@
+use frui_macros::sealed;
+
+use crate::api::any_ext::AnyExt;
+
+/// Lets a render widget skip layout or painting after its configuration was
+/// replaced by a new one, which lays it out or paints it the same way.
+///
+/// Children of that widget are laid out and painted again regardless, if they
+/// changed.
+pub trait RenderUpdate {
+    /// Returns whether this widget needs to be laid out again after it
+    /// replaced `old` configuration.
+    fn should_relayout(&self, old: &Self) -> bool {
+        let _ = old;
+        true
+    }
+
+    /// Returns whether this widget needs to be painted again after it
+    /// replaced `old` configuration. Widgets laid out again are always
+    /// painted again.
+    fn should_repaint(&self, old: &Self) -> bool {
+        let _ = old;
+        true
+    }
+}
+
+#[sealed(crate)]
+pub trait RenderUpdateOS {
+    fn relayout_after_update(&self, old: &dyn AnyExt) -> bool;
+    fn repaint_after_update(&self, old: &dyn AnyExt) -> bool;
+}
+
+impl<T> RenderUpdateOS for T {
+    default fn relayout_after_update(&self, _: &dyn AnyExt) -> bool {
+        true
+    }
+
+    default fn repaint_after_update(&self, _: &dyn AnyExt) -> bool {
+        true
+    }
+}
+
+impl<T: RenderUpdate> RenderUpdateOS for T {
+    fn relayout_after_update(&self, old: &dyn AnyExt) -> bool {
+        // Safety:
+        //
+        // Old configuration is only compared with the new one and is dropped
+        // right after the update, so no references can outlive it.
+        unsafe {
+            match old.downcast_ref::<T>() {
+                Some(old) => <T as RenderUpdate>::should_relayout(self, old),
+                None => true,
+            }
+        }
+    }
+
+    fn repaint_after_update(&self, old: &dyn AnyExt) -> bool {
+        // Safety: see `relayout_after_update`.
+        unsafe {
+            match old.downcast_ref::<T>() {
+                Some(old) => <T as RenderUpdate>::should_repaint(self, old),
+                None => true,
+            }
+        }
+    }
+}
@@ -1,6 +1,9 @@
-use std::ops::{Add, BitAnd, BitOr, Div, Mul, Neg, Rem, Sub};
+use std::{
+    f64::consts::{FRAC_PI_2, PI},
+    ops::{Add, BitAnd, BitOr, Div, Mul, Neg, Rem, Sub},
+};
 
-use druid_shell::kurbo::Point;
+use druid_shell::kurbo::{Arc, BezPath, Point, Vec2};
 
 use super::{Offset, Size};
 
@@ -556,6 +559,52 @@ impl RRect {
 
     // TODO: inner_rect, middle_rect, wide_middle_rect, tall_middle_rect
 
+    /// Returns outline of this rounded rectangle, which corners are
+    /// approximated within given `tolerance`.
+    pub fn to_path(&self, tolerance: f64) -> BezPath {
+        let mut path = BezPath::new();
+
+        path.move_to((self.left + self.tl_radius_x, self.top));
+        path.line_to((self.right - self.tr_radius_x, self.top));
+        append_corner(
+            &mut path,
+            (self.right - self.tr_radius_x, self.top + self.tr_radius_y),
+            self.top_right_radius(),
+            -FRAC_PI_2,
+            tolerance,
+        );
+        path.line_to((self.right, self.bottom - self.br_radius_y));
+        append_corner(
+            &mut path,
+            (
+                self.right - self.br_radius_x,
+                self.bottom - self.br_radius_y,
+            ),
+            self.bottom_right_radius(),
+            0.0,
+            tolerance,
+        );
+        path.line_to((self.left + self.bl_radius_x, self.bottom));
+        append_corner(
+            &mut path,
+            (self.left + self.bl_radius_x, self.bottom - self.bl_radius_y),
+            self.bottom_left_radius(),
+            FRAC_PI_2,
+            tolerance,
+        );
+        path.line_to((self.left, self.top + self.tl_radius_y));
+        append_corner(
+            &mut path,
+            (self.left + self.tl_radius_x, self.top + self.tl_radius_y),
+            self.top_left_radius(),
+            PI,
+            tolerance,
+        );
+        path.close_path();
+
+        path
+    }
+
     pub fn is_empty(&self) -> bool {
         self.left >= self.right || self.top >= self.bottom
     }
@@ -627,57 +676,60 @@ impl RRect {
     pub fn contains(&self, point: Offset) -> bool {
         let x = point.x;
         let y = point.y;
-        let left = self.left;
-        let top = self.top;
-        let right = self.right;
-        let bottom = self.bottom;
-        let tl_radius_x = self.tl_radius_x;
-        let tl_radius_y = self.tl_radius_y;
-        let tr_radius_x = self.tr_radius_x;
-        let tr_radius_y = self.tr_radius_y;
-        let bl_radius_x = self.bl_radius_x;
-        let bl_radius_y = self.bl_radius_y;
-        let br_radius_x = self.br_radius_x;
-        let br_radius_y = self.br_radius_y;
-
-        if x < left || x > right || y < top || y > bottom {
-            return false;
-        }
 
-        if tl_radius_x > 0.0 || tl_radius_y > 0.0 {
-            let dx = x - left;
-            let dy = y - top;
-            if dx * dx / (tl_radius_x * tl_radius_x) + dy * dy / (tl_radius_y * tl_radius_y) > 1.0 {
-                return false;
-            }
-        }
-
-        if tr_radius_x > 0.0 || tr_radius_y > 0.0 {
-            let dx = x - right;
-            let dy = y - top;
-            if dx * dx / (tr_radius_x * tr_radius_x) + dy * dy / (tr_radius_y * tr_radius_y) > 1.0 {
-                return false;
-            }
+        if x < self.left || x > self.right || y < self.top || y > self.bottom {
+            return false;
         }
 
-        if bl_radius_x > 0.0 || bl_radius_y > 0.0 {
-            let dx = x - left;
-            let dy = y - bottom;
-            if dx * dx / (bl_radius_x * bl_radius_x) + dy * dy / (bl_radius_y * bl_radius_y) > 1.0 {
-                return false;
-            }
-        }
+        // Distances are measured outwards from the center of each corner's
+        // ellipse, so only points in corners' areas can lie outside of them.
+        !(outside_corner(
+            self.left + self.tl_radius_x - x,
+            self.top + self.tl_radius_y - y,
+            self.top_left_radius(),
+        ) || outside_corner(
+            x - (self.right - self.tr_radius_x),
+            self.top + self.tr_radius_y - y,
+            self.top_right_radius(),
+        ) || outside_corner(
+            self.left + self.bl_radius_x - x,
+            y - (self.bottom - self.bl_radius_y),
+            self.bottom_left_radius(),
+        ) || outside_corner(
+            x - (self.right - self.br_radius_x),
+            y - (self.bottom - self.br_radius_y),
+            self.bottom_right_radius(),
+        ))
+    }
+}
 
-        if br_radius_x > 0.0 || br_radius_y > 0.0 {
-            let dx = x - right;
-            let dy = y - bottom;
-            if dx * dx / (br_radius_x * br_radius_x) + dy * dy / (br_radius_y * br_radius_y) > 1.0 {
-                return false;
-            }
-        }
+/// Appends a quarter of an ellipse, going clockwise from `start_angle`.
+fn append_corner(
+    path: &mut BezPath,
+    center: (f64, f64),
+    radius: Radius,
+    start_angle: f64,
+    tolerance: f64,
+) {
+    let arc = Arc {
+        center: center.into(),
+        radii: Vec2::new(radius.x, radius.y),
+        start_angle,
+        sweep_angle: FRAC_PI_2,
+        x_rotation: 0.0,
+    };
+
+    path.extend(arc.append_iter(tolerance));
+}
 
-        true
-    }
+/// Whether point at given distances from the center of a corner's ellipse
+/// lies in that corner, but outside of the ellipse.
+fn outside_corner(dx: f64, dy: f64, radius: Radius) -> bool {
+    radius.x > 0.0
+        && radius.y > 0.0
+        && dx > 0.0
+        && dy > 0.0
+        && (dx * dx) / (radius.x * radius.x) + (dy * dy) / (radius.y * radius.y) > 1.0
 }
 
 impl Add<Offset> for RRect {
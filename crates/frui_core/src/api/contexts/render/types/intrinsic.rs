@@ -0,0 +1,41 @@
+/// Intrinsic size of a widget, which its parent can query before laying it out
+/// (see [`LayoutCxOS::intrinsic_size`]).
+///
+/// Each dimension is computed for a given extent along the other axis, which
+/// may be infinite.
+///
+/// [`LayoutCxOS::intrinsic_size`]: crate::render::LayoutCxOS::intrinsic_size
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntrinsicDimension {
+    /// Smallest width, given a height, below which the widget can't paint its
+    /// content correctly (e.g. width of the longest word of a text).
+    MinWidth,
+    /// Smallest width, given a height, above which increasing the width
+    /// doesn't decrease the height (e.g. width of an unwrapped text).
+    MaxWidth,
+    /// Smallest height, given a width, below which the widget can't paint its
+    /// content correctly.
+    MinHeight,
+    /// Smallest height, given a width, above which increasing the height
+    /// doesn't change the widget. For most widgets it's the same as
+    /// [`MinHeight`](IntrinsicDimension::MinHeight).
+    MaxHeight,
+}
+
+impl IntrinsicDimension {
+    /// Whether this dimension is a width.
+    pub fn is_width(&self) -> bool {
+        matches!(
+            self,
+            IntrinsicDimension::MinWidth | IntrinsicDimension::MaxWidth
+        )
+    }
+
+    /// Whether this dimension is a minimal intrinsic size.
+    pub fn is_min(&self) -> bool {
+        matches!(
+            self,
+            IntrinsicDimension::MinWidth | IntrinsicDimension::MinHeight
+        )
+    }
+}
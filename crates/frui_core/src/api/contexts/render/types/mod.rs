@@ -1,9 +1,11 @@
 mod constraints;
 mod geometry;
+mod intrinsic;
 mod offset;
 mod size;
 
 pub use constraints::*;
 pub use geometry::*;
+pub use intrinsic::*;
 pub use offset::*;
 pub use size::*;
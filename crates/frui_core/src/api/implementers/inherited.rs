@@ -24,6 +24,14 @@ impl<T: InheritedWidget> InheritedWidgetOS for T {
         cx.child(0).paint(canvas, offset)
     }
 
+    fn baseline(&self, cx: LayoutCxOS) -> Option<f64> {
+        cx.child(0).baseline()
+    }
+
+    fn intrinsic_size(&self, cx: LayoutCxOS, dimension: IntrinsicDimension, extent: f64) -> f64 {
+        cx.child(0).intrinsic_size(dimension, extent)
+    }
+
     fn inherited_key(&self) -> Option<TypeId> {
         Some(TypeId::of::<T::UniqueTypeId>())
     }
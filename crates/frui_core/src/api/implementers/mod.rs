@@ -8,7 +8,7 @@ use super::{
     any_ext::AnyExt,
     contexts::{
         build_cx::widget_state::WidgetStateOS,
-        render::{ParentDataOS, RenderStateOS},
+        render::{ParentDataOS, RenderStateOS, RenderUpdateOS},
         RawBuildCx,
     },
     local_key::WidgetLocalKey,
@@ -52,6 +52,7 @@ pub trait WidgetDerive {
 pub trait OS:
     WidgetStateOS
     + RenderStateOS
+    + RenderUpdateOS
     + ParentDataOS
     + WidgetLocalKey
     + WidgetUniqueType
@@ -66,6 +67,10 @@ pub trait OS:
 
     fn paint(&self, cx: PaintCxOS, canvas: &mut Canvas, offset: &Offset);
 
+    fn baseline(&self, cx: LayoutCxOS) -> Option<f64>;
+
+    fn intrinsic_size(&self, cx: LayoutCxOS, dimension: IntrinsicDimension, extent: f64) -> f64;
+
     fn inherited_key(&self) -> Option<TypeId> {
         None
     }
@@ -13,6 +13,33 @@ pub trait RenderWidget: WidgetDerive + Sized {
     fn layout(&self, cx: &LayoutCx<Self>, constraints: Constraints) -> Size;
 
     fn paint(&self, cx: &mut PaintCx<Self>, canvas: &mut Canvas, offset: &Offset);
+
+    /// Returns distance from the top of this widget to the baseline of its
+    /// first line of text, or `None` if it has no text. It's called after this
+    /// widget was laid out.
+    ///
+    /// Widgets with a single child which they offset should return baseline of
+    /// that child moved by that offset.
+    fn baseline(&self, _: &LayoutCx<Self>) -> Option<f64> {
+        None
+    }
+
+    /// Returns intrinsic size of this widget in given `dimension`, given
+    /// `extent` along the other axis. Unlike `layout`, it must not lay out
+    /// children, but it can query their intrinsic sizes.
+    ///
+    /// By default it's the largest intrinsic size of children, which is right
+    /// for widgets which size themselves to their only child.
+    fn intrinsic_size(
+        &self,
+        cx: &LayoutCx<Self>,
+        dimension: IntrinsicDimension,
+        extent: f64,
+    ) -> f64 {
+        cx.children()
+            .map(|child| child.intrinsic_size(dimension, extent))
+            .fold(0., f64::max)
+    }
 }
 
 impl<T: RenderWidget> RenderWidgetOS for T {
@@ -36,4 +63,16 @@ impl<T: RenderWidget> RenderWidgetOS for T {
 
         T::paint(self, cx, canvas, offset);
     }
+
+    fn baseline(&self, cx: LayoutCxOS) -> Option<f64> {
+        let cx = &<LayoutCx<T>>::new(cx);
+
+        T::baseline(self, cx)
+    }
+
+    fn intrinsic_size(&self, cx: LayoutCxOS, dimension: IntrinsicDimension, extent: f64) -> f64 {
+        let cx = &<LayoutCx<T>>::new(cx);
+
+        T::intrinsic_size(self, cx, dimension, extent)
+    }
 }
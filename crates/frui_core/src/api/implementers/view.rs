@@ -29,4 +29,12 @@ impl<T: ViewWidget> ViewWidgetOS for T {
     fn paint<'w>(&'w self, mut cx: PaintCxOS, canvas: &mut Canvas, offset: &Offset) {
         cx.child(0).paint(canvas, offset)
     }
+
+    fn baseline(&self, cx: LayoutCxOS) -> Option<f64> {
+        cx.child(0).baseline()
+    }
+
+    fn intrinsic_size(&self, cx: LayoutCxOS, dimension: IntrinsicDimension, extent: f64) -> f64 {
+        cx.child(0).intrinsic_size(dimension, extent)
+    }
 }
@@ -102,6 +102,16 @@ impl RenderWidget for () {
     fn paint(&self, _: &mut PaintCx<Self>, _: &mut Canvas, _: &Offset) {}
 }
 
+impl RenderUpdate for () {
+    fn should_relayout(&self, _: &Self) -> bool {
+        false
+    }
+
+    fn should_repaint(&self, _: &Self) -> bool {
+        false
+    }
+}
+
 impl RawWidget for () {
     fn build<'w>(&'w self, cx: &'w super::contexts::RawBuildCx) -> Vec<super::WidgetPtr<'w>> {
         <Self as RenderWidgetOS>::build(self, cx)
@@ -115,6 +125,14 @@ impl RawWidget for () {
         <Self as RenderWidgetOS>::paint(self, cx, canvas, offset)
     }
 
+    fn baseline(&self, cx: LayoutCxOS) -> Option<f64> {
+        <Self as RenderWidgetOS>::baseline(self, cx)
+    }
+
+    fn intrinsic_size(&self, cx: LayoutCxOS, dimension: IntrinsicDimension, extent: f64) -> f64 {
+        <Self as RenderWidgetOS>::intrinsic_size(self, cx, dimension, extent)
+    }
+
     fn inherited_key(&self) -> Option<std::any::TypeId> {
         <Self as RenderWidgetOS>::inherited_key(self)
     }
@@ -144,6 +162,19 @@ macro_rules! impl_widget_os_deref_ {
                 self.deref().paint(cx, canvas, offset)
             }
 
+            fn baseline(&self, cx: LayoutCxOS) -> Option<f64> {
+                self.deref().baseline(cx)
+            }
+
+            fn intrinsic_size(
+                &self,
+                cx: LayoutCxOS,
+                dimension: IntrinsicDimension,
+                extent: f64,
+            ) -> f64 {
+                self.deref().intrinsic_size(cx, dimension, extent)
+            }
+
 
             fn inherited_key(&self) -> Option<std::any::TypeId> {
                 self.deref().inherited_key()
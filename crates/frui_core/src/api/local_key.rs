@@ -26,6 +26,12 @@ pub struct LocalKeyAny<'a> {
     key: &'a dyn PartialEqAny,
 }
 
+impl<'a> LocalKeyAny<'a> {
+    pub(crate) fn new<K: 'static + PartialEq>(key: &'a K) -> Self {
+        LocalKeyAny { key }
+    }
+}
+
 impl PartialEq for LocalKeyAny<'_> {
     fn eq(&self, other: &Self) -> bool {
         self.key.eq(other.key)
@@ -2,13 +2,16 @@ use std::any::TypeId;
 
 use self::implementers::{RawWidget, WidgetDerive};
 
+pub(crate) mod animation;
 pub(crate) mod any_ext;
 pub(crate) mod contexts;
 pub(crate) mod implementers;
 pub(crate) mod impls;
 pub(crate) mod local_key;
 pub(crate) mod pointer_events;
+pub(crate) mod scrolling;
 pub(crate) mod structural_eq;
+pub(crate) mod text_editing;
 pub(crate) mod widget_ptr;
 
 pub use widget_ptr::{IntoWidgetPtr, WidgetPtr};
@@ -61,4 +64,4 @@ impl<T> WidgetDebug for T {
 
         &full_name[start..end]
     }
-}
\ No newline at end of file
+}
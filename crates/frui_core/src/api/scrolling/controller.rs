@@ -0,0 +1,274 @@
+use std::{
+    cell::RefCell,
+    rc::{Rc, Weak},
+    time::Duration,
+};
+
+use druid_shell::kurbo::Vec2;
+use slotmap::SlotMap;
+
+use crate::{
+    api::{
+        animation::{AnimationController, Curve},
+        contexts::{build_cx::BuildCx, render::Size},
+    },
+    app::tree::NodeRef,
+};
+
+slotmap::new_key_type! { pub struct ScrollListenerKey; }
+
+/// Scroll offset of a scroll view, which can be read and changed from outside
+/// of that view.
+///
+/// Parent widget keeps the controller in its state and passes it to the
+/// scroll view:
+///
+/// ```ignore
+/// fn create_state(&self) -> Self::State {
+///     ScrollController::new()
+/// }
+///
+/// fn build<'w>(&'w self, cx: BuildCx<'w, Self>) -> Self::Widget<'w> {
+///     Column::builder().children((
+///         Button {
+///             on_pressed: || cx.state().animate_to(Vec2::ZERO, duration, Curves::EASE),
+///             ..
+///         },
+///         Scroll::builder().controller(cx.state().clone()).child(list),
+///     ))
+/// }
+/// ```
+///
+/// Until the scroll view is laid out, its maximal scroll offset isn't known
+/// and offsets are not clamped. This makes it possible to restore a position
+/// of a scroll view before it's built.
+///
+/// Cloned controllers refer to the same scroll offset.
+#[derive(Clone)]
+pub struct ScrollController {
+    inner: Rc<Inner>,
+}
+
+struct Inner {
+    state: RefCell<Position>,
+    /// Drives animations started by [`ScrollController::animate_to`].
+    animation: AnimationController,
+    /// Widgets rebuilt when the scroll offset changes.
+    nodes: RefCell<Vec<NodeRef>>,
+    listeners: RefCell<SlotMap<ScrollListenerKey, Rc<dyn Fn()>>>,
+}
+
+struct Position {
+    offset: Vec2,
+    /// Maximal scroll offset, known once the scroll view is laid out.
+    max_offset: Option<Vec2>,
+    viewport: Size,
+    animation: Option<ScrollAnimation>,
+}
+
+struct ScrollAnimation {
+    from: Vec2,
+    to: Vec2,
+    curve: Rc<dyn Curve>,
+}
+
+impl Default for ScrollController {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ScrollController {
+    pub fn new() -> Self {
+        Self::with_offset(Vec2::ZERO)
+    }
+
+    /// Creates controller which scrolls its scroll view to `offset` once it's
+    /// laid out.
+    pub fn with_offset(offset: Vec2) -> Self {
+        let inner = Rc::new_cyclic(|weak: &Weak<Inner>| {
+            let weak = weak.clone();
+            let animation = AnimationController::new(Duration::ZERO);
+
+            animation.add_listener(move || {
+                if let Some(inner) = weak.upgrade() {
+                    ScrollController { inner }.tick();
+                }
+            });
+
+            Inner {
+                state: RefCell::new(Position {
+                    offset,
+                    max_offset: None,
+                    viewport: Size::ZERO,
+                    animation: None,
+                }),
+                animation,
+                nodes: Default::default(),
+                listeners: Default::default(),
+            }
+        });
+
+        ScrollController { inner }
+    }
+
+    /// Rebuilds the widget of `cx` every time the scroll offset changes.
+    pub fn attach<T>(&self, cx: BuildCx<T>) {
+        let node = cx.node_ref();
+        let mut nodes = self.inner.nodes.borrow_mut();
+
+        if !nodes.contains(&node) {
+            nodes.push(node);
+        }
+    }
+
+    /// Current scroll offset.
+    pub fn offset(&self) -> Vec2 {
+        self.inner.state.borrow().offset
+    }
+
+    /// Maximal scroll offset, which is how much the content overflows the
+    /// viewport. It's `None` until the scroll view is laid out.
+    pub fn max_offset(&self) -> Option<Vec2> {
+        self.inner.state.borrow().max_offset
+    }
+
+    /// Size of the viewport of the scroll view.
+    pub fn viewport(&self) -> Size {
+        self.inner.state.borrow().viewport
+    }
+
+    /// Scrolls to `offset` (clamped to the maximal offset), stopping any
+    /// running animation.
+    pub fn jump_to(&self, offset: Vec2) {
+        self.inner.state.borrow_mut().animation = None;
+        self.inner.animation.stop();
+
+        self.set_offset(offset);
+    }
+
+    /// Scrolls by `delta`, stopping any running animation.
+    pub fn jump_by(&self, delta: Vec2) {
+        self.jump_to(self.offset() + delta);
+    }
+
+    /// Animates the scroll offset from the current one to `offset` (clamped
+    /// to the maximal offset).
+    pub fn animate_to(&self, offset: Vec2, duration: Duration, curve: impl Curve + 'static) {
+        let from = self.offset();
+        let to = self.clamp(offset);
+
+        if duration.is_zero() || from == to {
+            return self.jump_to(to);
+        }
+
+        self.inner.state.borrow_mut().animation = Some(ScrollAnimation {
+            from,
+            to,
+            curve: Rc::new(curve),
+        });
+
+        let animation = &self.inner.animation;
+
+        animation.set_duration(duration);
+        animation.forward_from(0.0);
+    }
+
+    pub fn is_animating(&self) -> bool {
+        self.inner.state.borrow().animation.is_some()
+    }
+
+    /// Updates the size of the viewport and the maximal scroll offset after
+    /// the scroll view is laid out. Current offset is clamped to the new
+    /// maximal offset, without notifying listeners.
+    pub fn set_metrics(&self, viewport: Size, max_offset: Vec2) {
+        let mut state = self.inner.state.borrow_mut();
+
+        state.viewport = viewport;
+        state.max_offset = Some(max_offset);
+        state.offset = clamp(state.offset, max_offset);
+    }
+
+    /// Registers a callback called every time the scroll offset changes.
+    pub fn add_listener(&self, listener: impl Fn() + 'static) -> ScrollListenerKey {
+        self.inner.listeners.borrow_mut().insert(Rc::new(listener))
+    }
+
+    pub fn remove_listener(&self, key: ScrollListenerKey) {
+        self.inner.listeners.borrow_mut().remove(key);
+    }
+
+    fn clamp(&self, offset: Vec2) -> Vec2 {
+        match self.max_offset() {
+            Some(max_offset) => clamp(offset, max_offset),
+            None => Vec2::new(offset.x.max(0.), offset.y.max(0.)),
+        }
+    }
+
+    fn set_offset(&self, offset: Vec2) {
+        let offset = self.clamp(offset);
+
+        let changed = {
+            let mut state = self.inner.state.borrow_mut();
+            std::mem::replace(&mut state.offset, offset) != offset
+        };
+
+        if changed {
+            self.notify();
+        }
+    }
+
+    fn tick(&self) {
+        let value = self.inner.animation.value();
+
+        let offset = {
+            let mut state = self.inner.state.borrow_mut();
+
+            let offset = match &state.animation {
+                Some(a) => a.from + (a.to - a.from) * a.curve.transform(value),
+                None => return,
+            };
+
+            if !self.inner.animation.is_animating() {
+                state.animation = None;
+            }
+
+            offset
+        };
+
+        self.set_offset(offset);
+    }
+
+    fn notify(&self) {
+        self.inner.nodes.borrow_mut().retain(|node| node.is_alive());
+
+        let nodes = self.inner.nodes.borrow().clone();
+
+        for node in nodes {
+            node.mark_dirty();
+        }
+
+        // Listeners may add or remove other listeners.
+        let listeners = {
+            let listeners = self.inner.listeners.borrow();
+            listeners.values().cloned().collect::<Vec<_>>()
+        };
+
+        for listener in listeners {
+            listener();
+        }
+    }
+}
+
+impl PartialEq for ScrollController {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+fn clamp(offset: Vec2, max_offset: Vec2) -> Vec2 {
+    Vec2::new(
+        offset.x.clamp(0., max_offset.x),
+        offset.y.clamp(0., max_offset.y),
+    )
+}
@@ -0,0 +1,6 @@
+//! Scroll offset of scroll views, which can be observed and changed through a
+//! [`ScrollController`].
+
+mod controller;
+
+pub use controller::{ScrollController, ScrollListenerKey};
@@ -0,0 +1,659 @@
+use std::{
+    borrow::Cow,
+    cell::{Ref, RefCell},
+    ops::Range,
+    rc::Rc,
+};
+
+use druid_shell::{
+    kurbo::{Point, Rect},
+    piet::{HitTestPoint, PietTextLayout, TextLayout},
+    text::{Action, Affinity, Direction, InputHandler, Movement, Selection},
+};
+use slotmap::SlotMap;
+
+use crate::{
+    api::contexts::build_cx::BuildCx,
+    app::{clipboard, tree::NodeRef},
+};
+
+use super::movement;
+
+slotmap::new_key_type! { pub struct TextListenerKey; }
+
+/// Maximum number of edits which can be undone.
+const MAX_UNDO: usize = 100;
+
+/// Text, selection and edit history of a text field.
+///
+/// Text field creates its own controller unless one is passed to it. Parent
+/// widget keeps the controller in its state to read or change the text of
+/// that text field:
+///
+/// ```ignore
+/// fn create_state(&self) -> Self::State {
+///     TextEditingController::with_text("Hello")
+/// }
+///
+/// fn build<'w>(&'w self, cx: BuildCx<'w, Self>) -> Self::Widget<'w> {
+///     Column::builder().children((
+///         TextField::builder().controller(cx.state().clone()),
+///         Button { on_pressed: || cx.state().clear() },
+///     ))
+/// }
+/// ```
+///
+/// Cloned controllers refer to the same text.
+#[derive(Clone, Default)]
+pub struct TextEditingController {
+    inner: Rc<Inner>,
+}
+
+#[derive(Default)]
+struct Inner {
+    state: RefCell<EditState>,
+    /// Widgets rebuilt when the text or selection changes.
+    nodes: RefCell<Vec<NodeRef>>,
+    listeners: RefCell<SlotMap<TextListenerKey, Rc<dyn Fn()>>>,
+}
+
+struct EditState {
+    text: String,
+    selection: Selection,
+    composition: Option<Range<usize>>,
+    multiline: bool,
+    /// Layout of the text, used to move the caret between visual lines and
+    /// to answer queries of the input method.
+    layout: Option<PietTextLayout>,
+    undo: Vec<Snapshot>,
+    redo: Vec<Snapshot>,
+    /// Kind of the last edit. Consecutive edits of the same kind (e.g. typed
+    /// characters) are undone at once.
+    last_edit: Option<EditKind>,
+    /// Incremented by every change of text, selection or composition.
+    revision: u64,
+}
+
+struct Snapshot {
+    text: String,
+    selection: Selection,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EditKind {
+    Insert,
+    Delete,
+    Other,
+}
+
+impl TextEditingController {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates controller with the caret placed at the end of `text`.
+    pub fn with_text(text: impl Into<String>) -> Self {
+        let text = text.into();
+        let this = Self::default();
+
+        {
+            let mut state = this.inner.state.borrow_mut();
+            state.selection = Selection::caret(text.len());
+            state.text = text;
+        }
+
+        this
+    }
+
+    /// Rebuilds the widget of `cx` every time the text, selection or
+    /// composition of this controller changes.
+    ///
+    /// Unlike [`AnimationController::attach`], many widgets can be attached
+    /// at once (e.g. the text field and the parent displaying its text). They
+    /// stay attached until they are unmounted.
+    ///
+    /// [`AnimationController::attach`]: crate::api::animation::AnimationController::attach
+    pub fn attach<T>(&self, cx: BuildCx<T>) {
+        let node = cx.node_ref();
+        let mut nodes = self.inner.nodes.borrow_mut();
+
+        if !nodes.contains(&node) {
+            nodes.push(node);
+        }
+    }
+
+    pub fn text(&self) -> String {
+        self.inner.state.borrow().text.clone()
+    }
+
+    /// Borrows the text without copying it.
+    pub fn text_ref(&self) -> Ref<str> {
+        Ref::map(self.inner.state.borrow(), |state| state.text.as_str())
+    }
+
+    /// Replaces the whole text and moves the caret to its end. Can be undone.
+    pub fn set_text(&self, text: impl Into<String>) {
+        let text = text.into();
+
+        self.update(|state| {
+            let len = state.text.len();
+            state.replace(0..len, &text, EditKind::Other);
+        });
+    }
+
+    pub fn clear(&self) {
+        self.set_text("");
+    }
+
+    pub fn selection(&self) -> Selection {
+        self.inner.state.borrow().selection
+    }
+
+    /// Sets the selection, clamped to the text.
+    pub fn set_selection(&self, selection: Selection) {
+        self.update(|state| state.set_selection(selection));
+    }
+
+    pub fn selected_text(&self) -> String {
+        let state = self.inner.state.borrow();
+        state.text[state.selection.range()].to_owned()
+    }
+
+    /// Range of the text which is being composed by the input method.
+    pub fn composition_range(&self) -> Option<Range<usize>> {
+        self.inner.state.borrow().composition.clone()
+    }
+
+    pub fn select_all(&self) {
+        self.handle_action(Action::SelectAll);
+    }
+
+    /// Replaces selected text with `text`, like typing does.
+    pub fn insert(&self, text: &str) {
+        self.update(|state| state.insert(text));
+    }
+
+    /// Copies selected text to the clipboard.
+    pub fn copy(&self) {
+        let text = self.selected_text();
+
+        if !text.is_empty() {
+            clipboard::put_string(text);
+        }
+    }
+
+    /// Copies selected text to the clipboard and deletes it.
+    pub fn cut(&self) {
+        self.copy();
+
+        self.update(|state| {
+            let range = state.selection.range();
+
+            if !range.is_empty() {
+                state.replace(range, "", EditKind::Other);
+            }
+        });
+    }
+
+    /// Replaces selected text with the text on the clipboard. Line breaks are
+    /// replaced with spaces unless this controller is multi-line.
+    pub fn paste(&self) {
+        if let Some(text) = clipboard::get_string() {
+            self.update(|state| {
+                let text = match state.multiline {
+                    true => text.replace("\r\n", "\n"),
+                    false => text.replace("\r\n", " ").replace('\n', " "),
+                };
+
+                let range = state.selection.range();
+                state.replace(range, &text, EditKind::Other);
+            });
+        }
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.inner.state.borrow().undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.inner.state.borrow().redo.is_empty()
+    }
+
+    /// Reverts the last edit. Returns `false` if there was nothing to undo.
+    pub fn undo(&self) -> bool {
+        self.update(|state| {
+            let snapshot = state.undo.pop()?;
+            let current = state.restore(snapshot);
+            state.redo.push(current);
+            Some(())
+        })
+        .is_some()
+    }
+
+    /// Reapplies the last undone edit. Returns `false` if there was nothing
+    /// to redo.
+    pub fn redo(&self) -> bool {
+        self.update(|state| {
+            let snapshot = state.redo.pop()?;
+            let current = state.restore(snapshot);
+            state.undo.push(current);
+            Some(())
+        })
+        .is_some()
+    }
+
+    /// Performs editing action, e.g. moves the caret by a word.
+    pub fn handle_action(&self, action: Action) {
+        self.update(|state| state.handle_action(action));
+    }
+
+    pub fn is_multiline(&self) -> bool {
+        self.inner.state.borrow().multiline
+    }
+
+    /// Whether line breaks can be inserted. Set by the text field using this
+    /// controller.
+    pub fn set_multiline(&self, multiline: bool) {
+        self.inner.state.borrow_mut().multiline = multiline;
+    }
+
+    /// Layout of the text set by the text field which displays it.
+    pub fn layout(&self) -> Option<PietTextLayout> {
+        self.inner.state.borrow().layout.clone()
+    }
+
+    /// Sets the layout of the text, which is used to move the caret between
+    /// visual lines and to convert positions to text offsets.
+    pub fn set_layout(&self, layout: Option<PietTextLayout>) {
+        self.inner.state.borrow_mut().layout = layout;
+    }
+
+    /// Offset in the text closest to `point` in the coordinates of the layout.
+    pub fn offset_at(&self, point: Point) -> usize {
+        let state = self.inner.state.borrow();
+
+        match &state.layout {
+            Some(layout) => layout.hit_test_point(point).idx,
+            None => state.text.len(),
+        }
+    }
+
+    /// Returns handler through which the input method edits the text (see
+    /// [`TextInput`]).
+    ///
+    /// [`TextInput`]: crate::app::text_input::TextInput
+    pub fn input_handler(&self) -> Rc<RefCell<dyn InputHandler>> {
+        Rc::new(RefCell::new(Handler(self.clone())))
+    }
+
+    /// Registers a callback called every time the text, selection or
+    /// composition of this controller changes.
+    pub fn add_listener(&self, listener: impl Fn() + 'static) -> TextListenerKey {
+        self.inner.listeners.borrow_mut().insert(Rc::new(listener))
+    }
+
+    pub fn remove_listener(&self, key: TextListenerKey) {
+        self.inner.listeners.borrow_mut().remove(key);
+    }
+
+    /// Applies `f` to the state and notifies listeners if it changed.
+    fn update<R>(&self, f: impl FnOnce(&mut EditState) -> R) -> R {
+        let (result, changed) = {
+            let mut state = self.inner.state.borrow_mut();
+            let revision = state.revision;
+            let result = f(&mut state);
+            (result, state.revision != revision)
+        };
+
+        if changed {
+            self.notify();
+        }
+
+        result
+    }
+
+    fn notify(&self) {
+        self.inner.nodes.borrow_mut().retain(|node| {
+            if node.is_alive() {
+                node.mark_dirty();
+                true
+            } else {
+                false
+            }
+        });
+
+        // Listeners may add or remove other listeners.
+        let listeners = {
+            let listeners = self.inner.listeners.borrow();
+            listeners.values().cloned().collect::<Vec<_>>()
+        };
+
+        for listener in listeners {
+            listener();
+        }
+    }
+}
+
+impl PartialEq for TextEditingController {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl Default for EditState {
+    fn default() -> Self {
+        EditState {
+            text: String::new(),
+            selection: Selection::caret(0),
+            composition: None,
+            multiline: false,
+            layout: None,
+            undo: Vec::new(),
+            redo: Vec::new(),
+            last_edit: None,
+            revision: 0,
+        }
+    }
+}
+
+impl EditState {
+    fn clamp(&self, offset: usize) -> usize {
+        let mut offset = offset.min(self.text.len());
+
+        while !self.text.is_char_boundary(offset) {
+            offset -= 1;
+        }
+
+        offset
+    }
+
+    fn set_selection(&mut self, selection: Selection) {
+        let anchor = self.clamp(selection.anchor);
+        let active = self.clamp(selection.active);
+        let selection = Selection::new(anchor, active).with_h_pos(selection.h_pos);
+
+        if selection != self.selection {
+            self.selection = selection;
+            self.last_edit = None;
+            self.revision += 1;
+        }
+    }
+
+    fn set_composition(&mut self, range: Option<Range<usize>>) {
+        let range = range.map(|range| self.clamp(range.start)..self.clamp(range.end));
+
+        if range != self.composition {
+            self.composition = range;
+            self.revision += 1;
+        }
+    }
+
+    /// Replaces `range` of the text with `text` and places the caret after
+    /// it.
+    fn replace(&mut self, range: Range<usize>, text: &str, kind: EditKind) {
+        let range = self.clamp(range.start)..self.clamp(range.end);
+
+        if range.is_empty() && text.is_empty() {
+            return;
+        }
+
+        if kind == EditKind::Other || self.last_edit != Some(kind) {
+            let snapshot = Snapshot {
+                text: self.text.clone(),
+                selection: self.selection,
+            };
+
+            self.undo.push(snapshot);
+
+            if self.undo.len() > MAX_UNDO {
+                self.undo.remove(0);
+            }
+        }
+
+        self.redo.clear();
+        self.last_edit = Some(kind);
+
+        self.text.replace_range(range.clone(), text);
+        self.selection = Selection::caret(range.start + text.len());
+
+        // Composition is moved with the text after it, and discarded if it
+        // was (partly) replaced.
+        self.composition = match self.composition.take() {
+            Some(c) if c.start >= range.end => {
+                let shift = |i: usize| i - range.len() + text.len();
+                Some(shift(c.start)..shift(c.end))
+            }
+            Some(c) if c.end <= range.start => Some(c),
+            _ => None,
+        };
+
+        self.revision += 1;
+    }
+
+    fn insert(&mut self, text: &str) {
+        let range = self.selection.range();
+        self.replace(range, text, EditKind::Insert);
+    }
+
+    /// Restores the text and selection of `snapshot` and returns the current
+    /// ones.
+    fn restore(&mut self, snapshot: Snapshot) -> Snapshot {
+        let current = Snapshot {
+            text: std::mem::replace(&mut self.text, snapshot.text),
+            selection: std::mem::replace(&mut self.selection, snapshot.selection),
+        };
+
+        self.composition = None;
+        self.last_edit = None;
+        self.revision += 1;
+
+        current
+    }
+
+    /// Offset which the caret moves to from the active end of the selection,
+    /// and the horizontal position that should be kept by vertical movements.
+    fn move_target(&self, movement: Movement) -> (usize, Option<f64>) {
+        let text = &self.text;
+        let offset = self.selection.active;
+
+        let target = match movement {
+            Movement::Grapheme(d) if movement::is_backward(d) => {
+                movement::prev_grapheme(text, offset)
+            }
+            Movement::Grapheme(_) => movement::next_grapheme(text, offset),
+            Movement::Word(d) if movement::is_backward(d) => {
+                movement::prev_word_start(text, offset)
+            }
+            Movement::Word(_) => movement::next_word_end(text, offset),
+            Movement::Line(d) => {
+                let line = movement::line_range(text, self.layout.as_ref(), offset);
+
+                match movement::is_backward(d) {
+                    true => line.start,
+                    false => line.end,
+                }
+            }
+            Movement::ParagraphStart => movement::paragraph_start(text, offset),
+            Movement::ParagraphEnd => movement::paragraph_end(text, offset),
+            Movement::Vertical(v) => {
+                return movement::vertical(
+                    text,
+                    self.layout.as_ref(),
+                    offset,
+                    self.selection.h_pos,
+                    v,
+                )
+            }
+        };
+
+        (target, None)
+    }
+
+    fn handle_action(&mut self, action: Action) {
+        match action {
+            Action::Move(m) => {
+                let selection = self.selection;
+
+                // Moving by a grapheme collapses selection to its side.
+                let (offset, h_pos) = match m {
+                    Movement::Grapheme(d) if !selection.is_caret() => {
+                        match movement::is_backward(d) {
+                            true => (selection.min(), None),
+                            false => (selection.max(), None),
+                        }
+                    }
+                    m => self.move_target(m),
+                };
+
+                self.set_selection(Selection::caret(offset).with_h_pos(h_pos));
+            }
+            Action::MoveSelecting(m) => {
+                let (offset, h_pos) = self.move_target(m);
+                let selection = Selection::new(self.selection.anchor, offset);
+
+                self.set_selection(selection.with_h_pos(h_pos));
+            }
+            Action::SelectAll => self.set_selection(Selection::new(0, self.text.len())),
+            Action::SelectWord => {
+                let range = movement::word_range(&self.text, self.selection.active);
+                self.set_selection(Selection::new(range.start, range.end));
+            }
+            Action::SelectLine | Action::SelectParagraph => {
+                let offset = self.selection.active;
+                let start = movement::paragraph_start(&self.text, offset);
+                let end = movement::paragraph_end(&self.text, offset);
+
+                self.set_selection(Selection::new(start, end));
+            }
+            Action::Delete(m) => {
+                let range = match self.selection.is_caret() {
+                    true => {
+                        let (offset, _) = self.move_target(m);
+                        let active = self.selection.active;
+                        offset.min(active)..offset.max(active)
+                    }
+                    false => self.selection.range(),
+                };
+
+                self.replace(range, "", EditKind::Delete);
+            }
+            Action::DecomposingBackspace => {
+                self.handle_action(Action::Delete(Movement::Grapheme(Direction::Upstream)))
+            }
+            Action::InsertNewLine { .. } if self.multiline => self.insert("\n"),
+            Action::UppercaseSelection => self.map_selection(str::to_uppercase),
+            Action::LowercaseSelection => self.map_selection(str::to_lowercase),
+            _ => {}
+        }
+    }
+
+    /// Replaces selected text with `f(selected)`, keeping it selected.
+    fn map_selection(&mut self, f: impl Fn(&str) -> String) {
+        let range = self.selection.range();
+
+        if range.is_empty() {
+            return;
+        }
+
+        let text = f(&self.text[range.clone()]);
+        self.replace(range.clone(), &text, EditKind::Other);
+        self.set_selection(Selection::new(range.start, range.start + text.len()));
+    }
+}
+
+/// Input handler of [`TextEditingController`], passed to the [`TextInput`].
+///
+/// [`TextInput`]: crate::app::text_input::TextInput
+struct Handler(TextEditingController);
+
+impl Handler {
+    fn state(&self) -> Ref<EditState> {
+        self.0.inner.state.borrow()
+    }
+}
+
+impl InputHandler for Handler {
+    fn selection(&self) -> Selection {
+        self.state().selection
+    }
+
+    fn set_selection(&mut self, selection: Selection) {
+        self.0.set_selection(selection);
+    }
+
+    fn composition_range(&self) -> Option<Range<usize>> {
+        self.state().composition.clone()
+    }
+
+    fn set_composition_range(&mut self, range: Option<Range<usize>>) {
+        self.0.update(|state| state.set_composition(range));
+    }
+
+    fn is_char_boundary(&self, i: usize) -> bool {
+        self.state().text.is_char_boundary(i)
+    }
+
+    fn len(&self) -> usize {
+        self.state().text.len()
+    }
+
+    fn slice(&self, range: Range<usize>) -> Cow<str> {
+        Cow::Owned(self.state().text[range].to_owned())
+    }
+
+    fn replace_range(&mut self, range: Range<usize>, text: &str) {
+        let kind = match text.is_empty() {
+            true => EditKind::Delete,
+            false => EditKind::Insert,
+        };
+
+        self.0.update(|state| state.replace(range, text, kind));
+    }
+
+    fn hit_test_point(&self, point: Point) -> HitTestPoint {
+        match &self.state().layout {
+            Some(layout) => layout.hit_test_point(point),
+            None => HitTestPoint::default(),
+        }
+    }
+
+    fn line_range(&self, index: usize, _affinity: Affinity) -> Range<usize> {
+        let state = self.state();
+        movement::line_range(&state.text, state.layout.as_ref(), index)
+    }
+
+    fn bounding_box(&self) -> Option<Rect> {
+        let state = self.state();
+        let layout = state.layout.as_ref()?;
+
+        Some(layout.size().to_rect())
+    }
+
+    fn slice_bounding_box(&self, range: Range<usize>) -> Option<Rect> {
+        let state = self.state();
+        let layout = state.layout.as_ref()?;
+
+        match range.is_empty() {
+            true => {
+                let position = layout.hit_test_text_position(range.start);
+                let metric = layout.line_metric(position.line)?;
+                let x = position.point.x;
+
+                Some(Rect::new(
+                    x,
+                    metric.y_offset,
+                    x,
+                    metric.y_offset + metric.height,
+                ))
+            }
+            false => layout
+                .rects_for_range(range)
+                .into_iter()
+                .reduce(|a, b| a.union(b)),
+        }
+    }
+
+    fn handle_action(&mut self, action: Action) {
+        self.0.handle_action(action);
+    }
+}
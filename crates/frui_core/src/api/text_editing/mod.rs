@@ -0,0 +1,7 @@
+//! Editing of text by the keyboard, the input method and the clipboard,
+//! shared by text-entry widgets through a [`TextEditingController`].
+
+mod controller;
+mod movement;
+
+pub use controller::{TextEditingController, TextListenerKey};
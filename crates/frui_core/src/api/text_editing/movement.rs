@@ -0,0 +1,215 @@
+//! Computes offsets which the caret moves to.
+//!
+//! Movement between graphemes operates on `char`s, which covers the text
+//! which isn't composed of multiple code points (e.g. most of the emojis).
+
+use std::ops::Range;
+
+use druid_shell::{
+    kurbo::Point,
+    piet::{PietTextLayout, TextLayout},
+    text::{Direction, VerticalMovement},
+};
+
+pub(super) fn is_backward(direction: Direction) -> bool {
+    match direction {
+        Direction::Left | Direction::Upstream => true,
+        Direction::Right | Direction::Downstream => false,
+    }
+}
+
+pub(super) fn prev_grapheme(text: &str, offset: usize) -> usize {
+    text[..offset]
+        .char_indices()
+        .next_back()
+        .map_or(0, |(i, _)| i)
+}
+
+pub(super) fn next_grapheme(text: &str, offset: usize) -> usize {
+    text[offset..]
+        .chars()
+        .next()
+        .map_or(offset, |c| offset + c.len_utf8())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CharClass {
+    Whitespace,
+    Word,
+    Punctuation,
+}
+
+impl CharClass {
+    fn of(c: char) -> Self {
+        if c.is_whitespace() {
+            CharClass::Whitespace
+        } else if c.is_alphanumeric() || c == '_' {
+            CharClass::Word
+        } else {
+            CharClass::Punctuation
+        }
+    }
+}
+
+/// Start of the word before `offset`, skipping whitespace in between.
+pub(super) fn prev_word_start(text: &str, offset: usize) -> usize {
+    let mut class = None;
+    let mut start = offset;
+
+    for (i, c) in text[..offset].char_indices().rev() {
+        match (class, CharClass::of(c)) {
+            (None, CharClass::Whitespace) => {}
+            (None, other) => class = Some(other),
+            (Some(class), other) if class != other => break,
+            _ => {}
+        }
+
+        start = i;
+    }
+
+    start
+}
+
+/// End of the word after `offset`, skipping whitespace in between.
+pub(super) fn next_word_end(text: &str, offset: usize) -> usize {
+    let mut class = None;
+    let mut end = offset;
+
+    for (i, c) in text[offset..].char_indices() {
+        match (class, CharClass::of(c)) {
+            (None, CharClass::Whitespace) => {}
+            (None, other) => class = Some(other),
+            (Some(class), other) if class != other => break,
+            _ => {}
+        }
+
+        end = offset + i + c.len_utf8();
+    }
+
+    end
+}
+
+/// Range of the word (or run of whitespace or punctuation) at `offset`.
+pub(super) fn word_range(text: &str, offset: usize) -> Range<usize> {
+    let class = match text[offset..].chars().next() {
+        Some(c) => CharClass::of(c),
+        None => match text[..offset].chars().next_back() {
+            Some(c) => CharClass::of(c),
+            None => return offset..offset,
+        },
+    };
+
+    let start = text[..offset]
+        .char_indices()
+        .rev()
+        .take_while(|(_, c)| CharClass::of(*c) == class)
+        .last()
+        .map_or(offset, |(i, _)| i);
+
+    let end = text[offset..]
+        .char_indices()
+        .take_while(|(_, c)| CharClass::of(*c) == class)
+        .last()
+        .map_or(offset, |(i, c)| offset + i + c.len_utf8());
+
+    start..end
+}
+
+pub(super) fn paragraph_start(text: &str, offset: usize) -> usize {
+    text[..offset].rfind('\n').map_or(0, |i| i + 1)
+}
+
+pub(super) fn paragraph_end(text: &str, offset: usize) -> usize {
+    text[offset..].find('\n').map_or(text.len(), |i| offset + i)
+}
+
+/// Range of the visual line containing `offset`, without the line break.
+///
+/// Without a layout (e.g. before the text was laid out) lines are delimited
+/// only by line breaks.
+pub(super) fn line_range(
+    text: &str,
+    layout: Option<&PietTextLayout>,
+    offset: usize,
+) -> Range<usize> {
+    let layout = match layout {
+        Some(layout) if layout.line_count() > 0 => layout,
+        _ => return paragraph_start(text, offset)..paragraph_end(text, offset),
+    };
+
+    let line = layout.hit_test_text_position(offset).line;
+
+    match layout.line_metric(line) {
+        Some(metric) => {
+            let end = metric.end_offset.min(text.len());
+            let end = match text[..end].ends_with('\n') {
+                true => end - 1,
+                false => end,
+            };
+
+            metric.start_offset.min(end)..end
+        }
+        None => paragraph_start(text, offset)..paragraph_end(text, offset),
+    }
+}
+
+/// Offset reached by moving the caret vertically from `offset`, and the
+/// horizontal position which following vertical movements should keep.
+///
+/// `h_pos` is the horizontal position kept by previous vertical movements.
+pub(super) fn vertical(
+    text: &str,
+    layout: Option<&PietTextLayout>,
+    offset: usize,
+    h_pos: Option<f64>,
+    movement: VerticalMovement,
+) -> (usize, Option<f64>) {
+    let up = match movement {
+        VerticalMovement::LineUp => true,
+        VerticalMovement::LineDown => false,
+        VerticalMovement::PageUp | VerticalMovement::DocumentStart => return (0, None),
+        VerticalMovement::PageDown | VerticalMovement::DocumentEnd => return (text.len(), None),
+    };
+
+    match layout {
+        Some(layout) if layout.line_count() > 0 => {
+            let position = layout.hit_test_text_position(offset);
+            let h_pos = h_pos.unwrap_or(position.point.x);
+
+            let line = match up {
+                true if position.line == 0 => return (0, Some(h_pos)),
+                true => position.line - 1,
+                false if position.line + 1 >= layout.line_count() => {
+                    return (text.len(), Some(h_pos))
+                }
+                false => position.line + 1,
+            };
+
+            let metric = layout.line_metric(line).unwrap();
+            let y = metric.y_offset + metric.height / 2.;
+
+            (layout.hit_test_point(Point::new(h_pos, y)).idx, Some(h_pos))
+        }
+        // Keep the column, counted in chars, between paragraphs.
+        _ => {
+            let start = paragraph_start(text, offset);
+            let column = h_pos.unwrap_or(text[start..offset].chars().count() as f64);
+
+            let target = match up {
+                true if start == 0 => return (0, Some(column)),
+                true => paragraph_start(text, start - 1),
+                false => match text[offset..].find('\n') {
+                    Some(i) => offset + i + 1,
+                    None => return (text.len(), Some(column)),
+                },
+            };
+
+            let offset = text[target..paragraph_end(text, target)]
+                .char_indices()
+                .nth(column as usize)
+                .map_or(paragraph_end(text, target), |(i, _)| target + i);
+
+            (offset, Some(column))
+        }
+    }
+}
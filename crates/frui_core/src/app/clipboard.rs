@@ -0,0 +1,41 @@
+//! Access to the system clipboard.
+//!
+//! Headless runners have no access to the system clipboard, so there text is
+//! copied to and pasted from a clipboard private to the current thread.
+
+use std::cell::RefCell;
+
+thread_local! {
+    /// Clipboard used when there is no system clipboard.
+    static LOCAL_CLIPBOARD: RefCell<Option<String>> = RefCell::new(None);
+}
+
+/// Puts `text` on the clipboard, replacing its previous contents.
+pub fn put_string(text: impl AsRef<str>) {
+    #[cfg(not(feature = "miri"))]
+    if let Some(mut clipboard) = system_clipboard() {
+        return clipboard.put_string(text);
+    }
+
+    LOCAL_CLIPBOARD.with(|c| *c.borrow_mut() = Some(text.as_ref().to_owned()));
+}
+
+/// Returns text currently on the clipboard, if there is any.
+pub fn get_string() -> Option<String> {
+    #[cfg(not(feature = "miri"))]
+    if let Some(clipboard) = system_clipboard() {
+        return clipboard.get_string();
+    }
+
+    LOCAL_CLIPBOARD.with(|c| c.borrow().clone())
+}
+
+#[cfg(not(feature = "miri"))]
+fn system_clipboard() -> Option<druid_shell::Clipboard> {
+    // Application exists only once a native window was connected.
+    if super::text_input::has_window() {
+        Some(druid_shell::Application::global().clipboard())
+    } else {
+        None
+    }
+}
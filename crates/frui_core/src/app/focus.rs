@@ -0,0 +1,561 @@
+//! Keyboard focus.
+//!
+//! Widgets which want to receive keyboard events attach a [`FocusNode`] to
+//! their node in the widget tree (usually through the `Focus` widget). At most
+//! one of those focus nodes has the *primary focus* at a time. Key events are
+//! delivered to it first and then bubble up through focus nodes attached to
+//! its ancestors, until one of them handles the event.
+//!
+//! Focus nodes created with [`FocusNode::scope`] group focus nodes below them.
+//! Tab / Shift+Tab move focus between nodes of the closest enclosing scope
+//! only, and a scope remembers which of its descendants was focused last.
+
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    rc::{Rc, Weak},
+};
+
+use druid_shell::{keyboard_types::Key, KeyEvent, KeyState};
+
+use crate::{api::contexts::build_cx::BuildCx, app::tree::NodeRef};
+
+/// Whether a key event was consumed by a handler, or should bubble up to the
+/// next focus node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyEventResult {
+    Handled,
+    Ignored,
+}
+
+thread_local! {
+    static FOCUS_MANAGER: RefCell<FocusManager> = Default::default();
+}
+
+#[derive(Default)]
+struct FocusManager {
+    /// Focus nodes by widget nodes they are attached to.
+    attached: HashMap<NodeRef, Weak<Inner>>,
+    /// Node which receives key events first.
+    primary_focus: Option<FocusNode>,
+    /// Node which requested focus while being built.
+    autofocus: Option<FocusNode>,
+}
+
+/// Handle to a node of the focus tree.
+///
+/// Focus node is usually created in [`WidgetState::create_state`] and passed
+/// to the `Focus` widget, which attaches it to the widget tree:
+///
+/// ```ignore
+/// Focus::builder()
+///     .focus_node(cx.state().clone())
+///     .on_key(|event| KeyEventResult::Handled)
+///     .child(child)
+/// ```
+///
+/// Cloned handles refer to the same focus node.
+///
+/// [`WidgetState::create_state`]: crate::prelude::WidgetState::create_state
+#[derive(Clone)]
+pub struct FocusNode {
+    inner: Rc<Inner>,
+}
+
+struct Inner {
+    is_scope: bool,
+    /// Widget this focus node is attached to.
+    node: RefCell<Option<NodeRef>>,
+    on_key: Cell<Option<*const dyn Fn(&KeyEvent) -> KeyEventResult>>,
+    on_focus_change: Cell<Option<*const dyn Fn(bool)>>,
+    can_request_focus: Cell<bool>,
+    skip_traversal: Cell<bool>,
+    order: Cell<Option<f64>>,
+    /// Descendant of this scope which was focused last.
+    focused_child: RefCell<Option<Weak<Inner>>>,
+}
+
+impl FocusNode {
+    pub fn new() -> Self {
+        Self::new_(false)
+    }
+
+    /// Creates a focus scope, which limits focus traversal to focus nodes
+    /// below it and restores focus of its last focused descendant when it
+    /// is focused itself.
+    pub fn scope() -> Self {
+        Self::new_(true)
+    }
+
+    fn new_(is_scope: bool) -> Self {
+        FocusNode {
+            inner: Rc::new(Inner {
+                is_scope,
+                node: RefCell::new(None),
+                on_key: Cell::new(None),
+                on_focus_change: Cell::new(None),
+                can_request_focus: Cell::new(true),
+                skip_traversal: Cell::new(false),
+                order: Cell::new(None),
+                focused_child: RefCell::new(None),
+            }),
+        }
+    }
+
+    pub fn is_scope(&self) -> bool {
+        self.inner.is_scope
+    }
+
+    /// Attaches this focus node to the widget of `cx`, which determines its
+    /// position in the focus tree. Replaces previously attached widget.
+    ///
+    /// Widget must [`detach`] it before being unmounted.
+    ///
+    /// [`detach`]: FocusNode::detach
+    pub fn attach<T>(&self, cx: BuildCx<T>) {
+        let node = cx.node_ref();
+
+        self.remove_from_tree();
+
+        FOCUS_MANAGER.with(|manager| {
+            let mut manager = manager.borrow_mut();
+            manager
+                .attached
+                .insert(node.clone(), Rc::downgrade(&self.inner));
+        });
+
+        *self.inner.node.borrow_mut() = Some(node);
+    }
+
+    /// Removes this focus node from the focus tree and clears its handlers.
+    ///
+    /// It keeps the primary focus if it had it, so that a widget reattaching
+    /// the same node after an update stays focused. Key events aren't
+    /// delivered to detached nodes though.
+    pub fn detach(&self) {
+        self.inner.on_key.set(None);
+        self.inner.on_focus_change.set(None);
+        self.remove_from_tree();
+    }
+
+    fn remove_from_tree(&self) {
+        if let Some(node) = self.inner.node.borrow_mut().take() {
+            // Focus manager may be already destroyed if this node is dropped
+            // while the thread is exiting.
+            let _ = FOCUS_MANAGER.try_with(|manager| {
+                manager.borrow_mut().attached.remove(&node);
+            });
+        }
+    }
+
+    pub fn is_attached(&self) -> bool {
+        self.attached_node().is_some()
+    }
+
+    /// Sets callbacks called when this node receives a key event, and when
+    /// it gains or loses focus.
+    ///
+    /// ## Safety:
+    ///
+    /// Values the callbacks point to must live until [`detach`] is called.
+    ///
+    /// [`detach`]: FocusNode::detach
+    pub unsafe fn set_handlers<'a>(
+        &self,
+        on_key: *const (dyn Fn(&KeyEvent) -> KeyEventResult + 'a),
+        on_focus_change: *const (dyn Fn(bool) + 'a),
+    ) {
+        self.inner.on_key.set(Some(std::mem::transmute(on_key)));
+        self.inner
+            .on_focus_change
+            .set(Some(std::mem::transmute(on_focus_change)));
+    }
+
+    /// Whether this node can be focused. Nodes which can't be focused are
+    /// still part of the focus tree, so key events bubble through them.
+    pub fn can_request_focus(&self) -> bool {
+        self.inner.can_request_focus.get()
+    }
+
+    /// Unfocuses this node if it can't be focused anymore.
+    pub fn set_can_request_focus(&self, value: bool) {
+        self.inner.can_request_focus.set(value);
+
+        if !value && self.has_focus() {
+            self.unfocus();
+        }
+    }
+
+    /// Whether this node is skipped by Tab / Shift+Tab traversal. It can still
+    /// be focused with [`request_focus`].
+    ///
+    /// [`request_focus`]: FocusNode::request_focus
+    pub fn skip_traversal(&self) -> bool {
+        self.inner.skip_traversal.get()
+    }
+
+    pub fn set_skip_traversal(&self, value: bool) {
+        self.inner.skip_traversal.set(value);
+    }
+
+    /// Position of this node in the traversal order of its scope.
+    pub fn order(&self) -> Option<f64> {
+        self.inner.order.get()
+    }
+
+    /// Sets position of this node in the traversal order of its scope. Nodes
+    /// with explicit order are visited first, in ascending order, and then
+    /// the rest of them in tree order.
+    pub fn set_order(&self, order: Option<f64>) {
+        self.inner.order.set(order);
+    }
+
+    /// Whether this node has the primary focus.
+    pub fn has_primary_focus(&self) -> bool {
+        primary_focus().as_ref() == Some(self)
+    }
+
+    /// Whether this node or any of its descendants has the primary focus.
+    pub fn has_focus(&self) -> bool {
+        match primary_focus() {
+            Some(focus) => focus.ancestors().any(|node| node == *self),
+            None => false,
+        }
+    }
+
+    /// Gives the primary focus to this node.
+    ///
+    /// Focusing a scope focuses its last focused descendant instead or, if
+    /// there is none, the first descendant in its traversal order.
+    pub fn request_focus(&self) {
+        if self.is_scope() {
+            if let Some(child) = self.focused_child() {
+                return child.request_focus();
+            }
+
+            if let Some(first) = self.traversal_order().into_iter().next() {
+                return first.request_focus();
+            }
+        }
+
+        if self.can_request_focus() {
+            set_primary_focus(Some(self.clone()));
+        }
+    }
+
+    /// Gives the primary focus to this node once the current frame is built,
+    /// unless some other node is focused by then.
+    ///
+    /// Unlike [`request_focus`], this can be called while the widget tree is
+    /// being built (e.g. from [`WidgetState::mount`]).
+    ///
+    /// [`request_focus`]: FocusNode::request_focus
+    /// [`WidgetState::mount`]: crate::prelude::WidgetState::mount
+    pub fn autofocus(&self) {
+        FOCUS_MANAGER.with(|manager| {
+            let mut manager = manager.borrow_mut();
+
+            if manager.autofocus.is_none() {
+                manager.autofocus = Some(self.clone());
+            }
+        });
+    }
+
+    /// Removes the focus from this node and its descendants.
+    pub fn unfocus(&self) {
+        if self.has_focus() {
+            set_primary_focus(None);
+        }
+    }
+
+    /// Moves focus to the next node in the traversal order of the scope of
+    /// this node. Returns `false` if there was no node to move focus to.
+    pub fn next_focus(&self) -> bool {
+        self.move_focus(true)
+    }
+
+    /// Moves focus to the previous node in the traversal order of the scope
+    /// of this node. Returns `false` if there was no node to move focus to.
+    pub fn previous_focus(&self) -> bool {
+        self.move_focus(false)
+    }
+
+    fn move_focus(&self, forward: bool) -> bool {
+        let order = match self.enclosing_scope() {
+            Some(scope) => scope.traversal_order(),
+            None => match self.attached_node() {
+                Some(node) => traversal_order_below(&root_of(&node), true),
+                None => return false,
+            },
+        };
+
+        let next = match order.iter().position(|node| node == self) {
+            Some(n) if forward => order.get((n + 1) % order.len()),
+            Some(n) => order.get((n + order.len() - 1) % order.len()),
+            None if forward => order.first(),
+            None => order.last(),
+        };
+
+        match next {
+            Some(next) => {
+                next.request_focus();
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn attached_node(&self) -> Option<NodeRef> {
+        self.inner
+            .node
+            .borrow()
+            .as_ref()
+            .filter(|node| node.is_alive())
+            .cloned()
+    }
+
+    fn focused_child(&self) -> Option<FocusNode> {
+        let child = self.inner.focused_child.borrow().as_ref()?.upgrade()?;
+        let child = FocusNode { inner: child };
+
+        // Child could have been moved out of this scope.
+        if child.can_request_focus() && child.ancestors().skip(1).any(|node| node == *self) {
+            Some(child)
+        } else {
+            None
+        }
+    }
+
+    /// Focus node attached to the closest ancestor of the widget of this node.
+    pub fn parent(&self) -> Option<FocusNode> {
+        let mut node = self.attached_node()?.parent();
+
+        FOCUS_MANAGER.with(|manager| {
+            let manager = manager.borrow();
+
+            while let Some(current) = node {
+                if let Some(inner) = manager.attached.get(&current).and_then(Weak::upgrade) {
+                    return Some(FocusNode { inner });
+                }
+
+                node = current.parent();
+            }
+
+            None
+        })
+    }
+
+    /// This node followed by its ancestors in the focus tree.
+    fn ancestors(&self) -> impl Iterator<Item = FocusNode> {
+        std::iter::successors(Some(self.clone()), |node| node.parent())
+    }
+
+    /// Closest scope above this node.
+    fn enclosing_scope(&self) -> Option<FocusNode> {
+        self.ancestors().skip(1).find(|node| node.is_scope())
+    }
+
+    /// Focusable nodes of this scope in the order Tab visits them.
+    fn traversal_order(&self) -> Vec<FocusNode> {
+        match self.attached_node() {
+            Some(node) => traversal_order_below(&node, false),
+            None => Vec::new(),
+        }
+    }
+}
+
+impl Default for FocusNode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PartialEq for FocusNode {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl Eq for FocusNode {}
+
+impl std::fmt::Debug for FocusNode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FocusNode")
+            .field("is_scope", &self.is_scope())
+            .field("node", &*self.inner.node.borrow())
+            .finish()
+    }
+}
+
+/// Focusable nodes below `node` in the order Tab visits them. Nested scopes
+/// are visited as a single node.
+fn traversal_order_below(node: &NodeRef, include_node: bool) -> Vec<FocusNode> {
+    let mut nodes = Vec::new();
+
+    FOCUS_MANAGER.with(|manager| {
+        let manager = manager.borrow();
+
+        if include_node {
+            collect_traversable(&manager, node, &mut nodes);
+        } else {
+            for child in node.children() {
+                collect_traversable(&manager, &child, &mut nodes);
+            }
+        }
+    });
+
+    // Stable sort keeps nodes without explicit order in tree order.
+    nodes.sort_by(|a, b| match (a.order(), b.order()) {
+        (Some(a), Some(b)) => a.total_cmp(&b),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => std::cmp::Ordering::Equal,
+    });
+
+    nodes
+}
+
+fn collect_traversable(manager: &FocusManager, node: &NodeRef, nodes: &mut Vec<FocusNode>) {
+    if let Some(inner) = manager.attached.get(node).and_then(Weak::upgrade) {
+        let focus_node = FocusNode { inner };
+
+        if !focus_node.skip_traversal() && focus_node.can_request_focus() {
+            let is_scope = focus_node.is_scope();
+
+            nodes.push(focus_node);
+
+            if is_scope {
+                return;
+            }
+        }
+    }
+
+    for child in node.children() {
+        collect_traversable(manager, &child, nodes);
+    }
+}
+
+fn root_of(node: &NodeRef) -> NodeRef {
+    std::iter::successors(Some(node.clone()), |node| node.parent())
+        .last()
+        .unwrap()
+}
+
+/// Node which currently has the primary focus, if it's attached.
+pub fn primary_focus() -> Option<FocusNode> {
+    FOCUS_MANAGER
+        .with(|manager| manager.borrow().primary_focus.clone())
+        .filter(FocusNode::is_attached)
+}
+
+fn set_primary_focus(focus: Option<FocusNode>) {
+    let old = primary_focus();
+
+    if old == focus {
+        return;
+    }
+
+    let old_path = old
+        .iter()
+        .flat_map(FocusNode::ancestors)
+        .collect::<Vec<_>>();
+    let new_path = focus
+        .iter()
+        .flat_map(FocusNode::ancestors)
+        .collect::<Vec<_>>();
+
+    FOCUS_MANAGER.with(|manager| manager.borrow_mut().primary_focus = focus.clone());
+
+    // Scopes remember their focused descendant.
+    if let Some(focus) = &focus {
+        for scope in new_path.iter().filter(|node| node.is_scope()) {
+            *scope.inner.focused_child.borrow_mut() = Some(Rc::downgrade(&focus.inner));
+        }
+    }
+
+    // Notify nodes which lost or gained focus, starting with the deepest.
+    for node in old_path.iter().filter(|node| !new_path.contains(node)) {
+        node.notify_focus_change(false);
+    }
+
+    for node in new_path.iter().filter(|node| !old_path.contains(node)) {
+        node.notify_focus_change(true);
+    }
+}
+
+impl FocusNode {
+    fn notify_focus_change(&self, has_focus: bool) {
+        if let Some(on_focus_change) = self.inner.on_focus_change.get() {
+            // Safety: `on_focus_change` is valid as ensured by `set_handlers`.
+            unsafe { (*on_focus_change)(has_focus) };
+        }
+    }
+}
+
+/// Focuses the node which requested [`FocusNode::autofocus`], if nothing is
+/// focused yet. Returns `true` if focus changed.
+pub(crate) fn apply_autofocus() -> bool {
+    let node = FOCUS_MANAGER.with(|manager| manager.borrow_mut().autofocus.take());
+
+    match node {
+        Some(node) if node.is_attached() && primary_focus().is_none() => {
+            node.request_focus();
+            primary_focus().is_some()
+        }
+        _ => false,
+    }
+}
+
+/// Delivers key event to the focused node and its ancestors, until one of
+/// them handles it. Unhandled Tab / Shift+Tab presses move focus.
+///
+/// Returns `true` if the event was handled.
+pub(crate) fn dispatch_key_event(event: &KeyEvent) -> bool {
+    let focus = primary_focus();
+
+    for node in focus.iter().flat_map(FocusNode::ancestors) {
+        if let Some(on_key) = node.inner.on_key.get() {
+            // Safety: `on_key` is valid as ensured by `set_handlers`.
+            if unsafe { (*on_key)(event) } == KeyEventResult::Handled {
+                return true;
+            }
+        }
+    }
+
+    if event.state == KeyState::Down && event.key == Key::Tab {
+        return match focus {
+            Some(focus) if event.mods.shift() => focus.previous_focus(),
+            Some(focus) => focus.next_focus(),
+            None => focus_first(event.mods.shift()),
+        };
+    }
+
+    false
+}
+
+/// Focuses the first (or the last) node of the outermost level of the focus
+/// tree, used when Tab is pressed while nothing is focused.
+fn focus_first(last: bool) -> bool {
+    // Any attached node leads us to the root of the widget tree.
+    let node = FOCUS_MANAGER.with(|manager| {
+        let manager = manager.borrow();
+        manager
+            .attached
+            .keys()
+            .find(|node| node.is_alive())
+            .cloned()
+    });
+
+    let order = match node {
+        Some(node) => traversal_order_below(&root_of(&node), true),
+        None => return false,
+    };
+
+    match if last { order.last() } else { order.first() } {
+        Some(node) => {
+            node.request_focus();
+            true
+        }
+        None => false,
+    }
+}
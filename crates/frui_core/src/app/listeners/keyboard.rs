@@ -2,17 +2,34 @@
 
 use std::cell::RefCell;
 
-use druid_shell::KeyEvent;
+use druid_shell::{KeyEvent, Modifiers};
 use slotmap::SlotMap;
 
 slotmap::new_key_type! { pub struct CallbackKey; }
 
-#[derive(Default)]
-pub struct KeyboardEventListeners {
-    callbacks: SlotMap<CallbackKey, *const dyn Fn(KeyEvent)>,
+/// Keyboard event received by raw keyboard listeners, regardless of which
+/// widget is focused.
+#[derive(Debug, Clone)]
+pub enum RawKeyEvent {
+    KeyDown(KeyEvent),
+    KeyUp(KeyEvent),
+    /// Set of pressed modifier keys changed.
+    ModifiersChanged(Modifiers),
 }
 
-impl KeyboardEventListeners {
+pub struct KeyboardEventListeners<E: 'static = KeyEvent> {
+    callbacks: SlotMap<CallbackKey, *const dyn Fn(E)>,
+}
+
+impl<E> Default for KeyboardEventListeners<E> {
+    fn default() -> Self {
+        Self {
+            callbacks: SlotMap::default(),
+        }
+    }
+}
+
+impl<E> KeyboardEventListeners<E> {
     /// Registers a callback which will be called when a keyboard event is received.
     ///
     /// ## Safety:
@@ -22,7 +39,7 @@ impl KeyboardEventListeners {
     /// In other words, you need to remove this callback, by calling [`remove`]
     /// with [`CallbackKey`] returned from this function, before that `callback` is
     /// going to be dropped.
-    pub unsafe fn register<'a>(&mut self, callback: *const (dyn Fn(KeyEvent) + 'a)) -> CallbackKey {
+    pub unsafe fn register<'a>(&mut self, callback: *const (dyn Fn(E) + 'a)) -> CallbackKey {
         self.callbacks.insert(std::mem::transmute(callback))
     }
 
@@ -34,7 +51,7 @@ impl KeyboardEventListeners {
         self.callbacks.len()
     }
 
-    pub(crate) fn iter<'a>(&'a self) -> impl Iterator<Item = &'a dyn Fn(KeyEvent)> {
+    pub(crate) fn iter<'a>(&'a self) -> impl Iterator<Item = &'a dyn Fn(E)> {
         self.callbacks.iter().map(|(_, f)| {
             // Safety: `callback` is valid as ensured by registrars to `KeyboardEventListeners`.
             unsafe { &**f }
@@ -46,4 +63,7 @@ thread_local! {
     /// Todo: Optimize this with something like SlotMap<(&f, PreviousNode, NextNode)> ?
     /// Basically linked tree but with advantages of slot map...?
     pub static KEYBOARD_EVENT_LISTENERS: RefCell<KeyboardEventListeners>  = Default::default();
+
+    /// Listeners of every key press, key release and change of modifiers.
+    pub static RAW_KEYBOARD_EVENT_LISTENERS: RefCell<KeyboardEventListeners<RawKeyEvent>> = Default::default();
 }
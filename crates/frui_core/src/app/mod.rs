@@ -2,8 +2,12 @@ use std::cell::{RefCell, RefMut};
 
 use druid_shell::piet::PietText;
 
+pub mod clipboard;
+pub mod focus;
 pub mod listeners;
 pub mod runner;
+pub mod text_input;
+pub mod ticker;
 pub mod tree;
 
 pub struct TextFactory(RefCell<Option<PietText>>);
@@ -0,0 +1,197 @@
+use std::{any::TypeId, cell::Ref};
+
+use crate::{
+    api::{
+        implementers::WidgetDerive,
+        local_key::{LocalKeyAny, WidgetLocalKey},
+        WidgetUniqueType,
+    },
+    app::tree::NodeRef,
+    render::{Constraints, LayoutCxOS, Offset, Rect, Size},
+};
+
+/// Describes which widgets should be matched by [`TestHarness::find`].
+///
+/// [`TestHarness::find`]: super::TestHarness::find
+pub struct Finder {
+    description: String,
+    predicate: Box<dyn Fn(&WidgetNode) -> bool>,
+}
+
+impl Finder {
+    /// Matches widgets of type `W`, regardless of its generic parameters.
+    ///
+    /// For example `Finder::by_type::<Text<&str>>()` will also match any
+    /// `Text<String>` widgets.
+    pub fn by_type<W: WidgetDerive>() -> Finder {
+        let unique_type = TypeId::of::<W::UniqueTypeId>();
+
+        Finder {
+            description: format!("type `{}`", short_type_name::<W>()),
+            predicate: Box::new(move |node| node.node.widget().unique_type() == unique_type),
+        }
+    }
+
+    /// Matches [`LocalKey`] widgets annotated with `key`.
+    ///
+    /// [`LocalKey`]: crate::prelude::LocalKey
+    pub fn by_key<K: 'static + PartialEq + std::fmt::Debug>(key: K) -> Finder {
+        Finder {
+            description: format!("key `{:?}`", key),
+            predicate: Box::new(move |node| match node.node.widget().local_key() {
+                Some(k) => k == LocalKeyAny::new(&key),
+                None => false,
+            }),
+        }
+    }
+
+    /// Matches widgets for which `predicate` returns `true`.
+    pub fn by_predicate(predicate: impl Fn(&WidgetNode) -> bool + 'static) -> Finder {
+        Finder {
+            description: String::from("predicate"),
+            predicate: Box::new(predicate),
+        }
+    }
+
+    pub fn matches(&self, node: &WidgetNode) -> bool {
+        (self.predicate)(node)
+    }
+}
+
+impl std::fmt::Debug for Finder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Finder({})", self.description)
+    }
+}
+
+/// Handle to a widget mounted in the tree of a [`TestHarness`]. It exposes
+/// the render data computed for that widget during the last frame.
+///
+/// Handle doesn't keep the widget alive. Accessing a widget that was removed
+/// from the tree will panic.
+///
+/// [`TestHarness`]: super::TestHarness
+#[derive(Clone)]
+pub struct WidgetNode {
+    pub(crate) node: NodeRef,
+}
+
+impl WidgetNode {
+    pub(crate) fn new(node: NodeRef) -> Self {
+        Self { node }
+    }
+
+    /// Whether this widget is still mounted in the tree.
+    pub fn is_alive(&self) -> bool {
+        self.node.is_alive()
+    }
+
+    /// Name of the widget type, without its module path and generics.
+    pub fn debug_name(&self) -> &'static str {
+        self.node.debug_name_short()
+    }
+
+    /// Whether this widget is of type `W`, regardless of its generic parameters.
+    pub fn is<W: WidgetDerive>(&self) -> bool {
+        self.node.widget().unique_type() == TypeId::of::<W::UniqueTypeId>()
+    }
+
+    /// Size computed during last layout.
+    pub fn size(&self) -> Size {
+        self.node.borrow().render_data.size
+    }
+
+    /// Incoming constraints received during last layout.
+    pub fn constraints(&self) -> Constraints {
+        self.node.borrow().render_data.constraints
+    }
+
+    /// Distance from the top of this widget to the baseline of its first line
+    /// of text, computed from its last layout.
+    pub fn baseline(&self) -> Option<f64> {
+        LayoutCxOS::new(self.node.clone()).baseline()
+    }
+
+    /// Offset relative to the offset of the parent, received during last paint.
+    pub fn local_offset(&self) -> Offset {
+        self.node.borrow().render_data.local_offset
+    }
+
+    /// Offset relative to the window, received during last paint.
+    ///
+    /// Note that transformations applied directly to the canvas (e.g. by
+    /// `Transform` widget) are not taken into account.
+    pub fn global_offset(&self) -> Offset {
+        let mut offset = self.local_offset();
+        let mut parent = self.parent();
+
+        while let Some(node) = parent {
+            offset = offset + node.local_offset();
+            parent = node.parent();
+        }
+
+        offset
+    }
+
+    /// Rectangle occupied by this widget in window coordinates.
+    pub fn global_rect(&self) -> Rect {
+        let offset = self.global_offset();
+        Rect::from_ltwh(offset.x, offset.y, self.size().width, self.size().height)
+    }
+
+    /// Whether this widget was laid out at least once.
+    pub fn is_laid_out(&self) -> bool {
+        self.node.borrow().render_data.laid_out
+    }
+
+    /// Returns [`WidgetState::State`] of this widget, if it has state of type `S`.
+    ///
+    /// [`WidgetState::State`]: crate::prelude::WidgetState::State
+    pub fn state<S: 'static>(&self) -> Option<Ref<S>> {
+        Ref::filter_map(self.node.borrow(), |node| node.state.downcast_ref::<S>()).ok()
+    }
+
+    pub fn parent(&self) -> Option<WidgetNode> {
+        self.node.parent().map(WidgetNode::new)
+    }
+
+    pub fn children(&self) -> Vec<WidgetNode> {
+        self.node
+            .children()
+            .into_iter()
+            .map(WidgetNode::new)
+            .collect()
+    }
+
+    /// Iterates over this node and all of its descendants in depth-first order.
+    pub fn descendants(&self) -> impl Iterator<Item = WidgetNode> {
+        let mut stack = vec![self.clone()];
+
+        std::iter::from_fn(move || {
+            let node = stack.pop()?;
+            stack.extend(node.children().into_iter().rev());
+            Some(node)
+        })
+    }
+}
+
+impl std::fmt::Debug for WidgetNode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_alive() {
+            f.debug_struct("WidgetNode")
+                .field("widget", &self.debug_name())
+                .field("size", &self.size())
+                .field("local_offset", &self.local_offset())
+                .finish()
+        } else {
+            write!(f, "WidgetNode (removed)")
+        }
+    }
+}
+
+fn short_type_name<T>() -> &'static str {
+    let full_name = std::any::type_name::<T>();
+    let end = full_name.find('<').unwrap_or(full_name.len());
+    let start = full_name[..end].rfind(':').map_or(0, |n| n + 1);
+    &full_name[start..end]
+}
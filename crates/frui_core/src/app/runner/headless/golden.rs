@@ -0,0 +1,412 @@
+//! Golden-image (snapshot) testing for [`TestHarness`].
+//!
+//! Golden images are stored in the `goldens` directory of the crate under
+//! test. To create or update them, run tests with `FRUI_UPDATE_GOLDENS=1`.
+//! When an image doesn't match, the rendered frame and an image highlighting
+//! differing pixels are written to `goldens/failures`.
+//!
+//! [`TestHarness`]: super::TestHarness
+
+use std::{
+    fs::File,
+    io::BufWriter,
+    path::{Path, PathBuf},
+};
+
+use super::TestHarness;
+
+/// Environment variable which, if set, makes [`TestHarness::assert_matches_golden`]
+/// overwrite golden images with the rendered frame instead of comparing them.
+pub const UPDATE_GOLDENS_ENV: &str = "FRUI_UPDATE_GOLDENS";
+
+/// Image with non-premultiplied 8-bit RGBA pixels, stored row by row.
+#[derive(Clone, PartialEq, Eq)]
+pub struct RgbaImage {
+    width: usize,
+    height: usize,
+    pixels: Vec<u8>,
+}
+
+impl RgbaImage {
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![0; width * height * 4],
+        }
+    }
+
+    /// Constructs image from pixels with premultiplied alpha, which is the
+    /// format produced by offscreen render targets.
+    pub(crate) fn from_premultiplied(width: usize, height: usize, mut pixels: Vec<u8>) -> Self {
+        assert_eq!(pixels.len(), width * height * 4);
+
+        for pixel in pixels.chunks_exact_mut(4) {
+            let a = pixel[3] as u32;
+
+            if a != 0 {
+                for c in &mut pixel[..3] {
+                    *c = ((*c as u32 * 255 + a / 2) / a).min(255) as u8;
+                }
+            }
+        }
+
+        Self {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    /// Returns the `[r, g, b, a]` value of pixel at given coordinates.
+    pub fn pixel(&self, x: usize, y: usize) -> [u8; 4] {
+        let i = self.index(x, y);
+        [
+            self.pixels[i],
+            self.pixels[i + 1],
+            self.pixels[i + 2],
+            self.pixels[i + 3],
+        ]
+    }
+
+    pub fn set_pixel(&mut self, x: usize, y: usize, rgba: [u8; 4]) {
+        let i = self.index(x, y);
+        self.pixels[i..i + 4].copy_from_slice(&rgba);
+    }
+
+    pub fn load_png(path: impl AsRef<Path>) -> Result<Self, GoldenError> {
+        let decoder = png::Decoder::new(File::open(path)?);
+        let mut reader = decoder.read_info()?;
+
+        let mut buf = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut buf)?;
+
+        if info.color_type != png::ColorType::Rgba || info.bit_depth != png::BitDepth::Eight {
+            return Err(GoldenError::UnsupportedFormat(
+                info.color_type,
+                info.bit_depth,
+            ));
+        }
+
+        buf.truncate(info.buffer_size());
+
+        Ok(Self {
+            width: info.width as usize,
+            height: info.height as usize,
+            pixels: buf,
+        })
+    }
+
+    pub fn save_png(&self, path: impl AsRef<Path>) -> Result<(), GoldenError> {
+        let file = BufWriter::new(File::create(path)?);
+
+        let mut encoder = png::Encoder::new(file, self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&self.pixels)?;
+
+        Ok(())
+    }
+
+    /// Compares two images of the same size. Returns `None` if `other` has
+    /// different dimensions.
+    pub fn compare(&self, other: &RgbaImage, tolerance: Tolerance) -> Option<ImageDiff> {
+        if self.width != other.width || self.height != other.height {
+            return None;
+        }
+
+        let mut diff_image = RgbaImage::new(self.width, self.height);
+        let mut differing_pixels = 0;
+
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let (a, b) = (self.pixel(x, y), other.pixel(x, y));
+
+                let max_delta = a
+                    .iter()
+                    .zip(b.iter())
+                    .map(|(a, b)| a.abs_diff(*b))
+                    .max()
+                    .unwrap();
+
+                if max_delta > tolerance.channel {
+                    differing_pixels += 1;
+                    diff_image.set_pixel(x, y, [255, 0, 0, 255]);
+                } else {
+                    // Keep a faded version of the image for context.
+                    let luma = (a[0] as u32 * 3 + a[1] as u32 * 6 + a[2] as u32) / 10;
+                    let faded = (255 - (255 - luma) / 4) as u8;
+                    diff_image.set_pixel(x, y, [faded, faded, faded, 255]);
+                }
+            }
+        }
+
+        Some(ImageDiff {
+            differing_pixels,
+            total_pixels: self.width * self.height,
+            tolerance,
+            diff_image,
+        })
+    }
+
+    fn index(&self, x: usize, y: usize) -> usize {
+        assert!(
+            x < self.width && y < self.height,
+            "pixel ({x}, {y}) is out of bounds of {}x{} image",
+            self.width,
+            self.height
+        );
+
+        (y * self.width + x) * 4
+    }
+}
+
+impl std::fmt::Debug for RgbaImage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "RgbaImage({}x{})", self.width, self.height)
+    }
+}
+
+/// How much a rendered frame may differ from a golden image.
+///
+/// Text rendering and anti-aliasing may slightly differ between platforms,
+/// which is why by default some small differences are allowed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tolerance {
+    /// Maximum difference of a single color channel for pixels to be
+    /// considered equal.
+    pub channel: u8,
+    /// Maximum fraction (`0.0..=1.0`) of pixels that may differ.
+    pub pixels: f64,
+}
+
+impl Tolerance {
+    pub const EXACT: Tolerance = Tolerance {
+        channel: 0,
+        pixels: 0.0,
+    };
+}
+
+impl Default for Tolerance {
+    fn default() -> Self {
+        Tolerance {
+            channel: 2,
+            pixels: 0.001,
+        }
+    }
+}
+
+/// Result of [`RgbaImage::compare`].
+#[derive(Debug, Clone)]
+pub struct ImageDiff {
+    pub differing_pixels: usize,
+    pub total_pixels: usize,
+    pub tolerance: Tolerance,
+    /// Image in which differing pixels are marked red.
+    pub diff_image: RgbaImage,
+}
+
+impl ImageDiff {
+    pub fn is_within_tolerance(&self) -> bool {
+        self.differing_pixels as f64 <= self.total_pixels as f64 * self.tolerance.pixels
+    }
+}
+
+#[derive(Debug)]
+pub enum GoldenError {
+    Io(std::io::Error),
+    Decoding(png::DecodingError),
+    Encoding(png::EncodingError),
+    UnsupportedFormat(png::ColorType, png::BitDepth),
+}
+
+impl std::fmt::Display for GoldenError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GoldenError::Io(e) => write!(f, "{e}"),
+            GoldenError::Decoding(e) => write!(f, "couldn't decode png: {e}"),
+            GoldenError::Encoding(e) => write!(f, "couldn't encode png: {e}"),
+            GoldenError::UnsupportedFormat(c, d) => {
+                write!(
+                    f,
+                    "unsupported png format {c:?} ({d:?}), expected 8-bit RGBA"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for GoldenError {}
+
+impl From<std::io::Error> for GoldenError {
+    fn from(e: std::io::Error) -> Self {
+        GoldenError::Io(e)
+    }
+}
+
+impl From<png::DecodingError> for GoldenError {
+    fn from(e: png::DecodingError) -> Self {
+        GoldenError::Decoding(e)
+    }
+}
+
+impl From<png::EncodingError> for GoldenError {
+    fn from(e: png::EncodingError) -> Self {
+        GoldenError::Encoding(e)
+    }
+}
+
+impl TestHarness {
+    /// Compares the last rendered frame with golden image `name` (e.g.
+    /// `"decoration.png"`) using default [`Tolerance`].
+    #[track_caller]
+    pub fn assert_matches_golden(&self, name: &str) {
+        self.assert_matches_golden_with(name, Tolerance::default());
+    }
+
+    /// Compares the last rendered frame with golden image `name`.
+    ///
+    /// If [`UPDATE_GOLDENS_ENV`] is set, the golden image is overwritten
+    /// with the rendered frame instead.
+    #[track_caller]
+    pub fn assert_matches_golden_with(&self, name: &str, tolerance: Tolerance) {
+        let golden_path = goldens_dir().join(name);
+
+        if std::env::var_os(UPDATE_GOLDENS_ENV).is_some() {
+            if let Some(dir) = golden_path.parent() {
+                std::fs::create_dir_all(dir).expect("couldn't create goldens directory");
+            }
+
+            self.frame()
+                .save_png(&golden_path)
+                .unwrap_or_else(|e| panic!("couldn't write {}: {e}", golden_path.display()));
+
+            return;
+        }
+
+        let golden = match RgbaImage::load_png(&golden_path) {
+            Ok(golden) => golden,
+            Err(e) => panic!(
+                "couldn't load golden image {}: {e}\n\
+                 (run tests with {UPDATE_GOLDENS_ENV}=1 to create it)",
+                golden_path.display(),
+            ),
+        };
+
+        let frame = self.frame();
+
+        let diff = match frame.compare(&golden, tolerance) {
+            Some(diff) => diff,
+            None => panic!(
+                "rendered frame ({}x{}) and golden image {} ({}x{}) differ in size",
+                frame.width(),
+                frame.height(),
+                golden_path.display(),
+                golden.width(),
+                golden.height(),
+            ),
+        };
+
+        if !diff.is_within_tolerance() {
+            let failures = goldens_dir().join("failures");
+            let stem = Path::new(name).with_extension("");
+
+            let actual_path = failures.join(stem.with_extension("actual.png"));
+            let diff_path = failures.join(stem.with_extension("diff.png"));
+
+            if let Some(dir) = actual_path.parent() {
+                let _ = std::fs::create_dir_all(dir);
+            }
+
+            let _ = frame.save_png(&actual_path);
+            let _ = diff.diff_image.save_png(&diff_path);
+
+            panic!(
+                "rendered frame doesn't match golden image {}: \
+                 {} of {} pixels differ (tolerance: {:?})\n\
+                 actual: {}\n\
+                 diff: {}",
+                golden_path.display(),
+                diff.differing_pixels,
+                diff.total_pixels,
+                tolerance,
+                actual_path.display(),
+                diff_path.display(),
+            );
+        }
+    }
+}
+
+fn goldens_dir() -> PathBuf {
+    let manifest_dir = std::env::var_os("CARGO_MANIFEST_DIR").unwrap_or_else(|| ".".into());
+    PathBuf::from(manifest_dir).join("goldens")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn filled(width: usize, height: usize, rgba: [u8; 4]) -> RgbaImage {
+        let mut image = RgbaImage::new(width, height);
+
+        for y in 0..height {
+            for x in 0..width {
+                image.set_pixel(x, y, rgba);
+            }
+        }
+
+        image
+    }
+
+    #[test]
+    fn compare_respects_tolerance() {
+        let a = filled(10, 10, [100, 100, 100, 255]);
+        let mut b = filled(10, 10, [102, 100, 100, 255]);
+
+        assert!(a
+            .compare(&b, Tolerance::default())
+            .unwrap()
+            .is_within_tolerance());
+        assert!(!a
+            .compare(&b, Tolerance::EXACT)
+            .unwrap()
+            .is_within_tolerance());
+
+        b.set_pixel(3, 4, [0, 0, 0, 255]);
+
+        let diff = a.compare(&b, Tolerance::default()).unwrap();
+
+        assert_eq!(diff.differing_pixels, 1);
+        assert_eq!(diff.diff_image.pixel(3, 4), [255, 0, 0, 255]);
+        assert!(!diff.is_within_tolerance());
+
+        let loose = Tolerance {
+            channel: 2,
+            pixels: 0.01,
+        };
+
+        assert!(a.compare(&b, loose).unwrap().is_within_tolerance());
+        assert!(a.compare(&filled(10, 9, [0; 4]), loose).is_none());
+    }
+
+    #[test]
+    fn unpremultiplies_pixels() {
+        let image = RgbaImage::from_premultiplied(1, 1, vec![64, 0, 128, 128]);
+
+        assert_eq!(image.pixel(0, 0), [128, 0, 255, 128]);
+    }
+}
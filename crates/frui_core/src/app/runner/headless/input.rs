@@ -0,0 +1,168 @@
+//! Synthetic input for [`TestHarness`].
+//!
+//! Each of the following methods constructs [`MouseEvent`]s / [`KeyEvent`]s
+//! the same way a native window would, passes them to the [`WindowHandler`]
+//! and pumps a frame after every step, so that widgets scheduled for rebuild
+//! by one event are up to date before the next one is dispatched.
+//!
+//! [`WindowHandler`]: super::WindowHandler
+
+use druid_shell::{
+    keyboard_types::Key,
+    kurbo::{Point, Vec2},
+    text::{simulate_input, InputHandler},
+    KeyEvent, KeyState, Modifiers, MouseButton, MouseButtons, MouseEvent,
+};
+
+use crate::app::text_input;
+
+use super::{Finder, FruiWindowHandler, TestHarness};
+
+impl TestHarness {
+    /// Moves the pointer to `pos` without pressing any button.
+    pub fn hover(&mut self, pos: Point) {
+        self.pointer_pos = pos;
+        self.handler
+            .mouse_move(&mouse_event(pos, self.pressed_buttons(), Vec2::ZERO));
+        self.pump();
+    }
+
+    /// Presses the left mouse button at `pos`.
+    pub fn pointer_down(&mut self, pos: Point) {
+        self.hover(pos);
+        self.buttons_down = true;
+        self.handler
+            .mouse_down(&mouse_event(pos, self.pressed_buttons(), Vec2::ZERO));
+        self.pump();
+    }
+
+    /// Releases the left mouse button at `pos`.
+    pub fn pointer_up(&mut self, pos: Point) {
+        self.pointer_pos = pos;
+        self.buttons_down = false;
+        self.handler
+            .mouse_up(&mouse_event(pos, self.pressed_buttons(), Vec2::ZERO));
+        self.pump();
+    }
+
+    /// Clicks the left mouse button at `pos`.
+    pub fn tap_at(&mut self, pos: Point) {
+        self.pointer_down(pos);
+        self.pointer_up(pos);
+    }
+
+    /// Clicks the left mouse button at the center of the only widget matched
+    /// by `finder`.
+    #[track_caller]
+    pub fn tap_widget(&mut self, finder: &Finder) {
+        let center = self.find_one(finder).global_rect().center();
+        self.tap_at(center.into());
+    }
+
+    /// Presses the left mouse button at `from`, moves the pointer to `to` in
+    /// `steps` evenly spaced moves and releases the button.
+    pub fn drag(&mut self, from: Point, to: Point, steps: usize) {
+        self.pointer_down(from);
+
+        let steps = steps.max(1);
+
+        for step in 1..=steps {
+            let t = step as f64 / steps as f64;
+            self.hover(from.lerp(to, t));
+        }
+
+        self.pointer_up(to);
+    }
+
+    /// Scrolls by `delta` at the current position of the pointer.
+    pub fn scroll(&mut self, delta: Vec2) {
+        self.scroll_at(self.pointer_pos, delta);
+    }
+
+    /// Scrolls by `delta` at `pos`.
+    pub fn scroll_at(&mut self, pos: Point, delta: Vec2) {
+        self.hover(pos);
+        self.handler
+            .wheel(&mouse_event(pos, self.pressed_buttons(), delta));
+        self.pump();
+    }
+
+    /// Presses `key` with no modifiers.
+    pub fn press_key(&mut self, key: Key) {
+        self.press_key_with(key, Modifiers::empty());
+    }
+
+    /// Presses `key` while holding `mods`.
+    ///
+    /// Like native windows, if the key isn't handled by any widget and a text
+    /// input is focused, it is delivered to that text input instead.
+    pub fn press_key_with(&mut self, key: Key, mods: Modifiers) {
+        simulate_input(
+            &mut self.handler,
+            text_input::focused(),
+            KeyEvent::for_test(mods, key),
+        );
+        self.pump();
+    }
+
+    /// Releases `key` with no modifiers.
+    pub fn release_key(&mut self, key: Key) {
+        self.release_key_with(key, Modifiers::empty());
+    }
+
+    /// Releases `key` while holding `mods`.
+    pub fn release_key_with(&mut self, key: Key, mods: Modifiers) {
+        let mut event = KeyEvent::for_test(mods, key);
+        event.state = KeyState::Up;
+
+        self.handler.key_up(event);
+        self.pump();
+    }
+
+    /// Edits the focused text input the way an input method would, e.g. to
+    /// compose text.
+    ///
+    /// Panics if no text input is focused.
+    #[track_caller]
+    pub fn edit_text_input(&mut self, edit: impl FnOnce(&mut dyn InputHandler)) {
+        let token = text_input::focused().expect("no text input is focused");
+
+        let mut handler = self.handler.acquire_input_lock(token, true);
+        edit(&mut *handler);
+        drop(handler);
+
+        self.handler.release_input_lock(token);
+        self.pump();
+    }
+
+    /// Types `text` one character at a time.
+    pub fn type_text(&mut self, text: &str) {
+        for char in text.chars() {
+            let mods = match char.is_uppercase() {
+                true => Modifiers::SHIFT,
+                false => Modifiers::empty(),
+            };
+
+            self.press_key_with(Key::Character(char.to_string()), mods);
+        }
+    }
+
+    fn pressed_buttons(&self) -> MouseButtons {
+        match self.buttons_down {
+            true => MouseButtons::new().with(MouseButton::Left),
+            false => MouseButtons::new(),
+        }
+    }
+}
+
+fn mouse_event(pos: Point, buttons: MouseButtons, wheel_delta: Vec2) -> MouseEvent {
+    MouseEvent {
+        pos,
+        buttons,
+        mods: Modifiers::empty(),
+        count: 1,
+        focus: false,
+        button: MouseButton::Left,
+        wheel_delta,
+    }
+}
@@ -0,0 +1,291 @@
+//! Runner which drives [`WindowHandler`] without opening a window.
+//!
+//! Unlike [`MiriRunner`], [`TestHarness`] works in a regular `cargo test` and
+//! gives access to the resulting widget tree, so tests can assert on the
+//! layout of each widget instead of checking it by eye in a `run_app` window.
+//!
+//! ```ignore
+//! let mut harness = TestHarness::new(App);
+//!
+//! let text = harness.find_one(&Finder::by_type::<Text<&str>>());
+//!
+//! assert_eq!(text.size(), Size::new(100., 20.));
+//! assert_eq!(text.global_offset(), Offset::new(200., 190.));
+//! ```
+//!
+//! Each frame is rendered into an offscreen bitmap on the CPU, which can be
+//! inspected with [`TestHarness::frame`] or compared with a golden image using
+//! [`TestHarness::assert_matches_golden`].
+//!
+//! Time in a harness is measured by a fake clock, which advances only when
+//! [`TestHarness::advance_time`] is called, so animations progress exactly
+//! the same way in every run.
+//!
+//! [`MiriRunner`]: super::miri::MiriRunner
+
+use std::time::Duration;
+
+use druid_shell::{
+    kurbo::{Point, Rect},
+    Region,
+};
+
+use crate::{
+    app::{
+        runner::window_handler::NEED_REBUILD,
+        ticker::{self, has_active_tickers},
+        tree::NodeRef,
+    },
+    prelude::Widget,
+    render::Size,
+};
+
+#[cfg(not(feature = "miri"))]
+use crate::app::TEXT_FACTORY;
+#[cfg(not(feature = "miri"))]
+use druid_shell::piet::{Device, ImageFormat, RenderContext};
+
+#[cfg(feature = "miri")]
+use super::Canvas;
+use super::{window_handler::WindowHandler, FruiWindowHandler};
+
+mod finder;
+#[cfg(not(feature = "miri"))]
+mod golden;
+mod input;
+
+pub use finder::{Finder, WidgetNode};
+#[cfg(not(feature = "miri"))]
+pub use golden::{GoldenError, ImageDiff, RgbaImage, Tolerance, UPDATE_GOLDENS_ENV};
+
+/// Maximum number of frames [`TestHarness::pump_until_settled`] will pump
+/// before giving up.
+const MAX_SETTLE_FRAMES: usize = 1000;
+
+/// Time between frames pumped by [`TestHarness::pump_for`] and
+/// [`TestHarness::pump_until_settled`].
+pub const FRAME_INTERVAL: Duration = Duration::from_micros(16_667);
+
+pub struct TestHarness {
+    window_size: Size,
+    handler: WindowHandler,
+    /// Last position of the pointer, used by [`TestHarness::scroll`].
+    pointer_pos: Point,
+    /// Whether left mouse button is currently held down.
+    buttons_down: bool,
+    /// Offscreen device used to create render contexts for each frame.
+    #[cfg(not(feature = "miri"))]
+    device: Device,
+    /// Pixels rendered during the last frame.
+    #[cfg(not(feature = "miri"))]
+    frame: RgbaImage,
+}
+
+impl TestHarness {
+    /// Mounts `widget` in a headless window of size 500x400 and pumps the
+    /// first frame.
+    pub fn new<W: Widget + 'static>(widget: W) -> Self {
+        Self::with_size(widget, Size::new(500., 400.))
+    }
+
+    /// Mounts `widget` in a headless window of given size and pumps the first
+    /// frame.
+    pub fn with_size<W: Widget + 'static>(widget: W, window_size: Size) -> Self {
+        #[cfg(not(feature = "miri"))]
+        let mut device = Device::new().expect("couldn't create offscreen device");
+
+        // Text layouts are created from the text factory of the window, which
+        // here we borrow from an offscreen render context.
+        #[cfg(not(feature = "miri"))]
+        {
+            let mut target = device
+                .bitmap_target(1, 1, 1.0)
+                .expect("couldn't create offscreen bitmap");
+            let mut piet = target.render_context();
+            TEXT_FACTORY.with(|f| f.set(piet.text().clone()));
+            let _ = piet.finish();
+        }
+
+        ticker::use_fake_clock();
+
+        let mut handler = WindowHandler::new(widget);
+
+        handler.mount_root();
+        handler.size(window_size.into());
+
+        let mut this = Self {
+            window_size,
+            handler,
+            pointer_pos: Point::ZERO,
+            buttons_down: false,
+            #[cfg(not(feature = "miri"))]
+            device,
+            #[cfg(not(feature = "miri"))]
+            frame: RgbaImage::new(0, 0),
+        };
+
+        this.pump();
+
+        this
+    }
+
+    /// Current size of the headless window.
+    pub fn window_size(&self) -> Size {
+        self.window_size
+    }
+
+    /// Resizes the headless window and pumps a frame.
+    pub fn set_size(&mut self, size: Size) {
+        self.window_size = size;
+        self.handler.size(size.into());
+        self.pump();
+    }
+
+    /// Pumps a single frame: rebuilds dirty widgets, then lays out and paints
+    /// the whole tree.
+    pub fn pump(&mut self) {
+        let region = full_region(self.window_size);
+
+        #[cfg(not(feature = "miri"))]
+        {
+            let (width, height) = self.pixel_size();
+
+            let mut target = self
+                .device
+                .bitmap_target(width, height, 1.0)
+                .expect("couldn't create offscreen bitmap");
+
+            {
+                let mut piet = target.render_context();
+
+                self.handler.prepare_paint();
+                self.handler.paint(&mut piet, &region);
+
+                if let Err(e) = piet.finish() {
+                    log::error!("finishing offscreen frame failed: {:?}", e);
+                }
+            }
+
+            let mut pixels = vec![0; width * height * 4];
+
+            match target.copy_raw_pixels(ImageFormat::RgbaPremul, &mut pixels) {
+                Ok(_) => self.frame = RgbaImage::from_premultiplied(width, height, pixels),
+                Err(e) => log::error!("reading offscreen frame failed: {:?}", e),
+            }
+        }
+
+        #[cfg(feature = "miri")]
+        {
+            self.handler.prepare_paint();
+            self.handler.paint(&mut Canvas::default(), &region);
+        }
+    }
+
+    /// Advances the clock by `duration` and pumps a single frame.
+    pub fn advance_time(&mut self, duration: Duration) {
+        ticker::advance_fake_clock(duration);
+        self.pump();
+    }
+
+    /// Pumps frames at [`FRAME_INTERVAL`] until `duration` passes.
+    pub fn pump_for(&mut self, duration: Duration) {
+        let mut elapsed = Duration::ZERO;
+
+        while elapsed < duration {
+            let step = FRAME_INTERVAL.min(duration - elapsed);
+            elapsed += step;
+
+            self.advance_time(step);
+        }
+    }
+
+    /// Time elapsed since this harness was created, according to its clock.
+    pub fn elapsed(&self) -> Duration {
+        ticker::now()
+    }
+
+    /// Number of widgets rebuilt during the last frame, counting widgets
+    /// rebuilt again after being laid out once per rebuild.
+    pub fn rebuilds_last_frame(&self) -> usize {
+        self.handler.rebuilds_last_frame()
+    }
+
+    /// Pumps frames until no widget is scheduled for rebuild and all
+    /// animations have finished. Clock advances by [`FRAME_INTERVAL`] every
+    /// frame.
+    ///
+    /// Panics if the tree didn't settle after a reasonable number of frames
+    /// (e.g. because some widget marks itself dirty on every build or an
+    /// animation is repeating).
+    #[track_caller]
+    pub fn pump_until_settled(&mut self) {
+        for _ in 0..MAX_SETTLE_FRAMES {
+            self.advance_time(FRAME_INTERVAL);
+
+            if !has_pending_rebuilds() && !has_active_tickers() {
+                return;
+            }
+        }
+
+        panic!("widget tree didn't settle after {MAX_SETTLE_FRAMES} frames");
+    }
+
+    /// Pixels rendered during the last frame, at scale 1.0.
+    #[cfg(not(feature = "miri"))]
+    pub fn frame(&self) -> &RgbaImage {
+        &self.frame
+    }
+
+    /// Root widget passed to this harness.
+    pub fn root(&self) -> WidgetNode {
+        WidgetNode::new(self.root_node())
+    }
+
+    /// Returns the first node (in depth-first order) matching `finder`.
+    pub fn find(&self, finder: &Finder) -> Option<WidgetNode> {
+        self.root().descendants().find(|n| finder.matches(n))
+    }
+
+    /// Returns all nodes (in depth-first order) matching `finder`.
+    pub fn find_all(&self, finder: &Finder) -> Vec<WidgetNode> {
+        self.root()
+            .descendants()
+            .filter(|n| finder.matches(n))
+            .collect()
+    }
+
+    /// Returns the only node matching `finder`, panicking if there are none
+    /// or more than one of them.
+    #[track_caller]
+    pub fn find_one(&self, finder: &Finder) -> WidgetNode {
+        let mut found = self.find_all(finder);
+
+        match found.len() {
+            1 => found.remove(0),
+            0 => panic!("no widget matched {finder:?}"),
+            n => panic!("expected one widget to match {finder:?}, found {n}"),
+        }
+    }
+
+    fn root_node(&self) -> NodeRef {
+        self.handler.widget_tree().root()
+    }
+
+    #[cfg(not(feature = "miri"))]
+    fn pixel_size(&self) -> (usize, usize) {
+        (
+            (self.window_size.width.ceil() as usize).max(1),
+            (self.window_size.height.ceil() as usize).max(1),
+        )
+    }
+}
+
+fn has_pending_rebuilds() -> bool {
+    NEED_REBUILD.with(|need_rebuild| !need_rebuild.lock().unwrap().is_empty())
+}
+
+fn full_region(window_size: Size) -> Region {
+    let mut region = Region::EMPTY;
+    region.add_rect(Rect::new(0., 0., window_size.width, window_size.height));
+    region
+}
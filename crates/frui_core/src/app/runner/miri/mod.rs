@@ -82,6 +82,10 @@ impl MiriRunner {
         self.handler.key_down(event);
     }
 
+    pub fn key_up(&mut self, event: KeyEvent) {
+        self.handler.key_up(event);
+    }
+
     pub fn size(&mut self, size: druid_shell::kurbo::Size) {
         self.handler.size(size);
         self.update(true);
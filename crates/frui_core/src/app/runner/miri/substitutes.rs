@@ -2,7 +2,8 @@ use std::{borrow::Cow, marker::PhantomData, sync::Mutex};
 
 use druid_shell::{
     piet::{CoreGraphicsImage, CoreGraphicsText, CoreGraphicsTextLayout, IntoBrush, PietText},
-    Cursor, IdleToken,
+    text::Event,
+    Cursor, IdleToken, TextFieldToken,
 };
 
 pub static REQUEST_ANIM_FRAME: Mutex<bool> = Mutex::new(false);
@@ -46,8 +47,20 @@ impl WindowHandle {
 
     pub fn invalidate(&self) {}
 
+    pub fn invalidate_rect(&self, _: druid_shell::kurbo::Rect) {}
+
     pub fn close(&self) {}
 
+    pub fn add_text_field(&self) -> TextFieldToken {
+        TextFieldToken::next()
+    }
+
+    pub fn remove_text_field(&self, _: TextFieldToken) {}
+
+    pub fn set_focused_text_field(&self, _: Option<TextFieldToken>) {}
+
+    pub fn update_text_field(&self, _: TextFieldToken, _: Event) {}
+
     #[track_caller]
     pub fn text(&self) -> PietText {
         todo!()
@@ -1,7 +1,9 @@
-use druid_shell::{IdleToken, KeyEvent, MouseEvent};
+use druid_shell::{text::InputHandler, IdleToken, KeyEvent, MouseEvent, TextFieldToken};
 
 pub mod window_handler;
 
+pub mod headless;
+
 #[cfg(feature = "miri")]
 pub mod miri;
 pub mod native;
@@ -55,5 +57,12 @@ pub trait FruiWindowHandler {
 
     fn key_down(&mut self, event: KeyEvent) -> bool;
 
+    fn key_up(&mut self, event: KeyEvent);
+
+    fn acquire_input_lock(&mut self, token: TextFieldToken, mutable: bool)
+        -> Box<dyn InputHandler>;
+
+    fn release_input_lock(&mut self, token: TextFieldToken);
+
     fn request_close(&mut self);
 }
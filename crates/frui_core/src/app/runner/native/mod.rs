@@ -1,6 +1,6 @@
 use druid_shell::{
-    kurbo::Size, piet::Piet, Application, IdleToken, KeyEvent, MouseEvent, Region, WinHandler,
-    WindowBuilder, WindowHandle,
+    kurbo::Size, piet::Piet, text::InputHandler, Application, IdleToken, KeyEvent, MouseEvent,
+    Region, TextFieldToken, WinHandler, WindowBuilder, WindowHandle,
 };
 use log::LevelFilter;
 use simplelog::{ColorChoice, Config, TermLogger, TerminalMode};
@@ -112,6 +112,22 @@ impl WinHandler for WindowHandler {
         FruiWindowHandler::key_down(self, event)
     }
 
+    fn key_up(&mut self, event: KeyEvent) {
+        FruiWindowHandler::key_up(self, event)
+    }
+
+    fn acquire_input_lock(
+        &mut self,
+        token: TextFieldToken,
+        mutable: bool,
+    ) -> Box<dyn InputHandler> {
+        FruiWindowHandler::acquire_input_lock(self, token, mutable)
+    }
+
+    fn release_input_lock(&mut self, token: TextFieldToken) {
+        FruiWindowHandler::release_input_lock(self, token)
+    }
+
     fn request_close(&mut self) {
         FruiWindowHandler::request_close(self)
     }
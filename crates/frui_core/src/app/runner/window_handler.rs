@@ -1,16 +1,21 @@
-use std::{cell::RefCell, sync::Mutex};
+use std::{cell::RefCell, cmp::Ordering, collections::BinaryHeap, sync::Mutex};
 
 use druid_shell::{
-    kurbo::Rect,
+    kurbo::{BezPath, Shape},
     piet::{Color, RenderContext},
-    Cursor, IdleToken, KeyEvent, MouseEvent,
+    text::InputHandler,
+    Cursor, IdleToken, KeyEvent, KeyState, Modifiers, MouseEvent, TextFieldToken,
 };
 
 use crate::{
     api::{pointer_events::events::PointerEvent, WidgetPtr},
     app::{
-        listeners::keyboard::KEYBOARD_EVENT_LISTENERS,
-        tree::{NodeRef, WidgetTree},
+        focus,
+        listeners::keyboard::{
+            RawKeyEvent, KEYBOARD_EVENT_LISTENERS, RAW_KEYBOARD_EVENT_LISTENERS,
+        },
+        text_input, ticker,
+        tree::{take_rebuild_count, NodeRef, WidgetTree},
         TEXT_FACTORY,
     },
     prelude::Widget,
@@ -27,6 +32,20 @@ thread_local! {
     pub(crate) static NEED_REBUILD: Mutex<Vec<NodeRef>>  = Mutex::new(Vec::with_capacity(100));
 }
 
+thread_local! {
+    /// Relayout boundaries whose descendants need to be laid out again.
+    pub(crate) static NEED_LAYOUT: Mutex<Vec<NodeRef>> = Mutex::new(Vec::new());
+}
+
+thread_local! {
+    /// Repaint boundaries (or the root) whose area needs to be repainted.
+    pub(crate) static NEED_PAINT: Mutex<Vec<NodeRef>> = Mutex::new(Vec::new());
+}
+
+/// Maximal number of times the tree is rebuilt and laid out in one frame. See
+/// [`WindowHandler::update_tree`].
+const MAX_LAYOUT_PASSES: usize = 8;
+
 pub struct WindowHandler {
     /// Current size of main window.
     window_size: Size,
@@ -35,6 +54,14 @@ pub struct WindowHandler {
 
     pending_update: bool,
     widget_tree: WidgetTree,
+    /// Number of widgets rebuilt during the last frame, in all of its layout
+    /// passes.
+    rebuilds_last_frame: usize,
+    /// Whether the whole window needs to be repainted in the next frame
+    /// (e.g. because it was resized).
+    needs_full_repaint: bool,
+    /// Modifier keys pressed during the last key event.
+    modifiers: Modifiers,
 
     /// Temporary field to store root widget before constructing the widget tree
     /// (which requires WindowHandle which can be obtained only after `connect`).
@@ -48,6 +75,9 @@ impl WindowHandler {
             window_handle: WindowHandle::default(),
             pending_update: true,
             widget_tree: WidgetTree::default(),
+            rebuilds_last_frame: 0,
+            needs_full_repaint: true,
+            modifiers: Modifiers::default(),
             root_temp: Some(WidgetPtr::from_owned(Box::new(widget))),
         }
     }
@@ -56,96 +86,238 @@ impl WindowHandler {
     pub fn schedule_update(&mut self) {
         if !self.pending_update {
             self.pending_update = true;
-            self.window_handle.invalidate();
+            // Damaged areas are invalidated in `prepare_paint`.
             self.window_handle.request_anim_frame();
         }
     }
 
-    fn rebuild_dirty(&mut self) {
-        NEED_REBUILD.with(|need_rebuild| {
-            let mut idx = 0;
+    /// Constructs the widget tree from the root widget passed to [`WindowHandler::new`].
+    ///
+    /// This is called by `connect`, but headless runners (which never receive a
+    /// [`WindowHandle`]) call it directly.
+    pub(crate) fn mount_root(&mut self) {
+        let root_widget = std::mem::take(&mut self.root_temp);
+        self.widget_tree = WidgetTree::new(root_widget.expect("root widget was already mounted"));
+    }
 
-            loop {
-                let need_rebuild = need_rebuild.lock().unwrap();
+    pub(crate) fn widget_tree(&self) -> &WidgetTree {
+        &self.widget_tree
+    }
 
-                // We use idx instead of iterator, since every call to `update_subtree` may add
-                // new widgets that need to be rebuilt.
-                if idx == need_rebuild.len() {
-                    break;
-                }
+    /// Number of widgets rebuilt during the last frame. Widgets rebuilt again
+    /// after being laid out (see [`WindowHandler::update_tree`]) are counted
+    /// once per rebuild.
+    pub fn rebuilds_last_frame(&self) -> usize {
+        self.rebuilds_last_frame
+    }
 
-                // Todo: Sort widgets according to their depth, which will allow us to avoid
-                // rebuilding some widgets multiple times. Remember to sort those widgets every
-                // time you update a widget (don't sort if length of vec didn't change).
+    /// Rebuilds dirty widgets and lays out the tree.
+    fn update_tree(&mut self) {
+        self.pending_update = false;
+        self.rebuilds_last_frame = 0;
 
-                // Acquire node reference.
-                let node = need_rebuild[idx].clone();
+        // Widgets which build their children depending on their own layout
+        // (e.g. lazily built lists) mark themselves as dirty during layout, so
+        // we rebuild and lay them out again before the frame is painted.
+        for _ in 0..MAX_LAYOUT_PASSES {
+            self.rebuild_dirty();
 
-                // Drop the lock.
-                drop(need_rebuild);
+            self.widget_tree
+                .layout(Constraints::new_tight(self.window_size));
 
-                if node.is_alive() {
-                    if node.borrow().dirty {
-                        node.update_subtree();
-                    }
-                }
+            if NEED_REBUILD.with(|need_rebuild| need_rebuild.lock().unwrap().is_empty()) {
+                return;
+            }
+        }
+
+        // Some widget keeps marking itself as dirty during layout. We paint the
+        // tree as it is and continue in the next frame.
+        log::warn!(
+            "widget tree is still dirty after {} layout passes, painting it anyway",
+            MAX_LAYOUT_PASSES
+        );
+
+        self.schedule_update();
+    }
 
-                idx += 1;
+    /// Rebuilds dirty widgets, starting with the ones closest to the root.
+    ///
+    /// Rebuilding a widget may rebuild its descendants too. Processing dirty
+    /// widgets in order of their depth ensures that each widget is rebuilt at
+    /// most once per frame, since descendants that were rebuilt by an ancestor
+    /// are no longer dirty when we get to them.
+    fn rebuild_dirty(&mut self) {
+        let mut queue = BinaryHeap::new();
+
+        loop {
+            // Every call to `update_subtree` may mark new widgets as dirty, so
+            // we move those to the queue before picking the next widget.
+            NEED_REBUILD.with(|need_rebuild| {
+                let mut need_rebuild = need_rebuild.lock().unwrap();
+                queue.extend(need_rebuild.drain(..).filter_map(DirtyNode::new));
+            });
+
+            let node = match queue.pop() {
+                Some(DirtyNode { node, .. }) => node,
+                // Widgets are notified about autofocus once they are built,
+                // which may mark more of them as dirty.
+                None if focus::apply_autofocus() => continue,
+                None => break,
+            };
+
+            if node.is_alive() && node.borrow().dirty {
+                node.update_subtree();
             }
+        }
+
+        let rebuilds = take_rebuild_count();
+        self.rebuilds_last_frame += rebuilds;
+
+        log::trace!("rebuilt {} widgets", rebuilds);
+    }
+
+    /// Delivers key event to raw keyboard listeners, preceded by change of
+    /// modifiers if there was one.
+    fn dispatch_raw_key_event(&mut self, event: &KeyEvent) {
+        let mut events = Vec::with_capacity(2);
+
+        if event.mods != self.modifiers {
+            self.modifiers = event.mods;
+            events.push(RawKeyEvent::ModifiersChanged(event.mods));
+        }
+
+        events.push(match event.state {
+            KeyState::Down => RawKeyEvent::KeyDown(event.clone()),
+            KeyState::Up => RawKeyEvent::KeyUp(event.clone()),
+        });
 
-            need_rebuild.lock().unwrap().clear();
+        RAW_KEYBOARD_EVENT_LISTENERS.with(|listeners| {
+            for event in events {
+                for listener in listeners.borrow().iter() {
+                    listener(event.clone());
+                }
+            }
         });
     }
 }
 
+/// Dirty node ordered by its depth in a way that makes [`BinaryHeap`] pop the
+/// shallowest node first.
+struct DirtyNode {
+    depth: usize,
+    node: NodeRef,
+}
+
+impl DirtyNode {
+    fn new(node: NodeRef) -> Option<Self> {
+        if node.is_alive() {
+            Some(DirtyNode {
+                depth: node.depth(),
+                node,
+            })
+        } else {
+            None
+        }
+    }
+}
+
+impl PartialEq for DirtyNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.depth == other.depth
+    }
+}
+
+impl Eq for DirtyNode {}
+
+impl PartialOrd for DirtyNode {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for DirtyNode {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.depth.cmp(&self.depth)
+    }
+}
+
 impl FruiWindowHandler for WindowHandler {
     fn connect(&mut self, handle: &WindowHandle) {
         APP_HANDLE.with(|r| *r.borrow_mut() = Some(handle.get_idle_handle().unwrap()));
+        text_input::set_window(handle);
 
         if !cfg!(feature = "miri") {
             TEXT_FACTORY.with(|f| f.set(self.window_handle.text()));
         }
 
-        let root_widget = std::mem::take(&mut self.root_temp);
-        self.widget_tree = WidgetTree::new(root_widget.unwrap());
+        self.mount_root();
         self.window_handle = handle.clone();
 
         self.window_handle.set_cursor(&Cursor::Arrow);
     }
 
     fn prepare_paint(&mut self) {
-        self.window_handle.invalidate();
+        // Advance animations before the tree is updated, so that widgets they
+        // marked as dirty are rebuilt in this frame.
+        ticker::tick();
+
+        self.update_tree();
+
+        // Invalidate only areas of the window that changed.
+        match self.widget_tree.take_damage() {
+            Some(rects) if !self.needs_full_repaint => {
+                for rect in rects {
+                    self.window_handle.invalidate_rect(rect);
+                }
+            }
+            _ => self.window_handle.invalidate(),
+        }
+
+        self.needs_full_repaint = false;
     }
 
-    fn paint(&mut self, piet: &mut Canvas, _invalid: &druid_shell::Region) {
+    fn paint(&mut self, piet: &mut Canvas, invalid: &druid_shell::Region) {
+        // Tree may have changed since `prepare_paint` was called.
+        self.update_tree();
+
+        let _ = piet.save();
+
         //
-        // Fill screen with one color (temp).
+        // Paint only within the invalid region.
 
-        let size = self.window_size;
-        let rect = Rect::new(0., 0., size.width, size.height);
-        let brush = &piet.solid_brush(Color::from_hex_str("#202324").unwrap());
+        let mut clip = BezPath::new();
 
-        druid_shell::piet::RenderContext::fill(piet, rect, brush);
+        for rect in invalid.rects() {
+            clip.extend(rect.path_elements(0.1));
+        }
+
+        druid_shell::piet::RenderContext::clip(piet, clip);
 
         //
-        // Rebuild widget tree.
+        // Fill invalid region with one color (temp).
 
-        self.pending_update = false;
-        self.rebuild_dirty();
+        let brush = &piet.solid_brush(Color::from_hex_str("#202324").unwrap());
+
+        for rect in invalid.rects() {
+            druid_shell::piet::RenderContext::fill(piet, *rect, brush);
+        }
 
         //
-        // Layout & Paint
+        // Paint
 
-        // Todo: Optimize layout.
-        self.widget_tree
-            .layout(Constraints::new_tight(self.window_size));
+        self.widget_tree.paint(piet, invalid.bounding_box());
+
+        let _ = piet.restore();
 
-        // Todo: Optimize paint.
-        self.widget_tree.paint(piet);
+        // Keep requesting frames while animations are running.
+        if ticker::has_active_tickers() {
+            self.schedule_update();
+        }
     }
 
     fn size(&mut self, size: druid_shell::kurbo::Size) {
         self.window_size = size.into();
+        self.needs_full_repaint = true;
     }
 
     fn idle(&mut self, _token: IdleToken) {
@@ -185,6 +357,20 @@ impl FruiWindowHandler for WindowHandler {
     }
 
     fn key_down(&mut self, event: KeyEvent) -> bool {
+        self.dispatch_raw_key_event(&event);
+
+        if focus::dispatch_key_event(&event) {
+            return true;
+        }
+
+        // Let the platform deliver the key to the focused text input, either
+        // through the input method or as an editing action.
+        if text_input::focused().is_some() {
+            return false;
+        }
+
+        // Events not handled by the focus tree are broadcast to global
+        // listeners (see `KeyboardEventDetector`).
         KEYBOARD_EVENT_LISTENERS.with(|listeners| {
             for listener in listeners.borrow_mut().iter() {
                 listener(event.clone());
@@ -194,6 +380,22 @@ impl FruiWindowHandler for WindowHandler {
         true
     }
 
+    fn key_up(&mut self, event: KeyEvent) {
+        self.dispatch_raw_key_event(&event);
+
+        focus::dispatch_key_event(&event);
+    }
+
+    fn acquire_input_lock(
+        &mut self,
+        token: TextFieldToken,
+        _mutable: bool,
+    ) -> Box<dyn InputHandler> {
+        text_input::input_handler(token).expect("text input was already removed")
+    }
+
+    fn release_input_lock(&mut self, _token: TextFieldToken) {}
+
     fn request_close(&mut self) {
         self.window_handle.close();
     }
@@ -0,0 +1,195 @@
+//! Text input through the platform's input method (IME).
+//!
+//! Input methods compose text (e.g. Japanese or Chinese characters) by
+//! querying and editing the text of the focused text field directly, instead
+//! of sending key events. Text-entry widgets expose their text to them by
+//! implementing [`InputHandler`] and registering it with a [`TextInput`].
+//!
+//! Key events which aren't handled by the focused widget are delivered to the
+//! focused text input as well, as insertions and editing [`Action`]s.
+
+use std::{
+    borrow::Cow,
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    ops::Range,
+    rc::Rc,
+};
+
+use druid_shell::{
+    kurbo::{Point, Rect},
+    piet::HitTestPoint,
+    text::{Action, Affinity, Event, InputHandler, Selection},
+    TextFieldToken,
+};
+
+use super::runner::WindowHandle;
+
+thread_local! {
+    static TEXT_INPUTS: RefCell<HashMap<TextFieldToken, Rc<RefCell<dyn InputHandler>>>> =
+        Default::default();
+
+    static FOCUSED: Cell<Option<TextFieldToken>> = Cell::new(None);
+
+    /// Window text inputs are registered with. Headless runners have none.
+    static WINDOW: RefCell<Option<WindowHandle>> = RefCell::new(None);
+}
+
+pub(crate) fn set_window(handle: &WindowHandle) {
+    WINDOW.with(|window| *window.borrow_mut() = Some(handle.clone()));
+}
+
+/// Whether text inputs are registered with a native window.
+pub(crate) fn has_window() -> bool {
+    WINDOW.with(|window| window.borrow().is_some())
+}
+
+fn with_window(f: impl FnOnce(&WindowHandle)) {
+    // Window may be already destroyed if text input is dropped while the
+    // thread is exiting.
+    let _ = WINDOW.try_with(|window| {
+        if let Some(window) = &*window.borrow() {
+            f(window);
+        }
+    });
+}
+
+/// Token of the focused text input, if any.
+pub fn focused() -> Option<TextFieldToken> {
+    FOCUSED.with(|focused| focused.get())
+}
+
+/// Returns handler of text input of given `token` (see [`InputHandler`]).
+pub(crate) fn input_handler(token: TextFieldToken) -> Option<Box<dyn InputHandler>> {
+    TEXT_INPUTS.with(|inputs| {
+        let handler = inputs.borrow().get(&token)?.clone();
+        Some(Box::new(SharedInputHandler(handler)) as Box<dyn InputHandler>)
+    })
+}
+
+/// Text field registered with the platform's input method.
+///
+/// Text-entry widget usually creates it in [`WidgetState::create_state`] and
+/// calls [`focus`] / [`unfocus`] when it gains or loses focus. Text input is
+/// unregistered when dropped.
+///
+/// [`WidgetState::create_state`]: crate::prelude::WidgetState::create_state
+/// [`focus`]: TextInput::focus
+/// [`unfocus`]: TextInput::unfocus
+pub struct TextInput {
+    token: TextFieldToken,
+}
+
+impl TextInput {
+    /// Registers `handler`, through which input method reads and edits the
+    /// text of this text field.
+    pub fn new(handler: Rc<RefCell<dyn InputHandler>>) -> Self {
+        let mut token = None;
+
+        with_window(|window| token = Some(window.add_text_field()));
+
+        let token = token.unwrap_or_else(TextFieldToken::next);
+
+        TEXT_INPUTS.with(|inputs| inputs.borrow_mut().insert(token, handler));
+
+        TextInput { token }
+    }
+
+    pub fn token(&self) -> TextFieldToken {
+        self.token
+    }
+
+    pub fn is_focused(&self) -> bool {
+        focused() == Some(self.token)
+    }
+
+    /// Directs input method and unhandled key events to this text input.
+    pub fn focus(&self) {
+        FOCUSED.with(|focused| focused.set(Some(self.token)));
+        with_window(|window| window.set_focused_text_field(Some(self.token)));
+    }
+
+    pub fn unfocus(&self) {
+        if self.is_focused() {
+            FOCUSED.with(|focused| focused.set(None));
+            with_window(|window| window.set_focused_text_field(None));
+        }
+    }
+
+    /// Notifies input method that text, selection or layout of this text
+    /// field changed other than through the [`InputHandler`].
+    pub fn update(&self, event: Event) {
+        with_window(|window| window.update_text_field(self.token, event));
+    }
+}
+
+impl Drop for TextInput {
+    fn drop(&mut self) {
+        let _ = FOCUSED.try_with(|focused| {
+            if focused.get() == Some(self.token) {
+                focused.set(None);
+            }
+        });
+
+        let _ = TEXT_INPUTS.try_with(|inputs| inputs.borrow_mut().remove(&self.token));
+
+        with_window(|window| window.remove_text_field(self.token));
+    }
+}
+
+/// [`InputHandler`] passed to the platform, which has to be `'static`.
+struct SharedInputHandler(Rc<RefCell<dyn InputHandler>>);
+
+impl InputHandler for SharedInputHandler {
+    fn selection(&self) -> Selection {
+        self.0.borrow().selection()
+    }
+
+    fn set_selection(&mut self, selection: Selection) {
+        self.0.borrow_mut().set_selection(selection)
+    }
+
+    fn composition_range(&self) -> Option<Range<usize>> {
+        self.0.borrow().composition_range()
+    }
+
+    fn set_composition_range(&mut self, range: Option<Range<usize>>) {
+        self.0.borrow_mut().set_composition_range(range)
+    }
+
+    fn is_char_boundary(&self, i: usize) -> bool {
+        self.0.borrow().is_char_boundary(i)
+    }
+
+    fn len(&self) -> usize {
+        self.0.borrow().len()
+    }
+
+    fn slice(&self, range: Range<usize>) -> Cow<str> {
+        Cow::Owned(self.0.borrow().slice(range).into_owned())
+    }
+
+    fn replace_range(&mut self, range: Range<usize>, text: &str) {
+        self.0.borrow_mut().replace_range(range, text)
+    }
+
+    fn hit_test_point(&self, point: Point) -> HitTestPoint {
+        self.0.borrow().hit_test_point(point)
+    }
+
+    fn line_range(&self, index: usize, affinity: Affinity) -> Range<usize> {
+        self.0.borrow().line_range(index, affinity)
+    }
+
+    fn bounding_box(&self) -> Option<Rect> {
+        self.0.borrow().bounding_box()
+    }
+
+    fn slice_bounding_box(&self, range: Range<usize>) -> Option<Rect> {
+        self.0.borrow().slice_bounding_box(range)
+    }
+
+    fn handle_action(&mut self, action: Action) {
+        self.0.borrow_mut().handle_action(action)
+    }
+}
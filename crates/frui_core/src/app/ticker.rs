@@ -0,0 +1,177 @@
+//! Tickers call registered callbacks once per frame for as long as they are
+//! active. They are what drives animations.
+//!
+//! Time of each frame is read from a per-thread clock. In tests that clock
+//! can be replaced with a fake one (see [`use_fake_clock`]), which only moves
+//! forward when told to, making animations fully deterministic.
+
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+use druid_shell::IdleToken;
+use slotmap::SlotMap;
+
+use super::runner::window_handler::APP_HANDLE;
+
+slotmap::new_key_type! { pub struct TickerKey; }
+
+#[derive(Default)]
+pub struct Tickers {
+    callbacks: SlotMap<TickerKey, Rc<dyn Fn(Duration)>>,
+}
+
+impl Tickers {
+    /// Registers a callback which will be called with the current time at the
+    /// beginning of every frame, until [`unregister`] is called.
+    ///
+    /// [`unregister`]: Tickers::unregister
+    pub fn register(&mut self, callback: Rc<dyn Fn(Duration)>) -> TickerKey {
+        self.callbacks.insert(callback)
+    }
+
+    pub fn unregister(&mut self, key: TickerKey) {
+        self.callbacks.remove(key);
+    }
+
+    pub fn len(&self) -> usize {
+        self.callbacks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.callbacks.is_empty()
+    }
+}
+
+thread_local! {
+    pub static TICKERS: RefCell<Tickers> = Default::default();
+
+    static CLOCK: RefCell<Clock> = RefCell::new(Clock::Real(Instant::now()));
+}
+
+/// Calls every registered ticker with the current time.
+pub(crate) fn tick() {
+    let now = now();
+
+    // Callbacks may (un)register tickers, so we can't hold the borrow.
+    let callbacks = TICKERS.with(|tickers| {
+        let tickers = tickers.borrow();
+        tickers.callbacks.values().cloned().collect::<Vec<_>>()
+    });
+
+    for callback in callbacks {
+        callback(now);
+    }
+}
+
+/// Whether any ticker is active, meaning that another frame is needed.
+pub(crate) fn has_active_tickers() -> bool {
+    TICKERS.with(|tickers| !tickers.borrow().is_empty())
+}
+
+/// Requests a new frame from the window, in which tickers will be called.
+fn request_frame() {
+    APP_HANDLE.with(|handle| {
+        // Headless runners (see `TestHarness`) have no handle and pump
+        // frames manually instead.
+        if let Some(handle) = handle.borrow_mut().as_mut() {
+            handle.schedule_idle(IdleToken::new(0));
+        }
+    });
+}
+
+//
+// Clock
+
+enum Clock {
+    Real(Instant),
+    Fake(Duration),
+}
+
+/// Time elapsed since the clock of this thread was started.
+pub fn now() -> Duration {
+    CLOCK.with(|clock| match &*clock.borrow() {
+        Clock::Real(start) => start.elapsed(),
+        Clock::Fake(now) => *now,
+    })
+}
+
+/// Replaces the clock of this thread with a fake one starting at zero, which
+/// only advances when [`advance_fake_clock`] is called.
+pub fn use_fake_clock() {
+    CLOCK.with(|clock| *clock.borrow_mut() = Clock::Fake(Duration::ZERO));
+}
+
+/// Advances the fake clock of this thread by `duration`.
+///
+/// Panics if [`use_fake_clock`] wasn't called first.
+#[track_caller]
+pub fn advance_fake_clock(duration: Duration) {
+    CLOCK.with(|clock| match &mut *clock.borrow_mut() {
+        Clock::Fake(now) => *now += duration,
+        Clock::Real(_) => panic!("fake clock is not in use, call `use_fake_clock` first"),
+    })
+}
+
+//
+// Ticker
+
+/// Calls a callback once per frame, with time elapsed since the ticker was
+/// started, while it's active.
+///
+/// Elapsed time is measured from the moment [`Ticker::start`] was called.
+/// Tickers are usually started during build, after tickers of that frame were
+/// already called, and measuring from the next frame instead would delay them
+/// by one frame.
+pub struct Ticker {
+    callback: Rc<dyn Fn(Duration)>,
+    key: Cell<Option<TickerKey>>,
+}
+
+impl Ticker {
+    pub fn new(callback: impl Fn(Duration) + 'static) -> Self {
+        Ticker {
+            callback: Rc::new(callback),
+            key: Cell::new(None),
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.key.get().is_some()
+    }
+
+    /// Starts calling the callback every frame. Restarts the ticker if it is
+    /// already active.
+    pub fn start(&self) {
+        self.stop();
+
+        let started_at = now();
+        let callback = self.callback.clone();
+
+        let key = TICKERS.with(|tickers| {
+            tickers.borrow_mut().register(Rc::new(move |now: Duration| {
+                callback(now.saturating_sub(started_at))
+            }))
+        });
+
+        self.key.set(Some(key));
+
+        request_frame();
+    }
+
+    pub fn stop(&self) {
+        if let Some(key) = self.key.take() {
+            // Registry may be already destroyed if this ticker is dropped
+            // while the thread is exiting.
+            let _ = TICKERS.try_with(|tickers| tickers.borrow_mut().unregister(key));
+        }
+    }
+}
+
+impl Drop for Ticker {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
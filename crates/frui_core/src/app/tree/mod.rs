@@ -10,19 +10,35 @@ use druid_shell::IdleToken;
 
 use crate::{
     api::{
-        contexts::{render::LayoutCxOS, RawBuildCx},
+        contexts::{
+            render::{begin_frame, Layer, LayoutCxOS},
+            RawBuildCx,
+        },
         pointer_events::events::PointerEvent,
         IntoWidgetPtr, WidgetPtr,
     },
-    app::runner::window_handler::{APP_HANDLE, NEED_REBUILD},
+    app::runner::window_handler::{APP_HANDLE, NEED_LAYOUT, NEED_PAINT, NEED_REBUILD},
     macro_exports::{PaintCxOS, RawWidget},
     render::{Canvas, Constraints, Offset, Size},
 };
 
+use druid_shell::kurbo::Rect;
+
 use self::pointer_handler::PointerHandler;
 
 pub mod pointer_handler;
 
+thread_local! {
+    /// Number of calls to [`NodeRef::rebuild`] since last call to
+    /// [`take_rebuild_count`].
+    static REBUILD_COUNT: Cell<usize> = Cell::new(0);
+}
+
+/// Returns the number of widgets rebuilt since the last call to this function.
+pub(crate) fn take_rebuild_count() -> usize {
+    REBUILD_COUNT.with(|count| count.replace(0))
+}
+
 pub(crate) struct WidgetTree {
     /// Node containing necessary configuration to support [`InheritedWidget`].
     /// Child of this node is the `root_node`.
@@ -51,16 +67,93 @@ impl WidgetTree {
 
     pub fn layout(&mut self, constraints: Constraints) {
         LayoutCxOS::new(self.root_node.clone()).layout(constraints);
+
+        // Lay out relayout boundaries that weren't reached from the root,
+        // starting with the ones closest to the root, since laying out those
+        // may lay out boundaries below them.
+        loop {
+            let mut boundaries = NEED_LAYOUT.with(|need_layout| {
+                std::mem::take(&mut *need_layout.lock().unwrap())
+            });
+
+            if boundaries.is_empty() {
+                break;
+            }
+
+            boundaries.retain(|node| node.is_alive());
+            boundaries.sort_by_key(|node| node.depth());
+
+            for node in boundaries {
+                let (needs_layout, constraints, old_size) = {
+                    let render_data = &node.borrow().render_data;
+                    (
+                        render_data.needs_layout && render_data.laid_out,
+                        render_data.constraints,
+                        render_data.size,
+                    )
+                };
+
+                if !needs_layout {
+                    continue;
+                }
+
+                let new_size = LayoutCxOS::new(node.clone()).layout(constraints);
+
+                if new_size != old_size {
+                    if let Some(parent) = node.parent() {
+                        parent.mark_needs_layout();
+                    }
+                }
+            }
+        }
     }
 
-    pub fn paint(&mut self, piet: &mut Canvas) {
+    /// Paints the tree. Repaint boundaries which don't intersect `invalid`
+    /// (in window coordinates) and have valid layers are skipped.
+    pub fn paint(&mut self, piet: &mut Canvas, invalid: Rect) {
+        begin_frame(piet, invalid);
         PaintCxOS::new(self.root_node.clone()).paint(piet, &Offset::default());
     }
 
+    /// Returns areas of the window which need to be repainted, since nodes in
+    /// them changed since last call to this function. Returns `None` if the
+    /// whole window needs to be repainted.
+    pub fn take_damage(&mut self) -> Option<Vec<Rect>> {
+        let damaged = NEED_PAINT.with(|need_paint| std::mem::take(&mut *need_paint.lock().unwrap()));
+
+        // Root, unless it is a repaint boundary itself, is scheduled only once
+        // until damage is taken (see `mark_needs_paint`).
+        {
+            let render_data = &mut self.root_node.borrow_mut().render_data;
+
+            if !render_data.repaint_boundary {
+                render_data.needs_paint = false;
+            }
+        }
+
+        let mut rects = Vec::with_capacity(damaged.len());
+
+        for node in damaged.into_iter().filter(|node| node.is_alive()) {
+            let render_data = &node.borrow().render_data;
+
+            match (&render_data.layer, render_data.repaint_boundary) {
+                (Some(layer), true) => rects.push(layer.bounds),
+                // Root of the tree, which isn't a repaint boundary.
+                _ => return None,
+            }
+        }
+
+        Some(rects)
+    }
+
     pub fn handle_pointer_event(&mut self, event: PointerEvent) {
         self.pointer_handler
             .handle_pointer_event(self.root_node.clone(), event)
     }
+
+    pub fn root(&self) -> NodeRef {
+        self.root_node.clone()
+    }
 }
 
 impl Drop for WidgetTree {
@@ -81,6 +174,9 @@ pub(crate) struct NodeInner {
     widget_ptr: WidgetPtr<'static>,
     parent: Option<NodeRef>,
     children: Vec<NodeRef>,
+    /// Number of ancestors of this node. Used to rebuild dirty nodes starting
+    /// from the ones closest to the root.
+    depth: usize,
 
     pub dirty: bool,
     pub state: Box<dyn Any>,
@@ -98,12 +194,15 @@ impl Node {
         let widget_ptr =
             unsafe { std::mem::transmute::<WidgetPtr, WidgetPtr<'static>>(widget.clone()) };
 
+        let depth = parent.as_ref().map_or(0, |p| p.depth() + 1);
+
         let node = Box::into_raw(Box::new(Node {
             inner: RefCell::new(NodeInner {
                 is_alive: Rc::new(Cell::new(std::ptr::null_mut())),
                 widget_ptr,
                 parent,
                 children: Vec::new(),
+                depth,
                 dirty: false,
                 state: widget.raw().create_state(),
                 render_data: RenderData::new(widget.raw()),
@@ -205,6 +304,18 @@ impl NodeRef {
         self.borrow().children.clone()
     }
 
+    #[track_caller]
+    pub(crate) fn parent(&self) -> Option<NodeRef> {
+        assert!(self.is_alive());
+        self.borrow().parent.clone()
+    }
+
+    #[track_caller]
+    pub(crate) fn depth(&self) -> usize {
+        assert!(self.is_alive());
+        self.borrow().depth
+    }
+
     #[allow(unused)]
     #[track_caller]
     pub fn debug_name_short(&self) -> &'static str {
@@ -219,15 +330,27 @@ impl NodeRef {
     pub fn update_subtree(&self) {
         assert!(self.is_alive());
 
-        // Todo: In the calling function, check that given WidgetNode of passed
-        // `widget` is indeed `dirty`, as it may have been already updated by
-        // previous call to this function.
+        self.mark_needs_layout();
+        self.mark_needs_paint();
+        self.rebuild();
+    }
+
+    /// Rebuilds this node and updates its children. Returns `true` if any of
+    /// the children was created, dropped or moved.
+    fn rebuild(&self) -> bool {
+        // Callers skip nodes which are no longer dirty, since those have been
+        // already rebuilt by one of their ancestors.
         self.borrow_mut().dirty = false;
 
+        REBUILD_COUNT.with(|count| count.set(count.get() + 1));
+
         let inherited_ancestor = &self.borrow().inheritance.inherited_ancestor(self);
 
-        let mut old_children = std::mem::take(&mut self.borrow_mut().children)
-            .into_iter()
+        let children_before = std::mem::take(&mut self.borrow_mut().children);
+
+        let mut old_children = children_before
+            .iter()
+            .cloned()
             .map(|c| Some(c))
             .collect::<Vec<_>>();
 
@@ -284,8 +407,12 @@ impl NodeRef {
             }
         }
 
+        let children_changed = new_children != children_before;
+
         // Update children keys.
         self.borrow_mut().children = new_children;
+
+        children_changed
     }
 
     pub fn update(&self, new_widget: WidgetPtr) -> NodeRef {
@@ -339,23 +466,64 @@ impl NodeRef {
                 //
                 // Old pointer is still alive (and didn't move, since it is either boxed or
                 // it points to a value in ascenstor widget), so it is fine to unmount/drop
-                // children of this widget (in `update_subtree` below).
+                // children of this widget (in `rebuild` below).
                 let old_widget_ptr =
                     std::mem::replace(&mut self.borrow_mut().widget_ptr, new_widget);
 
+                // Widgets which provide parent data (e.g. flex factor of `Flexible`)
+                // configure how their parent lays them out, so that parent needs to
+                // be laid out again, even if this node is a relayout boundary.
+                let parent_data = self.widget().create_parent_data();
+
+                if !parent_data.is::<()>() {
+                    self.borrow_mut().render_data.parent_data = parent_data;
+
+                    let parent = self.borrow().parent.clone();
+
+                    if let Some(parent) = parent {
+                        parent.mark_needs_layout();
+                    }
+                }
+
+                // Mount updated widget. Same as newly created widgets, it is mounted
+                // before it is built.
+                self.mount();
+
+                // State of an inherited widget may depend on its configuration, so
+                // widgets depending on it need to be rebuilt, even if their own
+                // configurations didn't change.
+                if self.borrow().widget_ptr.is_inherited_widget() {
+                    self.mark_dependent_widgets_as_dirty();
+                }
+
+                // Render widgets may skip layout (or painting) if their new configuration
+                // lays them out (or paints them) the same way, unless their children were
+                // created or dropped. Layout marks the node as needing paint by itself.
+                let relayout = self
+                    .widget()
+                    .relayout_after_update(old_widget_ptr.as_any_ext());
+
+                if relayout {
+                    self.mark_needs_layout();
+                } else if self
+                    .widget()
+                    .repaint_after_update(old_widget_ptr.as_any_ext())
+                {
+                    self.mark_needs_paint();
+                }
+
                 // Update descendants of this node, stopping at equal widget configurations
                 // or a leaf node.
-                self.update_subtree();
+                if self.rebuild() && !relayout {
+                    self.mark_needs_layout();
+                }
 
                 // Safety:
                 //
                 // There are no children widgets referencing old pointer (because we dropped
-                // them all in `update_subtree` above), so we can safely drop it.
+                // them all in `rebuild` above), so we can safely drop it.
                 unsafe { WidgetPtr::drop(&old_widget_ptr) };
 
-                // Mount updated widget.
-                self.mount();
-
                 return self.clone();
             }
         } else {
@@ -451,11 +619,11 @@ impl NodeRef {
         assert!(self.is_alive());
 
         APP_HANDLE.with(|handle| {
-            handle
-                .borrow_mut()
-                .as_mut()
-                .expect("APP_HANDLE wasn't set")
-                .schedule_idle(IdleToken::new(0));
+            // Headless runners (see `TestHarness`) have no handle and pump
+            // frames manually instead.
+            if let Some(handle) = handle.borrow_mut().as_mut() {
+                handle.schedule_idle(IdleToken::new(0));
+            }
         });
 
         if !self.borrow().dirty {
@@ -467,6 +635,110 @@ impl NodeRef {
         }
     }
 
+    /// Marks this node as needing layout, along with its ancestors up to the
+    /// closest relayout boundary. That boundary is then laid out again in the
+    /// next frame using constraints it received last time.
+    ///
+    /// Node is a relayout boundary if it received tight constraints (so its
+    /// size can't change) or if it was explicitly marked as one during layout
+    /// (see [`LayoutCxOS::mark_relayout_boundary`]). If size of a boundary
+    /// changes anyway, its parent is laid out as well.
+    pub fn mark_needs_layout(&self) {
+        assert!(self.is_alive());
+
+        let mut node = self.clone();
+
+        loop {
+            let (was_marked, is_boundary, parent) = {
+                let mut inner = node.borrow_mut();
+                let render_data = &mut inner.render_data;
+
+                // Nodes which were never laid out need layout from the start,
+                // without their ancestors being marked.
+                let was_marked =
+                    std::mem::replace(&mut render_data.needs_layout, true) && render_data.laid_out;
+                let is_boundary = render_data.relayout_boundary;
+
+                (was_marked, is_boundary, inner.parent.clone())
+            };
+
+            // Ancestors of a laid out node that already needs layout were
+            // marked (or scheduled) as well.
+            if was_marked {
+                return;
+            }
+
+            match parent {
+                Some(parent) if !is_boundary => node = parent,
+                _ => {
+                    NEED_LAYOUT.with(|need_layout| {
+                        need_layout.lock().unwrap().push(node);
+                    });
+
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Invalidates [`Layer`] of the closest repaint boundary containing this
+    /// node (and of boundaries containing that boundary), and schedules area
+    /// of that boundary to be repainted.
+    pub fn mark_needs_paint(&self) {
+        assert!(self.is_alive());
+
+        let mut node = self.clone();
+        let mut damaged = None;
+
+        loop {
+            let (is_boundary, was_marked, parent) = {
+                let mut inner = node.borrow_mut();
+                let render_data = &mut inner.render_data;
+
+                let is_boundary = render_data.repaint_boundary;
+                let was_marked =
+                    is_boundary && std::mem::replace(&mut render_data.needs_paint, true);
+
+                (is_boundary, was_marked, inner.parent.clone())
+            };
+
+            if is_boundary {
+                // Boundaries containing an already marked boundary were marked
+                // too, and its area was already scheduled for repaint.
+                if was_marked {
+                    break;
+                }
+
+                damaged.get_or_insert_with(|| node.clone());
+            }
+
+            match parent {
+                Some(parent) => node = parent,
+                None => {
+                    // Without an enclosing boundary, the whole window must be
+                    // repainted, which is signalled by scheduling the root
+                    // (once, `needs_paint` is reset in `take_damage`).
+                    if damaged.is_none() {
+                        let mut inner = node.borrow_mut();
+
+                        if !std::mem::replace(&mut inner.render_data.needs_paint, true) {
+                            drop(inner);
+                            damaged = Some(node);
+                        }
+                    }
+
+                    break;
+                }
+            }
+        }
+
+        if let Some(damaged) = damaged {
+            NEED_PAINT.with(|need_paint| {
+                need_paint.lock().unwrap().push(damaged);
+            });
+        }
+    }
+
     pub fn mark_dependent_widgets_as_dirty(&self) {
         assert!(self.is_alive());
 
@@ -566,6 +838,20 @@ pub(crate) struct RenderData {
     /// Whether child was laid out. Used to display an error message when
     /// painting children without laying them out beforehand.
     pub laid_out: bool,
+    /// Whether layout computed last time is no longer valid, even if this
+    /// node receives the same constraints.
+    pub needs_layout: bool,
+    /// Whether this node can be laid out again without laying out its parent.
+    /// See [`NodeRef::mark_needs_layout`].
+    pub relayout_boundary: bool,
+
+    /// Whether this node paints in its own [`Layer`]. See
+    /// [`PaintCxOS::paint_layer`].
+    pub repaint_boundary: bool,
+    /// Whether [`Layer`] of this repaint boundary is no longer valid.
+    pub needs_paint: bool,
+    /// Painted output cached by a repaint boundary.
+    pub layer: Option<Layer>,
 }
 
 impl RenderData {
@@ -577,6 +863,11 @@ impl RenderData {
             local_offset: Offset::default(),
             constraints: Constraints::default(),
             laid_out: false,
+            needs_layout: true,
+            relayout_boundary: false,
+            repaint_boundary: false,
+            needs_paint: true,
+            layer: None,
         }
     }
 }
@@ -705,6 +996,7 @@ impl Node {
                 widget_ptr: widget_ptr.clone(),
                 parent: None,
                 children: Vec::new(),
+                depth: 0,
                 dirty: false,
                 state: widget_ptr.raw().create_state(),
                 render_data: RenderData::new(widget_ptr.raw()),
@@ -11,15 +11,21 @@ pub mod app;
 pub mod prelude {
     pub use super::{
         api::{
+            animation::{AnimationController, AnimationStatus, Curve, Curves, Tween},
             contexts::build_cx::{
                 BuildCx, InheritedState, InheritedStateRef, InheritedStateRefMut, WidgetState,
             },
             implementers::{inherited::InheritedWidget, view::ViewWidget},
             impls::BoxedWidget,
             pointer_events::*,
+            scrolling::ScrollController,
+            text_editing::TextEditingController,
             Widget,
         },
-        app::runner::native::run_app,
+        app::{
+            focus::{FocusNode, KeyEventResult},
+            runner::native::run_app,
+        },
     };
 
     pub use crate::render::{Offset, Size};
@@ -33,6 +39,36 @@ pub mod prelude {
     pub use super::api::local_key::LocalKey;
 }
 
+pub mod animation {
+    pub use crate::api::animation::*;
+    pub use crate::app::ticker::Ticker;
+}
+
+pub mod clipboard {
+    pub use crate::app::clipboard::*;
+}
+
+pub mod focus {
+    pub use crate::app::focus::*;
+}
+
+pub mod scrolling {
+    pub use crate::api::scrolling::*;
+}
+
+pub mod text_input {
+    pub use crate::api::text_editing::*;
+    pub use crate::app::text_input::*;
+
+    pub use druid_shell::{
+        piet::HitTestPoint,
+        text::{
+            Action, Affinity, Direction, Event, InputHandler, Movement, Selection, VerticalMovement,
+        },
+        TextFieldToken,
+    };
+}
+
 pub mod render {
     pub use crate::api::implementers::render::RenderWidget;
 
@@ -63,7 +99,7 @@ pub mod macro_exports {
             WidgetPtr,
         },
         prelude::Widget,
-        render::{Canvas, Constraints, Offset, Size},
+        render::{Canvas, Constraints, IntrinsicDimension, Offset, Size},
     };
 }
 
@@ -12,7 +12,7 @@ pub fn impl_raw_widget(item: &ItemStruct, widget_kind: WidgetKind) -> TokenStrea
         Vec, TypeId,
         RawWidget, WidgetPtr,
         RawBuildCx, LayoutCxOS, PaintCxOS, Canvas, 
-        Size, Offset, Constraints, 
+        Size, Offset, Constraints, IntrinsicDimension,
     } = imports_impl_widget_os();
 
     let Target = &item.ident;
@@ -32,6 +32,19 @@ pub fn impl_raw_widget(item: &ItemStruct, widget_kind: WidgetKind) -> TokenStrea
                 <Self as #WidgetKindOS>::paint(self, cx, canvas, offset)
             }
 
+            fn baseline(&self, cx: #LayoutCxOS) -> Option<f64> {
+                <Self as #WidgetKindOS>::baseline(self, cx)
+            }
+
+            fn intrinsic_size(
+                &self,
+                cx: #LayoutCxOS,
+                dimension: #IntrinsicDimension,
+                extent: f64,
+            ) -> f64 {
+                <Self as #WidgetKindOS>::intrinsic_size(self, cx, dimension, extent)
+            }
+
             fn inherited_key(&self) -> Option<#TypeId> {
                 <Self as #WidgetKindOS>::inherited_key(self)
             }
@@ -65,6 +78,7 @@ struct Imports {
     Size: TokenStream,
     Offset: TokenStream,
     Constraints: TokenStream,
+    IntrinsicDimension: TokenStream,
 }
 
 fn imports_impl_widget_os() -> Imports {
@@ -82,5 +96,6 @@ fn imports_impl_widget_os() -> Imports {
         Size: quote!(#exports::Size),
         Offset: quote!(#exports::Offset),
         Constraints: quote!(#exports::Constraints),
+        IntrinsicDimension: quote!(#exports::IntrinsicDimension),
     }
 }
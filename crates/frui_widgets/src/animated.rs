@@ -0,0 +1,343 @@
+//! Implicitly animated variants of basic widgets.
+//!
+//! Each of these widgets animates from its previous configuration to the new
+//! one over `duration` whenever it is rebuilt with different properties, so
+//! e.g. changing `color` of an [`AnimatedContainer`] fades between colors
+//! instead of switching them immediately.
+
+use std::{cell::RefCell, time::Duration};
+
+use frui::{
+    animation::{AnimationController, Curve, Curves, Lerp, Tween},
+    prelude::*,
+    render::Affine,
+};
+
+use crate::{
+    Align, Alignment, BoxDecoration, Container, DecoratedBox, DecorationPosition,
+    DefaultBoxDecoration, Directional, Opacity, TextDirection, Transform,
+};
+
+/// Duration of implicit animations, unless specified otherwise.
+pub const DEFAULT_ANIMATION_DURATION: Duration = Duration::from_millis(200);
+
+/// State of an implicitly animated widget, which animates a value of type `T`
+/// every time its target changes.
+///
+/// Widget using it should [`attach`] it to itself when mounted, and call
+/// [`animate`] with its current configuration every time it's built.
+///
+/// [`attach`]: ImplicitAnimation::attach
+/// [`animate`]: ImplicitAnimation::animate
+pub struct ImplicitAnimation<T> {
+    controller: AnimationController,
+    tween: RefCell<Option<Tween<T>>>,
+}
+
+impl<T: Lerp + Clone + PartialEq> ImplicitAnimation<T> {
+    pub fn new() -> Self {
+        ImplicitAnimation {
+            controller: AnimationController::new(Duration::ZERO),
+            tween: RefCell::new(None),
+        }
+    }
+
+    pub fn controller(&self) -> &AnimationController {
+        &self.controller
+    }
+
+    /// Rebuilds the widget of `cx` on every frame of the animation.
+    pub fn attach<W>(&self, cx: BuildCx<W>) {
+        self.controller.attach(cx);
+    }
+
+    /// Returns the value for the current frame.
+    ///
+    /// If `target` differs from the target of the last call, a new animation
+    /// is started from the current value towards `target`. The first call
+    /// returns `target` right away.
+    pub fn animate(&self, target: &T, duration: Duration, curve: &dyn Curve) -> T {
+        let mut tween = self.tween.borrow_mut();
+        let tween = tween.get_or_insert_with(|| Tween::new(target.clone(), target.clone()));
+
+        if &tween.end != target {
+            if duration.is_zero() {
+                self.controller.stop();
+                *tween = Tween::new(target.clone(), target.clone());
+            } else {
+                let current = tween.transform(curve.transform(self.controller.value()));
+                *tween = Tween::new(current, target.clone());
+
+                self.controller.set_duration(duration);
+                self.controller.forward_from(0.0);
+            }
+        }
+
+        tween.transform(curve.transform(self.controller.value()))
+    }
+}
+
+impl<T: Lerp + Clone + PartialEq> Default for ImplicitAnimation<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// [`Container`] which animates changes of its size and color.
+#[derive(ViewWidget, Builder)]
+pub struct AnimatedContainer<W: Widget> {
+    pub child: W,
+    pub width: Option<f64>,
+    pub height: Option<f64>,
+    pub color: Option<Color>,
+    pub duration: Duration,
+    pub curve: &'static dyn Curve,
+}
+
+impl AnimatedContainer<()> {
+    pub fn builder() -> Self {
+        AnimatedContainer {
+            child: (),
+            width: None,
+            height: None,
+            color: None,
+            duration: DEFAULT_ANIMATION_DURATION,
+            curve: &Curves::LINEAR,
+        }
+    }
+}
+
+impl<W: Widget> WidgetState for AnimatedContainer<W> {
+    type State = ImplicitAnimation<(Option<f64>, Option<f64>, Option<Color>)>;
+
+    fn create_state(&self) -> Self::State {
+        ImplicitAnimation::new()
+    }
+
+    fn mount<'a>(&'a self, cx: BuildCx<'a, Self>) {
+        cx.state().attach(cx);
+    }
+}
+
+impl<W: Widget> ViewWidget for AnimatedContainer<W> {
+    fn build<'w>(&'w self, cx: BuildCx<'w, Self>) -> Self::Widget<'w> {
+        let target = (self.width, self.height, self.color.clone());
+        let (width, height, color) = cx.state().animate(&target, self.duration, self.curve);
+
+        // Curves may overshoot.
+        Container {
+            child: &self.child,
+            width: width.map(|w| w.max(0.0)),
+            height: height.map(|h| h.max(0.0)),
+            color,
+        }
+    }
+}
+
+/// [`Align`] which animates changes of its alignment and size factors.
+#[derive(ViewWidget, Builder)]
+pub struct AnimatedAlign<
+    T: Widget,
+    A: Directional<Output = Alignment> + Lerp + Clone + PartialEq + 'static,
+> {
+    pub child: T,
+    pub alignment: A,
+    pub width_factor: Option<f64>,
+    pub height_factor: Option<f64>,
+    pub text_direction: Option<TextDirection>,
+    pub duration: Duration,
+    pub curve: &'static dyn Curve,
+}
+
+impl AnimatedAlign<(), Alignment> {
+    pub fn builder() -> Self {
+        AnimatedAlign {
+            child: (),
+            alignment: Alignment::default(),
+            width_factor: None,
+            height_factor: None,
+            text_direction: None,
+            duration: DEFAULT_ANIMATION_DURATION,
+            curve: &Curves::LINEAR,
+        }
+    }
+}
+
+impl<T, A> WidgetState for AnimatedAlign<T, A>
+where
+    T: Widget,
+    A: Directional<Output = Alignment> + Lerp + Clone + PartialEq + 'static,
+{
+    type State = ImplicitAnimation<(A, Option<f64>, Option<f64>)>;
+
+    fn create_state(&self) -> Self::State {
+        ImplicitAnimation::new()
+    }
+
+    fn mount<'a>(&'a self, cx: BuildCx<'a, Self>) {
+        cx.state().attach(cx);
+    }
+}
+
+impl<T, A> ViewWidget for AnimatedAlign<T, A>
+where
+    T: Widget,
+    A: Directional<Output = Alignment> + Lerp + Clone + PartialEq + 'static,
+{
+    fn build<'w>(&'w self, cx: BuildCx<'w, Self>) -> Self::Widget<'w> {
+        let target = (
+            self.alignment.clone(),
+            self.width_factor,
+            self.height_factor,
+        );
+        let (alignment, width_factor, height_factor) =
+            cx.state().animate(&target, self.duration, self.curve);
+
+        Align {
+            child: &self.child,
+            alignment,
+            widgh_factor: width_factor.map(|f| f.max(0.0)),
+            height_factor: height_factor.map(|f| f.max(0.0)),
+            text_direction: self.text_direction,
+        }
+    }
+}
+
+/// [`DecoratedBox`] which animates changes of its decoration.
+///
+/// See [`BoxDecoration`]'s implementation of [`Lerp`] for how decorations
+/// are interpolated.
+#[derive(ViewWidget, Builder)]
+pub struct AnimatedDecoratedBox<W: Widget> {
+    pub child: W,
+    pub decoration: DefaultBoxDecoration,
+    pub position: DecorationPosition,
+    pub duration: Duration,
+    pub curve: &'static dyn Curve,
+}
+
+impl AnimatedDecoratedBox<()> {
+    pub fn builder() -> Self {
+        AnimatedDecoratedBox {
+            child: (),
+            decoration: BoxDecoration::builder(),
+            position: DecorationPosition::Background,
+            duration: DEFAULT_ANIMATION_DURATION,
+            curve: &Curves::LINEAR,
+        }
+    }
+}
+
+impl<W: Widget> WidgetState for AnimatedDecoratedBox<W> {
+    type State = ImplicitAnimation<DefaultBoxDecoration>;
+
+    fn create_state(&self) -> Self::State {
+        ImplicitAnimation::new()
+    }
+
+    fn mount<'a>(&'a self, cx: BuildCx<'a, Self>) {
+        cx.state().attach(cx);
+    }
+}
+
+impl<W: Widget> ViewWidget for AnimatedDecoratedBox<W> {
+    fn build<'w>(&'w self, cx: BuildCx<'w, Self>) -> Self::Widget<'w> {
+        let decoration = cx
+            .state()
+            .animate(&self.decoration, self.duration, self.curve);
+
+        DecoratedBox {
+            child: &self.child,
+            decoration,
+            position: self.position,
+        }
+    }
+}
+
+/// [`Transform`] which animates changes of its transformation.
+///
+/// Coefficients of the transformation matrix are interpolated separately,
+/// which works well for translations and scales, but not for rotations.
+#[derive(ViewWidget, Builder)]
+pub struct AnimatedTransform<W: Widget> {
+    pub child: W,
+    pub transform: Affine,
+    pub duration: Duration,
+    pub curve: &'static dyn Curve,
+}
+
+impl AnimatedTransform<()> {
+    pub fn builder() -> Self {
+        AnimatedTransform {
+            child: (),
+            transform: Affine::IDENTITY,
+            duration: DEFAULT_ANIMATION_DURATION,
+            curve: &Curves::LINEAR,
+        }
+    }
+}
+
+impl<W: Widget> WidgetState for AnimatedTransform<W> {
+    type State = ImplicitAnimation<Affine>;
+
+    fn create_state(&self) -> Self::State {
+        ImplicitAnimation::new()
+    }
+
+    fn mount<'a>(&'a self, cx: BuildCx<'a, Self>) {
+        cx.state().attach(cx);
+    }
+}
+
+impl<W: Widget> ViewWidget for AnimatedTransform<W> {
+    fn build<'w>(&'w self, cx: BuildCx<'w, Self>) -> Self::Widget<'w> {
+        let transform = cx
+            .state()
+            .animate(&self.transform, self.duration, self.curve);
+
+        Transform(transform, &self.child)
+    }
+}
+
+/// [`Opacity`] which animates changes of its opacity.
+#[derive(ViewWidget, Builder)]
+pub struct AnimatedOpacity<W: Widget> {
+    pub child: W,
+    pub opacity: f64,
+    pub duration: Duration,
+    pub curve: &'static dyn Curve,
+}
+
+impl AnimatedOpacity<()> {
+    pub fn builder() -> Self {
+        AnimatedOpacity {
+            child: (),
+            opacity: 1.0,
+            duration: DEFAULT_ANIMATION_DURATION,
+            curve: &Curves::LINEAR,
+        }
+    }
+}
+
+impl<W: Widget> WidgetState for AnimatedOpacity<W> {
+    type State = ImplicitAnimation<f64>;
+
+    fn create_state(&self) -> Self::State {
+        ImplicitAnimation::new()
+    }
+
+    fn mount<'a>(&'a self, cx: BuildCx<'a, Self>) {
+        cx.state().attach(cx);
+    }
+}
+
+impl<W: Widget> ViewWidget for AnimatedOpacity<W> {
+    fn build<'w>(&'w self, cx: BuildCx<'w, Self>) -> Self::Widget<'w> {
+        let opacity = cx.state().animate(&self.opacity, self.duration, self.curve);
+
+        Opacity {
+            opacity: opacity.clamp(0.0, 1.0),
+            child: &self.child,
+        }
+    }
+}
@@ -39,6 +39,10 @@ impl<T: Widget> WidgetState for Directionality<T> {
     fn create_state(&self) -> Self::State {
         self.direction
     }
+
+    fn mount<'a>(&'a self, cx: BuildCx<'a, Self>) {
+        *cx.state_mut() = self.direction;
+    }
 }
 
 impl<T: Widget> InheritedWidget for Directionality<T> {
@@ -133,6 +137,10 @@ where
             .offset;
         cx.child(0).paint(canvas, &(child_offset + *offset))
     }
+
+    fn baseline(&self, cx: &LayoutCx<Self>) -> Option<f64> {
+        child_baseline(cx)
+    }
 }
 
 #[derive(RenderWidget, Builder)]
@@ -179,4 +187,35 @@ where
             .offset;
         cx.child(0).paint(canvas, &(*offset + child_offset))
     }
+
+    fn baseline(&self, cx: &LayoutCx<Self>) -> Option<f64> {
+        child_baseline(cx)
+    }
+
+    fn intrinsic_size(
+        &self,
+        cx: &LayoutCx<Self>,
+        dimension: IntrinsicDimension,
+        extent: f64,
+    ) -> f64 {
+        let text_direction = Directionality::of_or_default(cx);
+        let padding = self.padding.resolve(&text_direction);
+
+        let (along, across) = match dimension.is_width() {
+            true => (padding.horizontal(), padding.vertical()),
+            false => (padding.vertical(), padding.horizontal()),
+        };
+
+        cx.child(0)
+            .intrinsic_size(dimension, (extent - across).max(0.))
+            + along
+    }
+}
+
+/// Baseline of the only child, moved by its offset stored in [`BoxLayoutData`].
+fn child_baseline<T>(cx: &LayoutCx<T>) -> Option<f64> {
+    let child = cx.child(0);
+    let offset = child.try_parent_data::<BoxLayoutData>()?.offset;
+
+    Some(child.baseline()? + offset.y)
 }
@@ -17,6 +17,19 @@ impl<T: Widget> ParentData for ConstrainedBox<T> {
     }
 }
 
+/// Returns intrinsic size of a widget which applies `constraints` to its child
+/// with intrinsic size `child_size`.
+pub(crate) fn constrain_intrinsic(
+    constraints: Constraints,
+    dimension: IntrinsicDimension,
+    child_size: f64,
+) -> f64 {
+    match dimension.is_width() {
+        true => constraints.constrain_width(child_size),
+        false => constraints.constrain_height(child_size),
+    }
+}
+
 impl<T: Widget> RenderWidget for ConstrainedBox<T> {
     fn build<'w>(&'w self, _cx: BuildCx<'w, Self>) -> Vec<Self::Widget<'w>> {
         vec![&self.child]
@@ -36,6 +49,21 @@ impl<T: Widget> RenderWidget for ConstrainedBox<T> {
     fn paint(&self, cx: &mut PaintCx<Self>, canvas: &mut Canvas, offset: &Offset) {
         cx.child(0).paint(canvas, offset)
     }
+
+    fn baseline(&self, cx: &LayoutCx<Self>) -> Option<f64> {
+        cx.child(0).baseline()
+    }
+
+    fn intrinsic_size(
+        &self,
+        cx: &LayoutCx<Self>,
+        dimension: IntrinsicDimension,
+        extent: f64,
+    ) -> f64 {
+        let child_size = cx.child(0).intrinsic_size(dimension, extent);
+
+        constrain_intrinsic(self.constraints, dimension, child_size)
+    }
 }
 
 #[derive(RenderWidget, Builder)]
@@ -68,6 +96,10 @@ impl<T: Widget> RenderWidget for UnconstrainedBox<T> {
     fn paint(&self, cx: &mut PaintCx<Self>, canvas: &mut Canvas, offset: &Offset) {
         cx.child(0).paint(canvas, offset)
     }
+
+    fn baseline(&self, cx: &LayoutCx<Self>) -> Option<f64> {
+        cx.child(0).baseline()
+    }
 }
 pub struct SizedBox;
 
@@ -124,6 +156,10 @@ impl<T: Widget> RenderWidget for ColoredBox<T> {
         canvas.fill(druid_shell::piet::kurbo::Rect::from(rect), brush);
         cx.child(0).paint(canvas, offset)
     }
+
+    fn baseline(&self, cx: &LayoutCx<Self>) -> Option<f64> {
+        cx.child(0).baseline()
+    }
 }
 
 #[derive(RenderWidget, Builder)]
@@ -175,4 +211,262 @@ impl<T: Widget> RenderWidget for LimitedBox<T> {
     fn paint(&self, cx: &mut PaintCx<Self>, canvas: &mut Canvas, offset: &Offset) {
         cx.child(0).paint(canvas, offset)
     }
+
+    fn baseline(&self, cx: &LayoutCx<Self>) -> Option<f64> {
+        cx.child(0).baseline()
+    }
+}
+
+/// Lays out its child with the incoming constraints, but makes changes in the
+/// child's subtree lay out only this subtree instead of also laying out its
+/// ancestors.
+///
+/// Useful to wrap frequently changing widgets, which are placed in large
+/// layouts whose size doesn't depend on them.
+#[derive(RenderWidget)]
+pub struct RelayoutBoundary<T: Widget> {
+    pub child: T,
+}
+
+impl<T: Widget> RelayoutBoundary<T> {
+    pub fn child(child: T) -> Self {
+        RelayoutBoundary { child }
+    }
+}
+
+impl<T: Widget> RenderWidget for RelayoutBoundary<T> {
+    fn build<'w>(&'w self, _: BuildCx<'w, Self>) -> Vec<Self::Widget<'w>> {
+        vec![&self.child]
+    }
+
+    fn layout(&self, cx: &LayoutCx<Self>, constraints: Constraints) -> Size {
+        cx.mark_relayout_boundary();
+        cx.child(0).layout(constraints)
+    }
+
+    fn paint(&self, cx: &mut PaintCx<Self>, canvas: &mut Canvas, offset: &Offset) {
+        cx.child(0).paint(canvas, offset)
+    }
+
+    fn baseline(&self, cx: &LayoutCx<Self>) -> Option<f64> {
+        cx.child(0).baseline()
+    }
+}
+
+/// Paints its child into a separate layer, which is cached and reused until
+/// the child's subtree changes.
+///
+/// Changes in the child's subtree repaint only the area of this widget, and
+/// changes outside of it don't repaint the child. Useful to separate
+/// frequently changing widgets (like animations) from the rest of the tree.
+///
+/// Painting of the child is clipped to the size of this widget.
+#[derive(RenderWidget)]
+pub struct RepaintBoundary<T: Widget> {
+    pub child: T,
+}
+
+impl<T: Widget> RepaintBoundary<T> {
+    pub fn child(child: T) -> Self {
+        RepaintBoundary { child }
+    }
+}
+
+impl<T: Widget> RenderWidget for RepaintBoundary<T> {
+    fn build<'w>(&'w self, _: BuildCx<'w, Self>) -> Vec<Self::Widget<'w>> {
+        vec![&self.child]
+    }
+
+    fn layout(&self, cx: &LayoutCx<Self>, constraints: Constraints) -> Size {
+        cx.child(0).layout(constraints)
+    }
+
+    fn paint(&self, cx: &mut PaintCx<Self>, canvas: &mut Canvas, offset: &Offset) {
+        cx.paint_layer(canvas, offset, |cx, canvas| {
+            cx.child(0).paint(canvas, offset)
+        })
+    }
+
+    fn baseline(&self, cx: &LayoutCx<Self>) -> Option<f64> {
+        cx.child(0).baseline()
+    }
+}
+
+impl<T: Widget> RenderUpdate for RepaintBoundary<T> {
+    // Boundary itself has nothing to lay out or paint, so its layer is only
+    // invalidated by changes in the child's subtree.
+
+    fn should_relayout(&self, _: &Self) -> bool {
+        false
+    }
+
+    fn should_repaint(&self, _: &Self) -> bool {
+        false
+    }
+}
+
+/// Paints its child partially transparent.
+///
+/// Opacity between `0.0` and `1.0` requires painting the child into an
+/// offscreen bitmap first, which is relatively expensive. Child isn't painted
+/// at all if opacity is `0.0`.
+#[derive(RenderWidget)]
+pub struct Opacity<T: Widget> {
+    pub opacity: f64,
+    pub child: T,
+}
+
+impl<T: Widget> RenderWidget for Opacity<T> {
+    fn build<'w>(&'w self, _: BuildCx<'w, Self>) -> Vec<Self::Widget<'w>> {
+        vec![&self.child]
+    }
+
+    fn layout(&self, cx: &LayoutCx<Self>, constraints: Constraints) -> Size {
+        cx.child(0).layout(constraints)
+    }
+
+    fn paint(&self, cx: &mut PaintCx<Self>, canvas: &mut Canvas, offset: &Offset) {
+        cx.paint_with_opacity(canvas, offset, self.opacity, |cx, canvas| {
+            cx.child(0).paint(canvas, offset)
+        })
+    }
+
+    fn baseline(&self, cx: &LayoutCx<Self>) -> Option<f64> {
+        cx.child(0).baseline()
+    }
+}
+
+/// Sizes its child to the child's maximal intrinsic width.
+///
+/// Useful when the width of a child would otherwise be unbounded or too
+/// large, e.g. to make children of a [`Column`] with stretched cross axis as
+/// wide as the widest of them. This is relatively expensive, since intrinsic
+/// width of the child is computed before every layout.
+///
+/// [`Column`]: crate::Column
+#[derive(RenderWidget)]
+pub struct IntrinsicWidth<T: Widget> {
+    pub child: T,
+}
+
+impl<T: Widget> IntrinsicWidth<T> {
+    pub fn child(child: T) -> Self {
+        IntrinsicWidth { child }
+    }
+}
+
+impl<T: Widget> RenderWidget for IntrinsicWidth<T> {
+    fn build<'w>(&'w self, _: BuildCx<'w, Self>) -> Vec<Self::Widget<'w>> {
+        vec![&self.child]
+    }
+
+    fn layout(&self, cx: &LayoutCx<Self>, constraints: Constraints) -> Size {
+        let child = cx.child(0);
+
+        let child_constraints = match constraints.has_tight_width() {
+            true => constraints,
+            false => {
+                let width = child.max_intrinsic_width(constraints.max_height);
+                constraints.tighten(Some(width), None)
+            }
+        };
+
+        child.layout(child_constraints)
+    }
+
+    fn paint(&self, cx: &mut PaintCx<Self>, canvas: &mut Canvas, offset: &Offset) {
+        cx.child(0).paint(canvas, offset)
+    }
+
+    fn baseline(&self, cx: &LayoutCx<Self>) -> Option<f64> {
+        cx.child(0).baseline()
+    }
+
+    fn intrinsic_size(
+        &self,
+        cx: &LayoutCx<Self>,
+        dimension: IntrinsicDimension,
+        extent: f64,
+    ) -> f64 {
+        let child = cx.child(0);
+
+        match dimension.is_width() {
+            true => child.max_intrinsic_width(extent),
+            false => {
+                let width = match extent.is_finite() {
+                    true => extent,
+                    false => child.max_intrinsic_width(f64::INFINITY),
+                };
+
+                child.intrinsic_size(dimension, width)
+            }
+        }
+    }
+}
+
+/// Sizes its child to the child's maximal intrinsic height.
+///
+/// Useful to make children of a [`Row`] with stretched cross axis as tall as
+/// the tallest of them. This is relatively expensive, since intrinsic height
+/// of the child is computed before every layout.
+///
+/// [`Row`]: crate::Row
+#[derive(RenderWidget)]
+pub struct IntrinsicHeight<T: Widget> {
+    pub child: T,
+}
+
+impl<T: Widget> IntrinsicHeight<T> {
+    pub fn child(child: T) -> Self {
+        IntrinsicHeight { child }
+    }
+}
+
+impl<T: Widget> RenderWidget for IntrinsicHeight<T> {
+    fn build<'w>(&'w self, _: BuildCx<'w, Self>) -> Vec<Self::Widget<'w>> {
+        vec![&self.child]
+    }
+
+    fn layout(&self, cx: &LayoutCx<Self>, constraints: Constraints) -> Size {
+        let child = cx.child(0);
+
+        let child_constraints = match constraints.has_tight_height() {
+            true => constraints,
+            false => {
+                let height = child.max_intrinsic_height(constraints.max_width);
+                constraints.tighten(None, Some(height))
+            }
+        };
+
+        child.layout(child_constraints)
+    }
+
+    fn paint(&self, cx: &mut PaintCx<Self>, canvas: &mut Canvas, offset: &Offset) {
+        cx.child(0).paint(canvas, offset)
+    }
+
+    fn baseline(&self, cx: &LayoutCx<Self>) -> Option<f64> {
+        cx.child(0).baseline()
+    }
+
+    fn intrinsic_size(
+        &self,
+        cx: &LayoutCx<Self>,
+        dimension: IntrinsicDimension,
+        extent: f64,
+    ) -> f64 {
+        let child = cx.child(0);
+
+        match dimension.is_width() {
+            true => {
+                let height = match extent.is_finite() {
+                    true => extent,
+                    false => child.max_intrinsic_height(f64::INFINITY),
+                };
+
+                child.intrinsic_size(dimension, height)
+            }
+            false => child.max_intrinsic_height(extent),
+        }
+    }
 }
@@ -0,0 +1,302 @@
+use frui::prelude::*;
+use frui::render::{
+    kurbo::{BezPath, Ellipse, Shape},
+    *,
+};
+
+use crate::{BorderRadius, Directional, Directionality, TextDirection, EPSILON};
+
+/// Clips its child to its own size.
+///
+/// Child isn't hit outside of that area.
+#[derive(RenderWidget)]
+pub struct ClipRect<W: Widget> {
+    pub child: W,
+}
+
+impl<W: Widget> ClipRect<W> {
+    pub fn child(child: W) -> Self {
+        ClipRect { child }
+    }
+}
+
+impl<W: Widget> RenderWidget for ClipRect<W> {
+    fn build<'w>(&'w self, _: BuildCx<'w, Self>) -> Vec<Self::Widget<'w>> {
+        vec![&self.child]
+    }
+
+    fn layout(&self, cx: &LayoutCx<Self>, constraints: Constraints) -> Size {
+        cx.child(0).layout(constraints)
+    }
+
+    fn paint(&self, cx: &mut PaintCx<Self>, canvas: &mut Canvas, offset: &Offset) {
+        let rect = DruidRect::from_origin_size(offset, cx.size());
+
+        paint_clipped(rect, cx, canvas, offset);
+    }
+
+    fn baseline(&self, cx: &LayoutCx<Self>) -> Option<f64> {
+        cx.child(0).baseline()
+    }
+}
+
+impl<W: Widget> HitTest for ClipRect<W> {
+    fn hit_test<'a>(&'a self, cx: &'a mut HitTestCx<Self>, point: Point) -> bool {
+        let contains = cx.layout_box().contains(point);
+
+        hit_test_clipped(contains, cx, point)
+    }
+}
+
+impl<W: Widget> RenderUpdate for ClipRect<W> {
+    fn should_relayout(&self, _: &Self) -> bool {
+        false
+    }
+
+    fn should_repaint(&self, _: &Self) -> bool {
+        false
+    }
+}
+
+/// Clips its child to a rectangle of its size with rounded corners.
+///
+/// Child isn't hit outside of that rounded rectangle.
+#[derive(RenderWidget, Builder)]
+pub struct ClipRRect<W: Widget, BR: Directional<Output = BorderRadius>> {
+    pub child: W,
+    pub border_radius: BR,
+    /// Used to resolve `border_radius`. If it's `None`, `ClipRRect` will use
+    /// the value of its ancestor [`Directionality`], or default if no ancestor
+    /// found.
+    pub text_direction: Option<TextDirection>,
+}
+
+impl ClipRRect<(), BorderRadius> {
+    pub fn builder() -> Self {
+        ClipRRect {
+            child: (),
+            border_radius: BorderRadius::ZERO,
+            text_direction: None,
+        }
+    }
+}
+
+impl<W: Widget, BR: Directional<Output = BorderRadius>> ClipRRect<W, BR> {
+    fn rrect(border_radius: &BorderRadius, size: Size) -> RRect {
+        border_radius.to_rrect(&Rect::from_origin_size(Point::ZERO, size))
+    }
+}
+
+impl<W: Widget, BR: Directional<Output = BorderRadius>> RenderState for ClipRRect<W, BR> {
+    /// Border radius resolved during layout.
+    type State = BorderRadius;
+
+    fn create_state(&self) -> Self::State {
+        BorderRadius::ZERO
+    }
+}
+
+impl<W: Widget, BR: Directional<Output = BorderRadius>> RenderWidget for ClipRRect<W, BR> {
+    fn build<'w>(&'w self, _: BuildCx<'w, Self>) -> Vec<Self::Widget<'w>> {
+        vec![&self.child]
+    }
+
+    fn layout(&self, cx: &LayoutCx<Self>, constraints: Constraints) -> Size {
+        let text_direction = Directionality::unwrap_or_default(self.text_direction, cx);
+
+        *cx.render_state_mut() = self.border_radius.resolve(&text_direction);
+
+        cx.child(0).layout(constraints)
+    }
+
+    fn paint(&self, cx: &mut PaintCx<Self>, canvas: &mut Canvas, offset: &Offset) {
+        let rrect = Self::rrect(&cx.render_state(), cx.size()) + *offset;
+
+        paint_clipped(rrect.to_path(EPSILON), cx, canvas, offset);
+    }
+
+    fn baseline(&self, cx: &LayoutCx<Self>) -> Option<f64> {
+        cx.child(0).baseline()
+    }
+}
+
+impl<W: Widget, BR: Directional<Output = BorderRadius>> HitTest for ClipRRect<W, BR> {
+    fn hit_test<'a>(&'a self, cx: &'a mut HitTestCx<Self>, point: Point) -> bool {
+        let rrect = Self::rrect(&cx.render_state(), cx.layout_box());
+        let contains = rrect.contains(Offset::new(point.x, point.y));
+
+        hit_test_clipped(contains, cx, point)
+    }
+}
+
+/// Clips its child to an ellipse inscribed in its size.
+///
+/// Child isn't hit outside of that ellipse.
+#[derive(RenderWidget)]
+pub struct ClipOval<W: Widget> {
+    pub child: W,
+}
+
+impl<W: Widget> ClipOval<W> {
+    pub fn child(child: W) -> Self {
+        ClipOval { child }
+    }
+}
+
+impl<W: Widget> RenderWidget for ClipOval<W> {
+    fn build<'w>(&'w self, _: BuildCx<'w, Self>) -> Vec<Self::Widget<'w>> {
+        vec![&self.child]
+    }
+
+    fn layout(&self, cx: &LayoutCx<Self>, constraints: Constraints) -> Size {
+        cx.child(0).layout(constraints)
+    }
+
+    fn paint(&self, cx: &mut PaintCx<Self>, canvas: &mut Canvas, offset: &Offset) {
+        let oval = Ellipse::from_rect(DruidRect::from_origin_size(offset, cx.size()));
+
+        paint_clipped(oval, cx, canvas, offset);
+    }
+
+    fn baseline(&self, cx: &LayoutCx<Self>) -> Option<f64> {
+        cx.child(0).baseline()
+    }
+}
+
+impl<W: Widget> HitTest for ClipOval<W> {
+    fn hit_test<'a>(&'a self, cx: &'a mut HitTestCx<Self>, point: Point) -> bool {
+        let size = cx.layout_box();
+
+        let (radius_x, radius_y) = (size.width / 2., size.height / 2.);
+        let (dx, dy) = (point.x - radius_x, point.y - radius_y);
+
+        let contains = radius_x > 0.
+            && radius_y > 0.
+            && (dx * dx) / (radius_x * radius_x) + (dy * dy) / (radius_y * radius_y) <= 1.;
+
+        hit_test_clipped(contains, cx, point)
+    }
+}
+
+impl<W: Widget> RenderUpdate for ClipOval<W> {
+    fn should_relayout(&self, _: &Self) -> bool {
+        false
+    }
+
+    fn should_repaint(&self, _: &Self) -> bool {
+        false
+    }
+}
+
+/// Returns the shape used by [`ClipPath`] to clip its child.
+pub trait Clipper {
+    /// Returns the shape to clip to, relative to the area of given `size`.
+    fn clip(&self, size: Size) -> BezPath;
+
+    /// Returns whether the child needs to be clipped again after this clipper
+    /// replaced the `old` one.
+    fn should_reclip(&self, old: &Self) -> bool
+    where
+        Self: Sized;
+}
+
+/// Clips to the same path regardless of the size.
+impl Clipper for BezPath {
+    fn clip(&self, _: Size) -> BezPath {
+        self.clone()
+    }
+
+    fn should_reclip(&self, old: &Self) -> bool {
+        self != old
+    }
+}
+
+impl Clipper for Box<dyn Clipper> {
+    fn clip(&self, size: Size) -> BezPath {
+        (**self).clip(size)
+    }
+
+    fn should_reclip(&self, _: &Self) -> bool {
+        // Boxed clippers can't be compared.
+        true
+    }
+}
+
+/// Clips its child to a path returned by its [`Clipper`].
+///
+/// Child isn't hit outside of that path.
+#[derive(RenderWidget)]
+pub struct ClipPath<W: Widget, C: Clipper> {
+    pub clipper: C,
+    pub child: W,
+}
+
+impl<W: Widget, C: Clipper> ClipPath<W, C> {
+    pub fn new(clipper: C, child: W) -> Self {
+        ClipPath { clipper, child }
+    }
+}
+
+impl<W: Widget, C: Clipper> RenderWidget for ClipPath<W, C> {
+    fn build<'w>(&'w self, _: BuildCx<'w, Self>) -> Vec<Self::Widget<'w>> {
+        vec![&self.child]
+    }
+
+    fn layout(&self, cx: &LayoutCx<Self>, constraints: Constraints) -> Size {
+        cx.child(0).layout(constraints)
+    }
+
+    fn paint(&self, cx: &mut PaintCx<Self>, canvas: &mut Canvas, offset: &Offset) {
+        let path = Affine::translate((offset.x, offset.y)) * self.clipper.clip(cx.size());
+
+        paint_clipped(path, cx, canvas, offset);
+    }
+
+    fn baseline(&self, cx: &LayoutCx<Self>) -> Option<f64> {
+        cx.child(0).baseline()
+    }
+}
+
+impl<W: Widget, C: Clipper> HitTest for ClipPath<W, C> {
+    fn hit_test<'a>(&'a self, cx: &'a mut HitTestCx<Self>, point: Point) -> bool {
+        let size = cx.layout_box();
+        let contains = size.contains(point) && self.clipper.clip(size).contains(point);
+
+        hit_test_clipped(contains, cx, point)
+    }
+}
+
+impl<W: Widget, C: Clipper> RenderUpdate for ClipPath<W, C> {
+    fn should_relayout(&self, _: &Self) -> bool {
+        false
+    }
+
+    fn should_repaint(&self, old: &Self) -> bool {
+        self.clipper.should_reclip(&old.clipper)
+    }
+}
+
+fn paint_clipped<W>(clip: impl Shape, cx: &mut PaintCx<W>, canvas: &mut Canvas, offset: &Offset) {
+    let r = canvas.with_save(|cv| {
+        cv.clip(clip);
+        cx.child(0).paint(cv, offset);
+
+        Ok(())
+    });
+
+    r.unwrap();
+}
+
+/// Hit tests children only if the clipped area `contains` the point. Clip
+/// widgets themselves are hit only through their children.
+fn hit_test_clipped<W>(contains: bool, cx: &mut HitTestCx<W>, point: Point) -> bool {
+    if contains {
+        for mut child in cx.children() {
+            if child.hit_test_with_paint_offset(point) {
+                return true;
+            }
+        }
+    }
+
+    false
+}
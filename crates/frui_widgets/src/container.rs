@@ -1,14 +1,17 @@
 use frui::prelude::*;
 use frui::render::*;
 
-use crate::{BoxDecoration, Decoration, DecorationPosition, DefaultBoxDecoration, TextDirection};
+use crate::{
+    boxes::constrain_intrinsic, BoxDecoration, Decoration, DecorationPosition,
+    DefaultBoxDecoration, TextDirection,
+};
 
 #[derive(RenderWidget)]
 pub struct Container<W: Widget> {
-    child: W,
-    width: Option<f64>,
-    height: Option<f64>,
-    color: Option<Color>,
+    pub(crate) child: W,
+    pub(crate) width: Option<f64>,
+    pub(crate) height: Option<f64>,
+    pub(crate) color: Option<Color>,
 }
 
 impl Container<()> {
@@ -71,6 +74,22 @@ impl<W: Widget> RenderWidget for Container<W> {
 
         cx.child(0).paint(canvas, offset)
     }
+
+    fn baseline(&self, cx: &LayoutCx<Self>) -> Option<f64> {
+        cx.child(0).baseline()
+    }
+
+    fn intrinsic_size(
+        &self,
+        cx: &LayoutCx<Self>,
+        dimension: IntrinsicDimension,
+        extent: f64,
+    ) -> f64 {
+        let constraints = Constraints::new_tight_for(self.width, self.height);
+        let child_size = cx.child(0).intrinsic_size(dimension, extent);
+
+        constrain_intrinsic(constraints, dimension, child_size)
+    }
 }
 
 #[derive(RenderWidget, Builder)]
@@ -118,4 +137,8 @@ impl<W: Widget, D: Decoration> RenderWidget for DecoratedBox<W, D> {
             self.decoration.paint(canvas, rect.into(), offset);
         }
     }
+
+    fn baseline(&self, cx: &LayoutCx<Self>) -> Option<f64> {
+        cx.child(0).baseline()
+    }
 }
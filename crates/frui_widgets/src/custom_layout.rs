@@ -0,0 +1,134 @@
+use frui::prelude::*;
+use frui::render::*;
+
+use crate::*;
+
+/// Lays out and positions children of a [`CustomMultiChildLayout`].
+pub trait MultiChildLayoutDelegate {
+    /// Returns size of the layout, given its `constraints`. By default it's
+    /// as big as `constraints` allow.
+    fn size(&self, constraints: Constraints) -> Size {
+        constraints.biggest()
+    }
+
+    /// Lays out and positions children of the layout of given `size`.
+    ///
+    /// Every child has to be laid out exactly once.
+    fn layout(&self, cx: &MultiChildLayoutCx, size: Size);
+
+    /// Returns whether children need to be laid out again after this delegate
+    /// replaced the `old` one.
+    fn should_relayout(&self, old: &Self) -> bool
+    where
+        Self: Sized;
+}
+
+impl MultiChildLayoutDelegate for Box<dyn MultiChildLayoutDelegate> {
+    fn size(&self, constraints: Constraints) -> Size {
+        (**self).size(constraints)
+    }
+
+    fn layout(&self, cx: &MultiChildLayoutCx, size: Size) {
+        (**self).layout(cx, size)
+    }
+
+    fn should_relayout(&self, _: &Self) -> bool {
+        // Boxed delegates can't be compared.
+        true
+    }
+}
+
+/// Gives [`MultiChildLayoutDelegate`] access to children of the layout.
+///
+/// Children are identified by keys of [`LocalKey`] widgets wrapping them.
+pub struct MultiChildLayoutCx<'a> {
+    cx: &'a LayoutCxOS,
+}
+
+impl MultiChildLayoutCx<'_> {
+    /// Returns `true` if the layout has a child with given `id`.
+    pub fn has_child<K: 'static + PartialEq>(&self, id: K) -> bool {
+        self.child(&id).is_some()
+    }
+
+    /// Lays out child with given `id` and returns its size.
+    ///
+    /// # Panics
+    ///
+    /// If the layout doesn't have a child with given `id`.
+    pub fn layout_child<K>(&self, id: K, constraints: Constraints) -> Size
+    where
+        K: 'static + PartialEq + std::fmt::Debug,
+    {
+        match self.child(&id) {
+            Some(child) => child.layout(constraints),
+            None => panic!("custom layout doesn't have a child with id `{:?}`", id),
+        }
+    }
+
+    /// Moves child with given `id` to `offset`, relative to the layout.
+    ///
+    /// # Panics
+    ///
+    /// If the layout doesn't have a child with given `id`.
+    pub fn position_child<K>(&self, id: K, offset: Offset)
+    where
+        K: 'static + PartialEq + std::fmt::Debug,
+    {
+        match self.child(&id) {
+            Some(child) => child.try_parent_data_mut::<BoxLayoutData>().unwrap().offset = offset,
+            None => panic!("custom layout doesn't have a child with id `{:?}`", id),
+        }
+    }
+
+    fn child<K: 'static + PartialEq>(&self, id: &K) -> Option<LayoutCxOS> {
+        self.cx.children().find(|child| child.matches_key(id))
+    }
+}
+
+/// Lays out its children using a [`MultiChildLayoutDelegate`].
+///
+/// Each child has to be wrapped in a [`LocalKey`], which key identifies it in
+/// the delegate.
+#[derive(RenderWidget)]
+pub struct CustomMultiChildLayout<WL: WidgetList, D: MultiChildLayoutDelegate> {
+    pub delegate: D,
+    pub children: WL,
+}
+
+impl<WL: WidgetList, D: MultiChildLayoutDelegate> CustomMultiChildLayout<WL, D> {
+    pub fn new(delegate: D, children: WL) -> Self {
+        Self { delegate, children }
+    }
+}
+
+impl<WL: WidgetList, D: MultiChildLayoutDelegate> RenderWidget for CustomMultiChildLayout<WL, D> {
+    fn build<'w>(&'w self, _cx: BuildCx<'w, Self>) -> Vec<Self::Widget<'w>> {
+        self.children.get()
+    }
+
+    fn layout(&self, cx: &LayoutCx<Self>, constraints: Constraints) -> Size {
+        self.ensure_parent_data(cx, BoxLayoutData::default);
+
+        let size = constraints.constrain(self.delegate.size(constraints));
+
+        self.delegate.layout(&MultiChildLayoutCx { cx }, size);
+
+        size
+    }
+
+    fn paint(&self, cx: &mut PaintCx<Self>, canvas: &mut Canvas, offset: &Offset) {
+        for mut child in cx.children() {
+            let child_offset: Offset = child
+                .try_parent_data::<BoxLayoutData>()
+                .map_or(*offset, |d| (*offset + d.offset));
+            child.paint(canvas, &child_offset);
+        }
+    }
+}
+
+impl<WL: WidgetList, D: MultiChildLayoutDelegate> RenderUpdate for CustomMultiChildLayout<WL, D> {
+    fn should_relayout(&self, old: &Self) -> bool {
+        self.delegate.should_relayout(&old.delegate)
+    }
+}
@@ -0,0 +1,178 @@
+use frui::prelude::*;
+use frui::render::*;
+
+/// Paints on the canvas of a [`CustomPaint`].
+pub trait Painter {
+    /// Paints within the area of given `size`. Canvas is translated, so that
+    /// its origin is the top left corner of that area.
+    fn paint(&self, canvas: &mut Canvas, size: Size);
+
+    /// Returns whether the canvas needs to be painted again after this painter
+    /// replaced the `old` one.
+    fn should_repaint(&self, old: &Self) -> bool
+    where
+        Self: Sized;
+
+    /// Returns whether the painting is hit at `point` (relative to the area of
+    /// given `size`), or `None` to treat the whole area as hit.
+    fn hit_test(&self, point: Point, size: Size) -> Option<bool> {
+        let _ = (point, size);
+        None
+    }
+}
+
+/// Paints nothing.
+impl Painter for () {
+    fn paint(&self, _: &mut Canvas, _: Size) {}
+
+    fn should_repaint(&self, _: &Self) -> bool {
+        false
+    }
+}
+
+impl Painter for Box<dyn Painter> {
+    fn paint(&self, canvas: &mut Canvas, size: Size) {
+        (**self).paint(canvas, size)
+    }
+
+    fn should_repaint(&self, _: &Self) -> bool {
+        // Boxed painters can't be compared.
+        true
+    }
+
+    fn hit_test(&self, point: Point, size: Size) -> Option<bool> {
+        (**self).hit_test(point, size)
+    }
+}
+
+/// Paints its child between a background `painter` and a `foreground_painter`.
+///
+/// It's as big as its child, but at least as big as `size` (within incoming
+/// constraints).
+#[derive(RenderWidget, Builder)]
+pub struct CustomPaint<P: Painter, FP: Painter, W: Widget> {
+    /// Paints before the child.
+    pub painter: P,
+    /// Paints after the child.
+    pub foreground_painter: FP,
+    pub size: Size,
+    pub child: W,
+}
+
+impl CustomPaint<(), (), ()> {
+    pub fn builder() -> Self {
+        Self {
+            painter: (),
+            foreground_painter: (),
+            size: Size::ZERO,
+            child: (),
+        }
+    }
+}
+
+impl<P: Painter, FP: Painter, W: Widget> RenderWidget for CustomPaint<P, FP, W> {
+    fn build<'w>(&'w self, _: BuildCx<'w, Self>) -> Vec<Self::Widget<'w>> {
+        vec![&self.child]
+    }
+
+    fn layout(&self, cx: &LayoutCx<Self>, constraints: Constraints) -> Size {
+        let child_size = cx.child(0).layout(constraints);
+
+        constraints.constrain(Size::new(
+            child_size.width.max(self.size.width),
+            child_size.height.max(self.size.height),
+        ))
+    }
+
+    fn paint(&self, cx: &mut PaintCx<Self>, canvas: &mut Canvas, offset: &Offset) {
+        let size = cx.size();
+
+        paint_at(&self.painter, canvas, size, offset);
+        cx.child(0).paint(canvas, offset);
+        paint_at(&self.foreground_painter, canvas, size, offset);
+    }
+
+    fn baseline(&self, cx: &LayoutCx<Self>) -> Option<f64> {
+        cx.child(0).baseline()
+    }
+
+    fn intrinsic_size(
+        &self,
+        cx: &LayoutCx<Self>,
+        dimension: IntrinsicDimension,
+        extent: f64,
+    ) -> f64 {
+        let child_size = cx.child(0).intrinsic_size(dimension, extent);
+
+        match dimension.is_width() {
+            true => child_size.max(self.size.width),
+            false => child_size.max(self.size.height),
+        }
+    }
+}
+
+fn paint_at(painter: &impl Painter, canvas: &mut Canvas, size: Size, offset: &Offset) {
+    let r = canvas.with_save(|cv| {
+        cv.transform(Affine::translate((offset.x, offset.y)));
+        painter.paint(cv, size);
+
+        Ok(())
+    });
+
+    r.unwrap();
+}
+
+impl<P: Painter, FP: Painter, W: Widget> HitTest for CustomPaint<P, FP, W> {
+    fn hit_test<'a>(&'a self, cx: &'a mut HitTestCx<Self>, point: Point) -> bool {
+        let size = cx.layout_box();
+
+        if !size.contains(point) {
+            return false;
+        }
+
+        if let Some(true) = self.foreground_painter.hit_test(point, size) {
+            return true;
+        }
+
+        // Empty child isn't hit, even when it's stretched to fill this widget.
+        if !self.child.is_empty() {
+            for mut child in cx.children() {
+                if child.hit_test_with_paint_offset(point) {
+                    return true;
+                }
+            }
+        }
+
+        self.painter.hit_test(point, size).unwrap_or(true)
+    }
+}
+
+/// Whether a child is the empty `()` widget.
+trait IsEmpty {
+    fn is_empty(&self) -> bool;
+}
+
+impl<W> IsEmpty for W {
+    default fn is_empty(&self) -> bool {
+        false
+    }
+}
+
+impl IsEmpty for () {
+    fn is_empty(&self) -> bool {
+        true
+    }
+}
+
+impl<P: Painter, FP: Painter, W: Widget> RenderUpdate for CustomPaint<P, FP, W> {
+    fn should_relayout(&self, old: &Self) -> bool {
+        self.size != old.size
+    }
+
+    fn should_repaint(&self, old: &Self) -> bool {
+        self.painter.should_repaint(&old.painter)
+            || self
+                .foreground_painter
+                .should_repaint(&old.foreground_painter)
+    }
+}
@@ -0,0 +1,157 @@
+use druid_shell::KeyEvent;
+use frui::prelude::*;
+
+/// Attaches a [`FocusNode`] to the widget tree, which makes `on_key` receive
+/// key events while this widget or any of its descendants has focus.
+///
+/// Events which `on_key` ignores bubble up to the closest `Focus` ancestor.
+/// Both key presses and key releases are delivered, which can be told apart
+/// by `event.state`.
+///
+/// ```ignore
+/// Focus::builder()
+///     .autofocus(true)
+///     .on_key(|event| match event.state {
+///         KeyState::Down => {
+///             println!("{:?}", event.key);
+///             KeyEventResult::Handled
+///         }
+///         KeyState::Up => KeyEventResult::Ignored,
+///     })
+///     .on_focus_change(|has_focus| *cx.state_mut() = has_focus)
+///     .child(child)
+/// ```
+#[derive(ViewWidget, Builder)]
+pub struct Focus<W: Widget, K: Fn(&KeyEvent) -> KeyEventResult, C: Fn(bool)> {
+    pub child: W,
+    pub on_key: K,
+    /// Called when this widget or any of its descendants gains or loses
+    /// focus.
+    pub on_focus_change: C,
+    /// Focus node to attach. If not set, this widget creates its own.
+    pub focus_node: Option<FocusNode>,
+    /// Whether to focus this widget when it's built, if nothing else is
+    /// focused.
+    pub autofocus: bool,
+    /// Whether this widget can be focused. If not, it still receives key
+    /// events bubbling up from its descendants.
+    pub can_request_focus: bool,
+    /// Whether Tab / Shift+Tab should skip this widget.
+    pub skip_traversal: bool,
+    /// Position in the traversal order of the enclosing [`FocusScope`]. See
+    /// [`FocusNode::set_order`].
+    pub order: Option<f64>,
+}
+
+impl Focus<(), fn(&KeyEvent) -> KeyEventResult, fn(bool)> {
+    pub fn builder() -> Self {
+        Focus {
+            child: (),
+            on_key: |_| KeyEventResult::Ignored,
+            on_focus_change: |_| {},
+            focus_node: None,
+            autofocus: false,
+            can_request_focus: true,
+            skip_traversal: false,
+            order: None,
+        }
+    }
+}
+
+impl<W, K, C> WidgetState for Focus<W, K, C>
+where
+    W: Widget,
+    K: Fn(&KeyEvent) -> KeyEventResult,
+    C: Fn(bool),
+{
+    type State = FocusNode;
+
+    fn create_state(&self) -> Self::State {
+        self.focus_node.clone().unwrap_or_default()
+    }
+
+    fn mount<'a>(&'a self, cx: BuildCx<'a, Self>) {
+        if let Some(focus_node) = &self.focus_node {
+            if *focus_node != *cx.state() {
+                *cx.state_mut() = focus_node.clone();
+            }
+        }
+
+        let focus_node = cx.state();
+
+        focus_node.attach(cx);
+        focus_node.set_can_request_focus(self.can_request_focus);
+        focus_node.set_skip_traversal(self.skip_traversal);
+        focus_node.set_order(self.order);
+
+        // Safety: Handlers are cleared in `unmount`, before `self` is dropped.
+        unsafe { focus_node.set_handlers(&self.on_key, &self.on_focus_change) };
+
+        if self.autofocus {
+            focus_node.autofocus();
+        }
+    }
+
+    fn unmount<'a>(&'a self, cx: BuildCx<'a, Self>) {
+        cx.state().detach();
+    }
+}
+
+impl<W, K, C> ViewWidget for Focus<W, K, C>
+where
+    W: Widget,
+    K: Fn(&KeyEvent) -> KeyEventResult,
+    C: Fn(bool),
+{
+    fn build<'w>(&'w self, _: BuildCx<'w, Self>) -> Self::Widget<'w> {
+        &self.child
+    }
+}
+
+/// Groups focusable descendants, so that Tab / Shift+Tab cycle only between
+/// them. When focused, the scope restores focus of the descendant which was
+/// focused last.
+#[derive(ViewWidget, Builder)]
+pub struct FocusScope<W: Widget> {
+    pub child: W,
+    /// Focus node to attach, which must be created with [`FocusNode::scope`].
+    /// If not set, this widget creates its own.
+    pub focus_node: Option<FocusNode>,
+    /// Whether to focus this scope when it's built, if nothing else is
+    /// focused.
+    pub autofocus: bool,
+}
+
+impl FocusScope<()> {
+    pub fn builder() -> Self {
+        FocusScope {
+            child: (),
+            focus_node: None,
+            autofocus: false,
+        }
+    }
+}
+
+impl<W: Widget> WidgetState for FocusScope<W> {
+    type State = FocusNode;
+
+    fn create_state(&self) -> Self::State {
+        FocusNode::scope()
+    }
+}
+
+impl<W: Widget> ViewWidget for FocusScope<W> {
+    fn build<'w>(&'w self, cx: BuildCx<'w, Self>) -> Self::Widget<'w> {
+        let focus_node = match &self.focus_node {
+            Some(focus_node) => focus_node.clone(),
+            None => cx.state().clone(),
+        };
+
+        debug_assert!(focus_node.is_scope(), "focus node is not a scope");
+
+        Focus::builder()
+            .focus_node(focus_node)
+            .autofocus(self.autofocus)
+            .child(&self.child)
+    }
+}
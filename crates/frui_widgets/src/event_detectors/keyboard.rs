@@ -1,9 +1,19 @@
 use druid_shell::KeyEvent;
 use frui::{
-    app::listeners::keyboard::{CallbackKey, KEYBOARD_EVENT_LISTENERS},
+    app::listeners::keyboard::{
+        CallbackKey, KEYBOARD_EVENT_LISTENERS, RAW_KEYBOARD_EVENT_LISTENERS,
+    },
     prelude::*,
 };
 
+pub use frui::app::listeners::keyboard::RawKeyEvent;
+
+/// Calls `on_event` for every key press which wasn't handled by the focused
+/// widget or its ancestors (see [`Focus`]), nor delivered to a focused text
+/// input. Key releases are delivered only to [`Focus`] and
+/// [`RawKeyboardListener`].
+///
+/// [`Focus`]: crate::Focus
 #[derive(ViewWidget)]
 pub struct KeyboardEventDetector<W: Widget, F: Fn(KeyEvent)> {
     pub on_event: F,
@@ -36,3 +46,40 @@ impl<W: Widget, F: Fn(KeyEvent)> ViewWidget for KeyboardEventDetector<W, F> {
         &self.child
     }
 }
+
+/// Calls `on_event` for every key press, key release and change of pressed
+/// modifier keys, regardless of which widget is focused.
+///
+/// Useful for games and other widgets tracking which keys are held down.
+#[derive(ViewWidget)]
+pub struct RawKeyboardListener<W: Widget, F: Fn(RawKeyEvent)> {
+    pub on_event: F,
+    pub child: W,
+}
+
+impl<W: Widget, F: Fn(RawKeyEvent)> WidgetState for RawKeyboardListener<W, F> {
+    type State = Option<CallbackKey>;
+
+    fn create_state(&self) -> Self::State {
+        None
+    }
+
+    fn mount(&self, cx: BuildCx<Self>) {
+        *cx.state_mut() = Some(
+            RAW_KEYBOARD_EVENT_LISTENERS
+                .with(|listeners| unsafe { listeners.borrow_mut().register(&self.on_event) }),
+        );
+    }
+
+    fn unmount(&self, cx: BuildCx<Self>) {
+        if let Some(key) = cx.state_mut().take() {
+            RAW_KEYBOARD_EVENT_LISTENERS.with(|listeners| listeners.borrow_mut().unregister(key));
+        }
+    }
+}
+
+impl<W: Widget, F: Fn(RawKeyEvent)> ViewWidget for RawKeyboardListener<W, F> {
+    fn build<'w>(&'w self, _: BuildCx<'w, Self>) -> Self::Widget<'w> {
+        &self.child
+    }
+}
@@ -1,4 +1,4 @@
-use frui::render::*;
+use frui::{animation::Lerp, render::*};
 
 use std::fmt::{Display, Formatter};
 use std::ops::{Add, Div, Mul, Neg, Sub};
@@ -86,6 +86,15 @@ impl Neg for Alignment {
     }
 }
 
+impl Lerp for Alignment {
+    fn lerp(&self, other: &Self, t: f64) -> Self {
+        Alignment {
+            x: Lerp::lerp(&self.x, &other.x, t),
+            y: Lerp::lerp(&self.y, &other.y, t),
+        }
+    }
+}
+
 impl Display for Alignment {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         for (alignment, name) in Alignment::PRELUDES {
@@ -222,6 +231,15 @@ impl Div<f64> for AlignmentDirectional {
     }
 }
 
+impl Lerp for AlignmentDirectional {
+    fn lerp(&self, other: &Self, t: f64) -> Self {
+        AlignmentDirectional {
+            start: Lerp::lerp(&self.start, &other.start, t),
+            y: Lerp::lerp(&self.y, &other.y, t),
+        }
+    }
+}
+
 impl Display for AlignmentDirectional {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         for (alignment, name) in AlignmentDirectional::PRELUDES {
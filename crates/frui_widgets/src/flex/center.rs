@@ -42,4 +42,10 @@ impl<W: Widget> RenderWidget for Center<W> {
 
         cx.child(0).paint(canvas, &child_offset);
     }
+
+    fn baseline(&self, cx: &LayoutCx<Self>) -> Option<f64> {
+        let child = cx.child(0);
+
+        Some(child.baseline()? + (cx.size().height - child.size().height) / 2.)
+    }
 }
@@ -10,12 +10,36 @@ pub enum Axis {
 }
 
 impl Axis {
-    fn max(&self, constraints: Constraints) -> f64 {
+    pub(crate) fn max(&self, constraints: Constraints) -> f64 {
         match self {
             Axis::Horizontal => constraints.max_width,
             Axis::Vertical => constraints.max_height,
         }
     }
+
+    /// Returns size with `main` extent along this axis.
+    pub(crate) fn size(&self, main: f64, cross: f64) -> Size {
+        match self {
+            Axis::Horizontal => Size::new(main, cross),
+            Axis::Vertical => Size::new(cross, main),
+        }
+    }
+
+    /// Returns offset by `main` along this axis.
+    pub(crate) fn offset(&self, main: f64, cross: f64) -> Offset {
+        match self {
+            Axis::Horizontal => Offset::new(main, cross),
+            Axis::Vertical => Offset::new(cross, main),
+        }
+    }
+
+    /// Returns constraints with `main` range along this axis.
+    pub(crate) fn constraints(&self, main: (f64, f64), cross: (f64, f64)) -> Constraints {
+        match self {
+            Axis::Horizontal => Constraints::new(main.0, main.1, cross.0, cross.1),
+            Axis::Vertical => Constraints::new(cross.0, cross.1, main.0, main.1),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -145,6 +169,15 @@ impl<WL: WidgetList> RenderWidget for Flex<WL> {
             cross_size_min = cross_size_min.max(cross_size_min_flex);
         }
 
+        //
+        // Compute space needed above and below baselines of children.
+
+        let baseline = self.compute_baseline(cx.children());
+
+        if let Some((above, below)) = baseline {
+            cross_size_min = cross_size_min.max(above + below);
+        }
+
         //
         // Compute size of this `Flex` widget.
 
@@ -161,6 +194,7 @@ impl<WL: WidgetList> RenderWidget for Flex<WL> {
 
         for child in cx.children() {
             let child_size = child.size();
+            let child_baseline = baseline.and_then(|_| child.baseline());
             let child_offset = &mut child.try_parent_data_mut::<FlexData>().unwrap().offset;
 
             if main_axis_flipped {
@@ -172,11 +206,14 @@ impl<WL: WidgetList> RenderWidget for Flex<WL> {
                 main_offset += child_size.main(self.direction) + space_between;
             }
 
-            let cross_offset = self.compute_cross_offset(
-                child_size.cross(self.direction),
-                cross_size,
-                text_direction,
-            );
+            let cross_offset = match (baseline, child_baseline) {
+                (Some((above, _)), Some(child_baseline)) => above - child_baseline,
+                _ => self.compute_cross_offset(
+                    child_size.cross(self.direction),
+                    cross_size,
+                    text_direction,
+                ),
+            };
 
             *child_offset.cross_mut(self.direction) = cross_offset;
         }
@@ -192,6 +229,90 @@ impl<WL: WidgetList> RenderWidget for Flex<WL> {
             child.paint(canvas, &child_offset);
         }
     }
+
+    fn intrinsic_size(
+        &self,
+        cx: &LayoutCx<Self>,
+        dimension: IntrinsicDimension,
+        extent: f64,
+    ) -> f64 {
+        let is_main_axis = dimension.is_width() == (self.direction == Axis::Horizontal);
+        let space_between = self.space_between * cx.children().len().saturating_sub(1) as f64;
+
+        let mut flex_count = 0;
+        let mut inflexible_size = 0.0f64;
+
+        if is_main_axis {
+            // Flexible children get space proportional to their flex factors,
+            // so the child which needs the most space per flex decides how
+            // much space all of them need.
+            let mut size_per_flex = 0.0f64;
+
+            for child in cx.children() {
+                let flex = get_flex(&child).unwrap_or(0);
+                let child_size = child.intrinsic_size(dimension, extent);
+
+                if flex > 0 {
+                    flex_count += flex;
+                    size_per_flex = size_per_flex.max(child_size / flex as f64);
+                } else {
+                    inflexible_size += child_size;
+                }
+            }
+
+            size_per_flex * flex_count as f64 + inflexible_size + space_between
+        } else {
+            // Children are given their maximal intrinsic size on the main axis
+            // and flexible children share what's left of `extent`.
+            let main_dimension = match self.direction {
+                Axis::Horizontal => IntrinsicDimension::MaxWidth,
+                Axis::Vertical => IntrinsicDimension::MaxHeight,
+            };
+
+            let mut cross_size = 0.0f64;
+
+            for child in cx.children() {
+                let flex = get_flex(&child).unwrap_or(0);
+
+                if flex > 0 {
+                    flex_count += flex;
+                } else {
+                    let main_size = child.intrinsic_size(main_dimension, f64::INFINITY);
+                    inflexible_size += main_size;
+                    cross_size = cross_size.max(child.intrinsic_size(dimension, main_size));
+                }
+            }
+
+            let free_space = (extent - inflexible_size - space_between).max(0.);
+            let space_per_flex = free_space / flex_count.max(1) as f64;
+
+            for child in cx.children() {
+                let flex = get_flex(&child).unwrap_or(0);
+
+                if flex > 0 {
+                    let main_size = space_per_flex * flex as f64;
+                    cross_size = cross_size.max(child.intrinsic_size(dimension, main_size));
+                }
+            }
+
+            cross_size
+        }
+    }
+
+    fn baseline(&self, cx: &LayoutCx<Self>) -> Option<f64> {
+        let mut baselines = cx.children().filter_map(|child| {
+            let offset = child.try_parent_data::<FlexData>()?.offset;
+
+            Some(child.baseline()? + offset.y)
+        });
+
+        match self.direction {
+            // Highest baseline of children in a row.
+            Axis::Horizontal => baselines.reduce(f64::min),
+            // Baseline of the first child with a baseline in a column.
+            Axis::Vertical => baselines.next(),
+        }
+    }
 }
 
 impl<WL: WidgetList> Flex<WL> {
@@ -475,14 +596,37 @@ impl<WL: WidgetList> Flex<WL> {
             self.cross_axis_alignment,
             self.start_is_top_left(text_direction),
         ) {
-            (Start, true) | (End, false) => 0.0,
-            (Start, false) | (End, true) => available,
+            // Children without a baseline are placed at the start.
+            (Start | Baseline, true) | (End, false) => 0.0,
+            (Start | Baseline, false) | (End, true) => available,
             (Center, _) => available / 2.,
             (Stretch, _) => 0.0,
-            (Baseline, _) => todo!("implement baseline alignment"),
         }
     }
 
+    /// Returns the largest distance from the top of a child to its baseline
+    /// and the largest distance from a baseline to the bottom of a child, if
+    /// children of a horizontal [`Flex`] are aligned by their baselines.
+    fn compute_baseline(&self, children: LayoutCxIter) -> Option<(f64, f64)> {
+        if self.direction != Axis::Horizontal
+            || self.cross_axis_alignment != CrossAxisAlignment::Baseline
+        {
+            return None;
+        }
+
+        let mut above = 0.0f64;
+        let mut below = 0.0f64;
+
+        for child in children {
+            if let Some(baseline) = child.baseline() {
+                above = above.max(baseline);
+                below = below.max(child.size().height - baseline);
+            }
+        }
+
+        Some((above, below))
+    }
+
     fn start_is_top_left(&self, text_direction: TextDirection) -> bool {
         match (self.direction, text_direction, self.vertical_direction) {
             (Axis::Vertical, TextDirection::Ltr, _) => true,
@@ -537,7 +681,7 @@ fn fit_tight(c: &LayoutCxOS) -> bool {
     get_fit(c).unwrap() == FlexFit::Tight
 }
 
-trait AxisExt {
+pub(crate) trait AxisExt {
     fn main(&self, axis: Axis) -> f64;
     fn main_mut(&mut self, axis: Axis) -> &mut f64;
 
@@ -0,0 +1,207 @@
+use frui::prelude::*;
+use frui::render::*;
+
+use crate::flex::flex::AxisExt;
+use crate::*;
+
+/// Decides how many children are placed next to each other along the cross
+/// axis of a [`GridView`].
+pub trait GridDelegate {
+    /// Returns number of columns (or rows, in a horizontal [`GridView`]),
+    /// given the cross axis extent of the grid and space between them.
+    fn cross_axis_count(&self, cross_extent: f64, spacing: f64) -> usize;
+}
+
+/// Places a fixed number of children along the cross axis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FixedCrossAxisCount(pub usize);
+
+impl GridDelegate for FixedCrossAxisCount {
+    fn cross_axis_count(&self, _: f64, _: f64) -> usize {
+        self.0
+    }
+}
+
+/// Places as few children along the cross axis as possible, such that each of
+/// them is at most this wide (or tall, in a horizontal [`GridView`]).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MaxCrossAxisExtent(pub f64);
+
+impl GridDelegate for MaxCrossAxisExtent {
+    fn cross_axis_count(&self, cross_extent: f64, spacing: f64) -> usize {
+        ((cross_extent + spacing) / (self.0 + spacing)).ceil() as usize
+    }
+}
+
+/// Lays out its children in a grid of cells, row by row (or column by column,
+/// if `direction` is [`Horizontal`](Axis::Horizontal)).
+///
+/// Cells split the cross axis extent of the grid evenly. It needs bounded
+/// constraints on that axis.
+#[derive(RenderWidget, Builder)]
+pub struct GridView<WL: WidgetList, D: GridDelegate> {
+    pub children: WL,
+
+    /// Decides how many cells are in each row.
+    pub delegate: D,
+
+    /// Axis along which rows are stacked.
+    pub direction: Axis,
+
+    /// If `text_direction` is set to [`Rtl`](TextDirection::Rtl), cells are
+    /// placed from right to left.
+    ///
+    /// If `text_direction` is set to `None`, `GridView` will use the value of
+    /// its ancestor [`Directionality`], or default if no ancestor found.
+    pub text_direction: Option<TextDirection>,
+
+    /// Space added between rows.
+    pub main_axis_spacing: f64,
+
+    /// Space added between cells in a row.
+    pub cross_axis_spacing: f64,
+
+    /// Ratio of the cross axis extent to the main axis extent of each cell.
+    ///
+    /// If `None`, each row is as long as its longest child.
+    pub child_aspect_ratio: Option<f64>,
+
+    /// How children should be laid out on the main axis of their cells.
+    pub main_axis_alignment: MainAxisAlignment,
+
+    /// How children should be laid out on the cross axis of their cells.
+    /// [`Stretch`](CrossAxisAlignment::Stretch) forces them to fill their
+    /// cells.
+    pub cross_axis_alignment: CrossAxisAlignment,
+}
+
+impl GridView<(), FixedCrossAxisCount> {
+    pub fn builder() -> Self {
+        Self {
+            children: (),
+            delegate: FixedCrossAxisCount(2),
+            direction: Axis::Vertical,
+            text_direction: None,
+            main_axis_spacing: 0.0,
+            cross_axis_spacing: 0.0,
+            child_aspect_ratio: None,
+            main_axis_alignment: MainAxisAlignment::Start,
+            cross_axis_alignment: CrossAxisAlignment::Stretch,
+        }
+    }
+}
+
+impl<WL: WidgetList, D: GridDelegate> RenderWidget for GridView<WL, D> {
+    fn build<'w>(&'w self, _cx: BuildCx<'w, Self>) -> Vec<Self::Widget<'w>> {
+        self.children.get()
+    }
+
+    fn layout(&self, cx: &LayoutCx<Self>, constraints: Constraints) -> Size {
+        let axis = self.direction;
+        let cross_size = constraints.biggest().cross(axis);
+        let text_direction = Directionality::unwrap_or_default(self.text_direction, cx);
+
+        assert!(
+            cross_size < f64::INFINITY,
+            "grid view received unbounded constraints"
+        );
+
+        self.ensure_parent_data(cx, BoxLayoutData::default);
+
+        //
+        // Compute extents of cells.
+
+        let count = self
+            .delegate
+            .cross_axis_count(cross_size, self.cross_axis_spacing)
+            .max(1);
+
+        let cell_cross =
+            ((cross_size - self.cross_axis_spacing * (count - 1) as f64) / count as f64).max(0.);
+
+        let cell_main = self.child_aspect_ratio.map(|ratio| cell_cross / ratio);
+
+        //
+        // Layout children.
+
+        let stretch = self.cross_axis_alignment == CrossAxisAlignment::Stretch;
+
+        let child_constraints = axis.constraints(
+            match (cell_main, stretch) {
+                (Some(main), true) => (main, main),
+                (Some(main), false) => (0., main),
+                (None, _) => (0., f64::INFINITY),
+            },
+            match stretch {
+                true => (cell_cross, cell_cross),
+                false => (0., cell_cross),
+            },
+        );
+
+        let mut row_sizes = Vec::new();
+
+        for (n, child) in cx.children().enumerate() {
+            let child_main = child.layout(child_constraints).main(axis);
+
+            if n % count == 0 {
+                row_sizes.push(cell_main.unwrap_or(0.));
+            }
+
+            if cell_main.is_none() {
+                let row_size = row_sizes.last_mut().unwrap();
+                *row_size = row_size.max(child_main);
+            }
+        }
+
+        //
+        // Compute size of this `GridView` widget.
+
+        let main_size = row_sizes.iter().sum::<f64>()
+            + self.main_axis_spacing * row_sizes.len().saturating_sub(1) as f64;
+
+        let size = constraints.constrain(axis.size(main_size, cross_size));
+
+        //
+        // Position children.
+
+        let mut row_offset = 0.;
+
+        for (n, child) in cx.children().enumerate() {
+            let row_size = row_sizes[n / count];
+            let column = n % count;
+            let child_size = child.size();
+
+            let (main_offset, _) = self
+                .main_axis_alignment
+                .distribute(row_size - child_size.main(axis), 1);
+
+            let cross_offset = column as f64 * (cell_cross + self.cross_axis_spacing)
+                + self
+                    .cross_axis_alignment
+                    .offset(cell_cross - child_size.cross(axis));
+
+            let mut offset = axis.offset(row_offset + main_offset, cross_offset);
+
+            if text_direction == TextDirection::Rtl {
+                offset.x = size.width - offset.x - child_size.width;
+            }
+
+            child.try_parent_data_mut::<BoxLayoutData>().unwrap().offset = offset;
+
+            if column == count - 1 {
+                row_offset += row_size + self.main_axis_spacing;
+            }
+        }
+
+        size
+    }
+
+    fn paint(&self, cx: &mut PaintCx<Self>, canvas: &mut Canvas, offset: &Offset) {
+        for mut child in cx.children() {
+            let child_offset: Offset = child
+                .try_parent_data::<BoxLayoutData>()
+                .map_or(*offset, |d| (*offset + d.offset));
+            child.paint(canvas, &child_offset);
+        }
+    }
+}
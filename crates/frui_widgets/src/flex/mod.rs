@@ -6,12 +6,16 @@ use frui::render::*;
 pub use alignment::*;
 pub use center::*;
 pub use flex::*;
+pub use grid::*;
 pub use stack::*;
+pub use wrap::*;
 
 pub mod alignment;
 pub mod center;
 pub mod flex;
+pub mod grid;
 pub mod stack;
+pub mod wrap;
 
 #[derive(Debug, Clone, Copy, Default)]
 pub struct BoxLayoutData {
@@ -47,6 +51,32 @@ impl Default for MainAxisAlignment {
     }
 }
 
+impl MainAxisAlignment {
+    /// Returns space before the first of `count` children and space between
+    /// each of them, given `free_space` left after placing them back to back.
+    pub(crate) fn distribute(&self, free_space: f64, count: usize) -> (f64, f64) {
+        use MainAxisAlignment::*;
+
+        let free_space = free_space.max(0.);
+
+        if count == 0 {
+            return (0., 0.);
+        }
+
+        let count = count as f64;
+
+        match self {
+            Start => (0., 0.),
+            Center => (free_space / 2., 0.),
+            End => (free_space, 0.),
+            SpaceBetween if count > 1. => (0., free_space / (count - 1.)),
+            SpaceBetween => (0., 0.),
+            SpaceAround => (free_space / count / 2., free_space / count),
+            SpaceEvenly => (free_space / (count + 1.), free_space / (count + 1.)),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum CrossAxisSize {
     Min,
@@ -74,6 +104,21 @@ impl Default for CrossAxisAlignment {
     }
 }
 
+impl CrossAxisAlignment {
+    /// Returns offset of a child which leaves `free_space` on the cross axis.
+    /// [`Stretch`] and [`Baseline`] place it at the start.
+    ///
+    /// [`Stretch`]: CrossAxisAlignment::Stretch
+    /// [`Baseline`]: CrossAxisAlignment::Baseline
+    pub(crate) fn offset(&self, free_space: f64) -> f64 {
+        match self {
+            CrossAxisAlignment::Center => free_space / 2.,
+            CrossAxisAlignment::End => free_space,
+            _ => 0.,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum FlexFit {
     Loose,
@@ -157,6 +202,10 @@ impl<W: Widget> RenderWidget for Flexible<W> {
     fn paint(&self, cx: &mut PaintCx<Self>, canvas: &mut Canvas, offset: &Offset) {
         cx.child(0).paint(canvas, offset)
     }
+
+    fn baseline(&self, cx: &LayoutCx<Self>) -> Option<f64> {
+        cx.child(0).baseline()
+    }
 }
 
 pub struct Expanded;
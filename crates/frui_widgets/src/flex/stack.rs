@@ -222,6 +222,19 @@ impl<WL: WidgetList, A: Directional<Output = Alignment>> RenderWidget for Stack<
             }
         }
     }
+
+    fn intrinsic_size(
+        &self,
+        cx: &LayoutCx<Self>,
+        dimension: IntrinsicDimension,
+        extent: f64,
+    ) -> f64 {
+        // Positioned children don't affect the size of a stack.
+        cx.children()
+            .filter(|child| !Stack::is_positioned(child))
+            .map(|child| child.intrinsic_size(dimension, extent))
+            .fold(0., f64::max)
+    }
 }
 
 impl<WL: WidgetList, A: Directional<Output = Alignment>> HitTest for Stack<WL, A> {
@@ -0,0 +1,164 @@
+use frui::prelude::*;
+use frui::render::*;
+
+use crate::flex::flex::AxisExt;
+use crate::*;
+
+/// Lays out its children one after another along `direction` and starts a new
+/// run once a child doesn't fit in the remaining space of the current run.
+///
+/// Each run is as long as the children in it and as thick as its thickest
+/// child.
+#[derive(RenderWidget, Builder)]
+pub struct Wrap<WL: WidgetList> {
+    pub children: WL,
+
+    /// Direction in which children are placed in a run. Runs are stacked along
+    /// the other axis.
+    pub direction: Axis,
+
+    /// If `text_direction` is set to [`Rtl`](TextDirection::Rtl), children are
+    /// placed from right to left in a horizontal [`Wrap`], and runs are
+    /// stacked from right to left in a vertical one.
+    ///
+    /// If `text_direction` is set to `None`, `Wrap` will use the value of its
+    /// ancestor [`Directionality`], or default if no ancestor found.
+    pub text_direction: Option<TextDirection>,
+
+    /// Space added between children in a run.
+    pub spacing: f64,
+
+    /// Space added between runs.
+    pub run_spacing: f64,
+
+    /// How children should be laid out within a run.
+    pub alignment: MainAxisAlignment,
+
+    /// How runs should be laid out within this [`Wrap`].
+    pub run_alignment: MainAxisAlignment,
+
+    /// How children should be aligned within a run, if they are thinner than
+    /// it. [`Stretch`] and [`Baseline`] are treated as [`Start`].
+    ///
+    /// [`Stretch`]: CrossAxisAlignment::Stretch
+    /// [`Baseline`]: CrossAxisAlignment::Baseline
+    /// [`Start`]: CrossAxisAlignment::Start
+    pub cross_axis_alignment: CrossAxisAlignment,
+}
+
+impl Wrap<()> {
+    pub fn builder() -> Self {
+        Self {
+            children: (),
+            direction: Axis::Horizontal,
+            text_direction: None,
+            spacing: 0.0,
+            run_spacing: 0.0,
+            alignment: MainAxisAlignment::Start,
+            run_alignment: MainAxisAlignment::Start,
+            cross_axis_alignment: CrossAxisAlignment::Start,
+        }
+    }
+}
+
+struct Run {
+    main_size: f64,
+    cross_size: f64,
+    child_count: usize,
+}
+
+impl<WL: WidgetList> RenderWidget for Wrap<WL> {
+    fn build<'w>(&'w self, _cx: BuildCx<'w, Self>) -> Vec<Self::Widget<'w>> {
+        self.children.get()
+    }
+
+    fn layout(&self, cx: &LayoutCx<Self>, constraints: Constraints) -> Size {
+        let axis = self.direction;
+        let main_size_max = axis.max(constraints);
+        let text_direction = Directionality::unwrap_or_default(self.text_direction, cx);
+
+        self.ensure_parent_data(cx, BoxLayoutData::default);
+
+        //
+        // Layout children and split them into runs.
+
+        let child_constraints = axis.constraints((0., main_size_max), (0., f64::INFINITY));
+
+        let mut runs: Vec<Run> = Vec::new();
+
+        for child in cx.children() {
+            let child_size = child.layout(child_constraints);
+            let child_main = child_size.main(axis);
+            let child_cross = child_size.cross(axis);
+
+            match runs.last_mut() {
+                Some(run) if run.main_size + self.spacing + child_main <= main_size_max => {
+                    run.main_size += self.spacing + child_main;
+                    run.cross_size = run.cross_size.max(child_cross);
+                    run.child_count += 1;
+                }
+                _ => runs.push(Run {
+                    main_size: child_main,
+                    cross_size: child_cross,
+                    child_count: 1,
+                }),
+            }
+        }
+
+        //
+        // Compute size of this `Wrap` widget.
+
+        let content_main = runs.iter().map(|r| r.main_size).fold(0., f64::max);
+        let content_cross = runs.iter().map(|r| r.cross_size).sum::<f64>()
+            + self.run_spacing * runs.len().saturating_sub(1) as f64;
+
+        let size = constraints.constrain(axis.size(content_main, content_cross));
+
+        //
+        // Position children.
+
+        let (mut run_offset, run_between) = self
+            .run_alignment
+            .distribute(size.cross(axis) - content_cross, runs.len());
+
+        let mut children = cx.children();
+
+        for run in runs {
+            let (mut child_offset, child_between) = self
+                .alignment
+                .distribute(size.main(axis) - run.main_size, run.child_count);
+
+            for child in children.by_ref().take(run.child_count) {
+                let child_size = child.size();
+
+                let cross_offset = run_offset
+                    + self
+                        .cross_axis_alignment
+                        .offset(run.cross_size - child_size.cross(axis));
+
+                let mut offset = axis.offset(child_offset, cross_offset);
+
+                if text_direction == TextDirection::Rtl {
+                    offset.x = size.width - offset.x - child_size.width;
+                }
+
+                child.try_parent_data_mut::<BoxLayoutData>().unwrap().offset = offset;
+
+                child_offset += child_size.main(axis) + self.spacing + child_between;
+            }
+
+            run_offset += run.cross_size + self.run_spacing + run_between;
+        }
+
+        size
+    }
+
+    fn paint(&self, cx: &mut PaintCx<Self>, canvas: &mut Canvas, offset: &Offset) {
+        for mut child in cx.children() {
+            let child_offset: Offset = child
+                .try_parent_data::<BoxLayoutData>()
+                .map_or(*offset, |d| (*offset + d.offset));
+            child.paint(canvas, &child_offset);
+        }
+    }
+}
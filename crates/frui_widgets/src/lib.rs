@@ -1,26 +1,47 @@
 #![feature(type_alias_impl_trait)]
+#![feature(min_specialization)]
 
+mod animated;
 mod basic;
 mod boxes;
+mod clip;
 mod container;
+mod custom_layout;
+mod custom_paint;
 mod event_detectors;
 mod flex;
+mod list_view;
 mod painting;
+mod paragraph;
+mod rich_text;
 mod scroll;
+mod table;
 mod testing;
 mod text;
+mod text_field;
+mod text_style;
 mod transform;
 mod widget_list;
 
+pub use self::animated::*;
 pub use self::basic::*;
 pub use self::boxes::*;
+pub use self::clip::*;
 pub use self::container::*;
+pub use self::custom_layout::*;
+pub use self::custom_paint::*;
+pub use self::event_detectors::focus::*;
 pub use self::event_detectors::keyboard::*;
 pub use self::flex::*;
+pub use self::list_view::*;
 pub use self::painting::*;
+pub use self::rich_text::*;
 pub use self::scroll::*;
+pub use self::table::*;
 pub use self::testing::*;
 pub use self::text::*;
+pub use self::text_field::*;
+pub use self::text_style::*;
 pub use self::transform::*;
 pub use self::widget_list::*;
 
@@ -0,0 +1,441 @@
+use std::{marker::PhantomData, ops::Range};
+
+use frui::prelude::*;
+use frui::render::*;
+
+use crate::Axis;
+
+/// Extent of items assumed before any item was laid out.
+const ESTIMATED_ITEM_EXTENT: f64 = 50.;
+
+/// Scrollable list of widgets which are built on demand.
+///
+/// Only items intersecting the viewport (extended by [`cache_extent`] in both
+/// directions) are built, laid out and painted, which makes it possible to
+/// display lists of any length:
+///
+/// ```ignore
+/// ListView::builder(100_000, |_, index| Text::new(format!("Row {}", index)))
+/// ```
+///
+/// Items are keyed by their index, so their state is preserved while they stay
+/// within that range. Items leaving it are dropped together with their state.
+///
+/// Positions of items which were never laid out are estimated from the average
+/// extent of the ones which were. If all items have the same extent, set
+/// [`item_extent`] to skip that estimation.
+///
+/// List takes all available space in the scroll direction. It is scrolled with
+/// the mouse wheel or through a [`ScrollController`] passed to [`controller`].
+///
+/// [`cache_extent`]: ListView::cache_extent
+/// [`item_extent`]: ListView::item_extent
+/// [`controller`]: ListView::controller
+#[derive(RenderWidget)]
+pub struct ListView<W: Widget, F: Fn(BuildCx<()>, usize) -> W> {
+    pub item_count: usize,
+    /// Builds item of given index. Context passed to it belongs to the list.
+    pub item_builder: F,
+    pub scroll_direction: Axis,
+    /// Extent of every item in the scroll direction.
+    pub item_extent: Option<f64>,
+    /// Extent of the areas before and after the viewport in which items are
+    /// built, even though they aren't visible.
+    pub cache_extent: f64,
+    pub controller: Option<ScrollController>,
+    _p: PhantomData<W>,
+}
+
+impl<W: Widget, F: Fn(BuildCx<()>, usize) -> W> ListView<W, F> {
+    pub fn builder(item_count: usize, item_builder: F) -> Self {
+        ListView {
+            item_count,
+            item_builder,
+            scroll_direction: Axis::Vertical,
+            item_extent: None,
+            cache_extent: 250.,
+            controller: None,
+            _p: PhantomData,
+        }
+    }
+
+    pub fn scroll_direction(mut self, scroll_direction: Axis) -> Self {
+        self.scroll_direction = scroll_direction;
+        self
+    }
+
+    pub fn item_extent(mut self, item_extent: f64) -> Self {
+        self.item_extent = Some(item_extent);
+        self
+    }
+
+    pub fn cache_extent(mut self, cache_extent: f64) -> Self {
+        self.cache_extent = cache_extent;
+        self
+    }
+
+    pub fn controller(mut self, controller: ScrollController) -> Self {
+        self.controller = Some(controller);
+        self
+    }
+
+    /// Items which were built. Those are items requested during the last
+    /// layout, limited to the current number of items.
+    fn built_items(&self, state: &ListViewState) -> Range<usize> {
+        let end = state.items.end.min(self.item_count);
+        state.items.start.min(end)..end
+    }
+
+    /// Leading edge of the item of given index, or the extent of the whole
+    /// list if `index` is the number of items.
+    fn item_start(&self, state: &ListViewRenderState, index: usize) -> f64 {
+        match self.item_extent {
+            Some(extent) => index as f64 * extent,
+            None => state.offsets[index],
+        }
+    }
+
+    /// Items which intersect the area between `start` and `end`.
+    fn items_between(&self, state: &ListViewRenderState, start: f64, end: f64) -> Range<usize> {
+        let count = self.item_count;
+
+        let (first, last) = match self.item_extent {
+            Some(extent) if extent > 0. => (
+                ((start / extent).floor().max(0.) as usize).min(count),
+                ((end / extent).ceil().max(0.) as usize).min(count),
+            ),
+            Some(_) => (0, count),
+            None => (
+                // First item ending after `start`.
+                state.offsets[1..].partition_point(|&offset| offset <= start),
+                // First item starting at or after `end`.
+                state.offsets[..count].partition_point(|&offset| offset < end),
+            ),
+        };
+
+        first.min(last)..last
+    }
+
+    fn along(&self, v: Vec2) -> f64 {
+        match self.scroll_direction {
+            Axis::Horizontal => v.x,
+            Axis::Vertical => v.y,
+        }
+    }
+
+    fn main(&self, size: Size) -> f64 {
+        match self.scroll_direction {
+            Axis::Horizontal => size.width,
+            Axis::Vertical => size.height,
+        }
+    }
+
+    fn cross(&self, size: Size) -> f64 {
+        match self.scroll_direction {
+            Axis::Horizontal => size.height,
+            Axis::Vertical => size.width,
+        }
+    }
+
+    fn vec(&self, main: f64) -> Vec2 {
+        match self.scroll_direction {
+            Axis::Horizontal => Vec2::new(main, 0.),
+            Axis::Vertical => Vec2::new(0., main),
+        }
+    }
+
+    /// Constraints of items, which fill the list in the cross direction.
+    fn item_constraints(&self, constraints: Constraints) -> Constraints {
+        let (min_main, max_main) = match self.item_extent {
+            Some(extent) => (extent, extent),
+            None => (0., f64::INFINITY),
+        };
+
+        match self.scroll_direction {
+            Axis::Horizontal => Constraints {
+                min_width: min_main,
+                max_width: max_main,
+                min_height: finite_or(constraints.max_height, 0.),
+                max_height: constraints.max_height,
+            },
+            Axis::Vertical => Constraints {
+                min_width: finite_or(constraints.max_width, 0.),
+                max_width: constraints.max_width,
+                min_height: min_main,
+                max_height: max_main,
+            },
+        }
+    }
+}
+
+fn finite_or(v: f64, or: f64) -> f64 {
+    if v.is_finite() {
+        v
+    } else {
+        or
+    }
+}
+
+#[doc(hidden)]
+pub struct ListViewState {
+    controller: ScrollController,
+    /// Items which should be built.
+    items: Range<usize>,
+}
+
+impl<W: Widget, F: Fn(BuildCx<()>, usize) -> W> WidgetState for ListView<W, F> {
+    type State = ListViewState;
+
+    fn create_state(&self) -> Self::State {
+        ListViewState {
+            controller: self.controller.clone().unwrap_or_default(),
+            items: 0..0,
+        }
+    }
+
+    fn mount<'a>(&'a self, cx: BuildCx<'a, Self>) {
+        if let Some(controller) = &self.controller {
+            if *controller != cx.state().controller {
+                cx.state_mut().controller = controller.clone();
+            }
+        }
+
+        cx.state().controller.attach(cx);
+    }
+}
+
+#[doc(hidden)]
+pub struct ListViewRenderState {
+    /// Extents of items in the scroll direction, measured when they were laid
+    /// out. Unused if items have a fixed extent.
+    extents: Vec<Option<f64>>,
+    /// Leading edges of all items, followed by the extent of the whole list.
+    /// Recomputed only when `extents` change. Unused if items have a fixed
+    /// extent.
+    offsets: Vec<f64>,
+}
+
+impl ListViewRenderState {
+    /// Updates positions of items, estimating extents of items which weren't
+    /// laid out yet.
+    fn update_offsets(&mut self) {
+        let (sum, count) = self
+            .extents
+            .iter()
+            .flatten()
+            .fold((0., 0), |(sum, count), extent| (sum + extent, count + 1));
+
+        let estimate = match count {
+            0 => ESTIMATED_ITEM_EXTENT,
+            _ => sum / count as f64,
+        };
+
+        self.offsets.clear();
+        self.offsets.reserve(self.extents.len() + 1);
+
+        let mut offset = 0.;
+
+        for extent in &self.extents {
+            self.offsets.push(offset);
+            offset += extent.unwrap_or(estimate);
+        }
+
+        self.offsets.push(offset);
+    }
+}
+
+impl<W: Widget, F: Fn(BuildCx<()>, usize) -> W> RenderState for ListView<W, F> {
+    type State = ListViewRenderState;
+
+    fn create_state(&self) -> Self::State {
+        ListViewRenderState {
+            extents: Vec::new(),
+            offsets: vec![0.],
+        }
+    }
+}
+
+impl<W: Widget, F: Fn(BuildCx<()>, usize) -> W> RenderWidget for ListView<W, F> {
+    fn build<'w>(&'w self, cx: BuildCx<'w, Self>) -> Vec<Self::Widget<'w>> {
+        let items = self.built_items(&cx.state());
+        let item_cx = cx.erased();
+
+        items
+            .map(|index| LocalKey::new(index, (self.item_builder)(item_cx, index)))
+            .collect()
+    }
+
+    fn layout(&self, cx: &LayoutCx<Self>, constraints: Constraints) -> Size {
+        let (controller, built) = {
+            let state = cx.widget_state();
+            (state.controller.clone(), self.built_items(&state))
+        };
+
+        let item_constraints = self.item_constraints(constraints);
+
+        let sizes = cx
+            .children()
+            .map(|child| child.layout(item_constraints))
+            .collect::<Vec<_>>();
+
+        let mut render_state = cx.render_state_mut();
+
+        if self.item_extent.is_none() {
+            let mut changed = render_state.extents.len() != self.item_count;
+            render_state.extents.resize(self.item_count, None);
+
+            for (index, size) in built.clone().zip(&sizes) {
+                let extent = Some(self.main(*size));
+
+                if render_state.extents[index] != extent {
+                    render_state.extents[index] = extent;
+                    changed = true;
+                }
+            }
+
+            if changed {
+                render_state.update_offsets();
+            }
+        }
+
+        let content = self.item_start(&render_state, self.item_count);
+        let cross = sizes
+            .iter()
+            .map(|size| self.cross(*size))
+            .fold(0., f64::max);
+
+        // Take all available space, unless it's unbounded.
+        let biggest = constraints.biggest();
+
+        let size = constraints.constrain(match self.scroll_direction {
+            Axis::Horizontal => Size::new(
+                finite_or(biggest.width, content),
+                finite_or(biggest.height, cross),
+            ),
+            Axis::Vertical => Size::new(
+                finite_or(biggest.width, cross),
+                finite_or(biggest.height, content),
+            ),
+        });
+
+        let viewport = self.main(size);
+        controller.set_metrics(size, self.vec((content - viewport).max(0.)));
+
+        let offset = self.along(controller.offset());
+
+        let needed = self.items_between(
+            &render_state,
+            offset - self.cache_extent,
+            offset + viewport + self.cache_extent,
+        );
+
+        drop(render_state);
+
+        // Items are built before the list is laid out, so if it turns out that
+        // different items are needed, the list is built and laid out again.
+        if needed != built {
+            cx.widget_state_mut().items = needed;
+        }
+
+        size
+    }
+
+    fn paint(&self, cx: &mut PaintCx<Self>, canvas: &mut Canvas, offset: &Offset) {
+        let size = cx.size();
+        let viewport = self.main(size);
+
+        let (scroll_offset, built) = {
+            let state = cx.widget_state();
+            (
+                self.along(state.controller.offset()),
+                self.built_items(&state),
+            )
+        };
+
+        // Leading and trailing edges of built items within the viewport.
+        let edges = {
+            let render_state = cx.render_state();
+
+            built
+                .map(|index| {
+                    (
+                        self.item_start(&render_state, index) - scroll_offset,
+                        self.item_start(&render_state, index + 1) - scroll_offset,
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+
+        if let Err(e) = canvas.save() {
+            log::error!("saving render context failed: {:?}", e);
+            return;
+        }
+
+        canvas.clip(DruidRect::from(Rect::from_origin_size(*offset, size)));
+
+        for ((start, end), mut child) in edges.into_iter().zip(cx.children()) {
+            // Skip items which are built only in advance.
+            if end <= 0. || start >= viewport {
+                continue;
+            }
+
+            let start = self.vec(start);
+
+            let child_offset = Offset {
+                x: offset.x + start.x,
+                y: offset.y + start.y,
+            };
+
+            child.paint(canvas, &child_offset);
+        }
+
+        if let Err(e) = canvas.restore() {
+            log::error!("restoring render context failed: {:?}", e);
+        }
+    }
+}
+
+impl<W: Widget, F: Fn(BuildCx<()>, usize) -> W> HitTest for ListView<W, F> {
+    fn hit_test<'a>(&'a self, cx: &'a mut HitTestCx<Self>, point: Point) -> bool {
+        if !cx.layout_box().contains(point) {
+            return false;
+        }
+
+        let (scroll_offset, built) = {
+            let state = cx.widget_state();
+            (state.controller.offset(), self.built_items(&state))
+        };
+
+        let starts = {
+            let render_state = cx.render_state();
+
+            built
+                .map(|index| self.vec(self.item_start(&render_state, index)))
+                .collect::<Vec<_>>()
+        };
+
+        for (start, mut child) in starts.into_iter().zip(cx.children()) {
+            if child.hit_test_with_transform(point, Affine::translate(scroll_offset - start)) {
+                break;
+            }
+        }
+
+        true
+    }
+
+    fn handle_event(&self, cx: &mut HitTestCx<Self>, event: &PointerEvent) {
+        if let PointerEvent::PointerScroll(e) = event {
+            let delta = e.0.wheel_delta;
+
+            // Mouse wheels without horizontal scrolling scroll horizontal lists.
+            let delta = match self.scroll_direction {
+                Axis::Horizontal if delta.x == 0. => Vec2::new(delta.y, 0.),
+                Axis::Horizontal => Vec2::new(delta.x, 0.),
+                Axis::Vertical => Vec2::new(0., delta.y),
+            };
+
+            let controller = cx.widget_state().controller.clone();
+            controller.jump_by(delta);
+        }
+    }
+}
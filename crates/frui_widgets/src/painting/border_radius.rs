@@ -1,4 +1,7 @@
-use frui::render::{RRect, Radius, Rect};
+use frui::{
+    animation::Lerp,
+    render::{RRect, Radius, Rect},
+};
 
 use crate::{Directional, TextDirection};
 
@@ -69,8 +72,8 @@ impl BorderRadius {
             rect.clone(),
             self.top_left,
             self.top_right,
-            self.bottom_right,
             self.bottom_left,
+            self.bottom_right,
         )
     }
 }
@@ -83,6 +86,17 @@ impl Directional for BorderRadius {
     }
 }
 
+impl Lerp for BorderRadius {
+    fn lerp(&self, other: &Self, t: f64) -> Self {
+        BorderRadius {
+            top_left: self.top_left.lerp(&other.top_left, t),
+            top_right: self.top_right.lerp(&other.top_right, t),
+            bottom_left: self.bottom_left.lerp(&other.bottom_left, t),
+            bottom_right: self.bottom_right.lerp(&other.bottom_right, t),
+        }
+    }
+}
+
 #[derive(Copy, Clone, Debug, Default, PartialEq)]
 pub struct BorderRadiusDirectional {
     pub top_start: Radius,
@@ -1,8 +1,8 @@
 use std::ops::{Add, Mul};
 
 use druid_shell::{kurbo::BezPath, piet::StrokeStyle};
-use frui::prelude::*;
 use frui::render::Rect;
+use frui::{animation::Lerp, prelude::*};
 
 use crate::EdgeInsets;
 
@@ -107,6 +107,20 @@ impl Mul<f64> for BorderSide {
     }
 }
 
+impl Lerp for BorderSide {
+    /// Style of the border jumps from one to the other when `t` reaches `0.5`.
+    fn lerp(&self, other: &Self, t: f64) -> Self {
+        BorderSide {
+            color: Lerp::lerp(&self.color, &other.color, t),
+            width: Lerp::lerp(&self.width, &other.width, t).max(0.0),
+            style: match t < 0.5 {
+                true => self.style.clone(),
+                false => other.style.clone(),
+            },
+        }
+    }
+}
+
 pub trait ShapeBorder: Add + Sized {
     fn dimensions(&self) -> EdgeInsets;
 
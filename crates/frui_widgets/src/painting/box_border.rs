@@ -5,6 +5,7 @@ use druid_shell::{
     piet::RenderContext,
 };
 use frui::{
+    animation::Lerp,
     prelude::*,
     render::{Canvas, Rect},
 };
@@ -47,6 +48,17 @@ impl Default for BoxBorder {
     }
 }
 
+impl Lerp for BoxBorder {
+    fn lerp(&self, other: &Self, t: f64) -> Self {
+        BoxBorder {
+            top: self.top.lerp(&other.top, t),
+            right: self.right.lerp(&other.right, t),
+            bottom: self.bottom.lerp(&other.bottom, t),
+            left: self.left.lerp(&other.left, t),
+        }
+    }
+}
+
 impl Add for BoxBorder {
     type Output = Option<Self>;
 
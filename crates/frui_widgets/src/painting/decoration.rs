@@ -3,6 +3,7 @@ use druid_shell::{
     piet::{kurbo::Shape, RenderContext},
 };
 use frui::{
+    animation::Lerp,
     prelude::*,
     render::{Canvas, Offset, Rect},
 };
@@ -30,6 +31,7 @@ pub enum DecorationPosition {
     Foreground,
 }
 
+#[derive(Clone, Debug, PartialEq)]
 pub struct BoxDecoration<B, BR>
 where
     B: Directional<Output = BoxBorder>,
@@ -117,6 +119,57 @@ where
     }
 }
 
+/// Missing colors and border radii are interpolated as transparent and zero
+/// respectively. Shadows present in only one of the decorations are scaled
+/// down, while borders and other properties jump from one to the other when
+/// `t` reaches `0.5`.
+impl<B, BR> Lerp for BoxDecoration<B, BR>
+where
+    B: Directional<Output = BoxBorder> + Lerp + Clone,
+    BR: Directional<Output = BorderRadius> + Lerp + Clone + Default,
+{
+    fn lerp(&self, other: &Self, t: f64) -> Self {
+        let color = match (&self.color, &other.color) {
+            (Some(a), Some(b)) => Some(Lerp::lerp(a, b, t)),
+            (Some(a), None) => Some(Lerp::lerp(a, &a.clone().with_alpha(0.), t)),
+            (None, Some(b)) => Some(Lerp::lerp(&b.clone().with_alpha(0.), b, t)),
+            (None, None) => None,
+        };
+
+        let border_radius = match (&self.border_radius, &other.border_radius) {
+            (Some(a), Some(b)) => Some(a.lerp(b, t)),
+            (Some(a), None) => Some(a.lerp(&BR::default(), t)),
+            (None, Some(b)) => Some(BR::default().lerp(b, t)),
+            (None, None) => None,
+        };
+
+        let (shape, text_direction) = match t < 0.5 {
+            true => (self.shape, self.text_direction),
+            false => (other.shape, other.text_direction),
+        };
+
+        BoxDecoration {
+            color,
+            box_shadow: lerp_shadows(&self.box_shadow, &other.box_shadow, t),
+            border: self.border.lerp(&other.border, t),
+            border_radius,
+            shape,
+            text_direction,
+        }
+    }
+}
+
+fn lerp_shadows(a: &[BoxShadow], b: &[BoxShadow], t: f64) -> Vec<BoxShadow> {
+    (0..a.len().max(b.len()))
+        .map(|n| match (a.get(n), b.get(n)) {
+            (Some(a), Some(b)) => a.lerp(b, t),
+            (Some(a), None) => a.clone() * (1.0 - t),
+            (None, Some(b)) => b.clone() * t,
+            (None, None) => unreachable!(),
+        })
+        .collect()
+}
+
 impl<B, BR> Decoration for BoxDecoration<B, BR>
 where
     B: Directional<Output = BoxBorder>,
@@ -1,4 +1,5 @@
 use frui::{
+    animation::Lerp,
     prelude::*,
     render::{Constraints, Offset, Rect, Size},
 };
@@ -236,6 +237,12 @@ impl Mul<f64> for EdgeInsets {
     }
 }
 
+impl Lerp for EdgeInsets {
+    fn lerp(&self, other: &Self, t: f64) -> Self {
+        EdgeInsets::lerp(self, *other, t)
+    }
+}
+
 impl Display for EdgeInsets {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
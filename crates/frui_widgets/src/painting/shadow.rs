@@ -2,6 +2,7 @@ use std::ops::Mul;
 
 use druid_shell::{kurbo::Affine, piet::RenderContext};
 use frui::{
+    animation::Lerp,
     prelude::*,
     render::{Canvas, Offset, Rect},
 };
@@ -58,3 +59,20 @@ impl Mul<f64> for BoxShadow {
         }
     }
 }
+
+impl Lerp for BoxShadow {
+    /// Interpolates every property except for `blur_style`, which is taken
+    /// from `other` once `t` reaches `0.5`.
+    fn lerp(&self, other: &Self, t: f64) -> Self {
+        BoxShadow {
+            color: Lerp::lerp(&self.color, &other.color, t),
+            offset: Lerp::lerp(&self.offset, &other.offset, t),
+            blur_radius: Lerp::lerp(&self.blur_radius, &other.blur_radius, t).max(0.),
+            spread_radius: Lerp::lerp(&self.spread_radius, &other.spread_radius, t),
+            blur_style: match t < 0.5 {
+                true => self.blur_style,
+                false => other.blur_style,
+            },
+        }
+    }
+}
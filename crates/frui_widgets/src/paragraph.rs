@@ -0,0 +1,472 @@
+//! Layout of a paragraph of text with options which Piet doesn't support
+//! directly: line height, letter spacing, maximal number of lines and
+//! ellipsized or faded overflow.
+//!
+//! Text is wrapped by Piet, after which each line is painted separately from
+//! that layout, moved to its own position. Lines with letter spacing are
+//! painted one character at a time, each from its own layout.
+
+use std::ops::Range;
+
+use druid_shell::piet::{
+    kurbo::{Point, Rect},
+    Color, PietTextLayout, RenderContext, TextAlignment, TextAttribute, TextLayout,
+};
+use frui::render::{Canvas, Size};
+
+use crate::{TextAlign, TextDirection, TextOverflow};
+
+/// Creates layout of given text, wrapped at given width, with additional
+/// attributes applied to given ranges.
+pub(crate) type MakeLayout<'a> =
+    dyn Fn(&str, f64, TextAlignment, &[(Range<usize>, TextAttribute)]) -> PietTextLayout + 'a;
+
+const ELLIPSIS: &str = "\u{2026}";
+
+/// Maximal number of times text is wrapped again to fit letter spacing.
+const MAX_WRAP_PASSES: usize = 6;
+
+pub(crate) struct ParagraphOptions {
+    pub align: TextAlign,
+    pub direction: TextDirection,
+    pub max_lines: Option<usize>,
+    pub overflow: TextOverflow,
+    /// Height of each line as a multiple of the font size.
+    pub line_height: Option<f64>,
+    pub letter_spacing: f64,
+    pub font_size: f64,
+    pub color: Color,
+}
+
+pub(crate) struct Paragraph {
+    /// Layout of the whole text.
+    layout: PietTextLayout,
+    lines: Vec<Line>,
+    letter_spacing: f64,
+    align: TextAlign,
+    direction: TextDirection,
+    /// Size of the text, which is the width of its widest line and the
+    /// height of all lines.
+    size: Size,
+    /// Whether lines are at the same positions as in the layout, so it can
+    /// be painted all at once.
+    is_plain: bool,
+}
+
+struct Line {
+    /// Layout of the last line which was truncated, otherwise the line is
+    /// painted from the paragraph layout.
+    truncated: Option<PietTextLayout>,
+    /// Visible text of the line in its layout.
+    range: Range<usize>,
+    /// Top of the line in its layout.
+    top: f64,
+    /// Height of the glyphs of the line.
+    height: f64,
+    /// Distance from the top of the glyphs to the baseline of the line.
+    baseline: f64,
+    /// Left edge of the text in its layout.
+    left: f64,
+    /// Width of the text, including letter spacing.
+    width: f64,
+    /// Whether the line was stretched to the width of the layout.
+    justified: bool,
+    /// Characters of the line, if it has letter spacing.
+    glyphs: Vec<Glyph>,
+    /// Position of the left edge of the text in the paragraph.
+    x: f64,
+    /// Position of the top of the glyphs in the paragraph.
+    y: f64,
+}
+
+struct Glyph {
+    layout: PietTextLayout,
+    /// Position of the layout relative to the left edge of the text and the
+    /// top of the glyphs of its line.
+    offset: Point,
+}
+
+impl Paragraph {
+    pub fn new(
+        text: &str,
+        max_width: f64,
+        options: &ParagraphOptions,
+        make: &MakeLayout,
+    ) -> Paragraph {
+        let alignment = match options.align {
+            TextAlign::Justify => TextAlignment::Justified,
+            _ => TextAlignment::Start,
+        };
+
+        let spacing = options.letter_spacing;
+        let mut wrap_width = max_width;
+
+        // Piet doesn't know about letter spacing, so we narrow the width at
+        // which lines are wrapped until spaced lines fit. Lines which can't be
+        // broken any further never fit, so the number of passes is limited.
+        let mut pass = 1;
+
+        let layout = loop {
+            let layout = make(text, wrap_width, alignment, &[]);
+
+            if spacing <= 0. || !max_width.is_finite() || wrap_width <= 0. {
+                break layout;
+            }
+
+            let widest = (0..layout.line_count())
+                .filter_map(|n| layout.line_metric(n))
+                .map(|metric| text_width(&layout, visible_range(text, &metric), spacing))
+                .fold(0., f64::max);
+
+            if widest <= max_width || pass == MAX_WRAP_PASSES || wrap_width < max_width * 0.5 {
+                break layout;
+            }
+
+            // Narrow by at least a pixel, so that lines overshooting by a
+            // fraction of it don't take the remaining passes.
+            wrap_width -= (widest - max_width).max(1.);
+            pass += 1;
+        };
+
+        let line_count = layout.line_count();
+        let visible_count = options.max_lines.map_or(line_count, |n| n.min(line_count));
+        let is_truncated = visible_count < line_count;
+
+        let mut lines = Vec::with_capacity(visible_count);
+        let mut y = 0.;
+
+        for n in 0..visible_count {
+            let metric = match layout.line_metric(n) {
+                Some(metric) => metric,
+                None => break,
+            };
+
+            let is_last = n + 1 == visible_count;
+            let range = visible_range(text, &metric);
+
+            let ends_paragraph =
+                text[..metric.end_offset.min(text.len())].ends_with('\n') || n + 1 == line_count;
+
+            let mut line = Line {
+                truncated: None,
+                left: left_edge(&layout, range.clone()),
+                width: text_width(&layout, range.clone(), spacing),
+                range,
+                top: metric.y_offset,
+                height: metric.height,
+                baseline: metric.baseline,
+                justified: options.align == TextAlign::Justify && !ends_paragraph,
+                glyphs: Vec::new(),
+                x: 0.,
+                y: 0.,
+            };
+
+            // Attributes of the truncated layout.
+            let mut attributes = Vec::new();
+
+            if is_last && is_truncated {
+                let rest = &text[metric.start_offset..paragraph_end(text, metric.start_offset)];
+
+                line.truncated = match options.overflow {
+                    TextOverflow::Ellipsis => Some(ellipsize(rest, max_width, spacing, make)),
+                    TextOverflow::Fade => {
+                        attributes = fade(rest, max_width, options, make);
+                        Some(make(rest, f64::INFINITY, TextAlignment::Start, &attributes))
+                    }
+                    TextOverflow::Clip | TextOverflow::Visible => None,
+                };
+
+                if let Some(layout) = &line.truncated {
+                    let range = 0..layout.text().len();
+
+                    line.left = left_edge(layout, range.clone());
+                    line.width = text_width(layout, range.clone(), spacing).min(max_width);
+                    line.range = range;
+                    line.top = 0.;
+                    line.justified = false;
+
+                    if let Some(metric) = layout.line_metric(0) {
+                        line.baseline = metric.baseline;
+                    }
+                }
+            }
+
+            if spacing != 0. {
+                let source = line.truncated.as_ref().unwrap_or(&layout);
+                line.glyphs = glyphs(source, &line, spacing, &attributes, make);
+            }
+
+            let line_box = options
+                .line_height
+                .map_or(metric.height, |height| height * options.font_size);
+
+            // Glyphs are centered vertically in their line.
+            line.y = y + (line_box - metric.height) / 2.;
+            y += line_box;
+
+            lines.push(line);
+        }
+
+        let width = lines.iter().map(|line| line.width).fold(0., f64::max);
+
+        let mut this = Paragraph {
+            layout,
+            lines,
+            letter_spacing: spacing,
+            align: options.align,
+            direction: options.direction,
+            size: Size::new(width, y),
+            is_plain: false,
+        };
+
+        this.align(width);
+        this
+    }
+
+    /// Size of the text, which is the width of its widest line and the height
+    /// of all its lines.
+    pub fn size(&self) -> Size {
+        self.size
+    }
+
+    /// Distance from the top of the paragraph to the baseline of its first
+    /// line.
+    pub fn first_baseline(&self) -> Option<f64> {
+        let line = self.lines.first()?;
+
+        Some(line.y + line.baseline)
+    }
+
+    /// Positions lines horizontally within a paragraph of given `width`.
+    pub fn align(&mut self, width: f64) {
+        let is_ltr = self.direction == TextDirection::Ltr;
+
+        for line in self.lines.iter_mut() {
+            let free = width - line.width;
+
+            line.x = match self.align {
+                TextAlign::Justify if line.justified => 0.,
+                TextAlign::Left => 0.,
+                TextAlign::Right => free,
+                TextAlign::Center => free / 2.,
+                TextAlign::Start | TextAlign::Justify if is_ltr => 0.,
+                TextAlign::Start | TextAlign::Justify => free,
+                TextAlign::End if is_ltr => free,
+                TextAlign::End => 0.,
+            };
+        }
+
+        self.is_plain = self.letter_spacing == 0.
+            && self
+                .lines
+                .iter()
+                .all(|line| line.truncated.is_none() && line.x == line.left && line.y == line.top);
+    }
+
+    pub fn paint(&self, canvas: &mut Canvas, origin: Point) {
+        if self.is_plain {
+            return canvas.draw_text(&self.layout, origin);
+        }
+
+        for line in &self.lines {
+            if self.letter_spacing != 0. {
+                for glyph in &line.glyphs {
+                    let x = origin.x + line.x + glyph.offset.x;
+                    let y = origin.y + line.y + glyph.offset.y;
+
+                    canvas.draw_text(&glyph.layout, Point::new(x, y));
+                }
+
+                continue;
+            }
+
+            let layout = line.truncated.as_ref().unwrap_or(&self.layout);
+
+            // Translation from the coordinates of the layout.
+            let dx = origin.x + line.x - line.left;
+            let dy = origin.y + line.y - line.top;
+
+            let top = origin.y + line.y;
+            let bottom = top + line.height;
+
+            // Glyphs may overhang their advance (e.g. in italic), which
+            // shouldn't be clipped.
+            let overhang = line.height;
+
+            let x = origin.x + line.x;
+            let clip = Rect::new(x - overhang, top, x + line.width + overhang, bottom);
+
+            paint_clipped(canvas, layout, clip, Point::new(dx, dy));
+        }
+    }
+}
+
+/// Lays out each character of the `line` of `layout` separately, spaced by
+/// `spacing`. Characters keep `attributes` of `layout` which apply to them.
+fn glyphs(
+    layout: &PietTextLayout,
+    line: &Line,
+    spacing: f64,
+    attributes: &[(Range<usize>, TextAttribute)],
+    make: &MakeLayout,
+) -> Vec<Glyph> {
+    let text = layout.text();
+    let mut glyphs = Vec::new();
+
+    for (n, (i, c)) in text[line.range.clone()].char_indices().enumerate() {
+        if c.is_whitespace() {
+            continue;
+        }
+
+        let range = line.range.start + i..line.range.start + i + c.len_utf8();
+
+        let rect = match layout.rects_for_range(range.clone()).first() {
+            Some(rect) => *rect,
+            None => continue,
+        };
+
+        let attributes = attributes
+            .iter()
+            .filter(|(r, _)| r.start <= range.start && range.end <= r.end)
+            .map(|(_, attribute)| (0..c.len_utf8(), attribute.clone()))
+            .collect::<Vec<_>>();
+
+        let glyph = make(
+            &text[range],
+            f64::INFINITY,
+            TextAlignment::Start,
+            &attributes,
+        );
+        let baseline = glyph.line_metric(0).map_or(line.baseline, |m| m.baseline);
+
+        glyphs.push(Glyph {
+            offset: Point::new(
+                rect.x0 - line.left + n as f64 * spacing,
+                line.baseline - baseline,
+            ),
+            layout: glyph,
+        });
+    }
+
+    glyphs
+}
+
+fn paint_clipped(canvas: &mut Canvas, layout: &PietTextLayout, clip: Rect, origin: Point) {
+    let r = canvas.with_save(|canvas| {
+        canvas.clip(clip);
+        canvas.draw_text(layout, origin);
+        Ok(())
+    });
+
+    if let Err(e) = r {
+        log::error!("painting text failed: {:?}", e);
+    }
+}
+
+/// Range of the line without its trailing whitespace.
+fn visible_range(text: &str, metric: &druid_shell::piet::LineMetric) -> Range<usize> {
+    let end = metric.end_offset.min(text.len());
+    let start = metric.start_offset.min(end);
+
+    start..start + text[start..end].trim_end().len()
+}
+
+fn paragraph_end(text: &str, offset: usize) -> usize {
+    text[offset..].find('\n').map_or(text.len(), |i| offset + i)
+}
+
+fn left_edge(layout: &PietTextLayout, range: Range<usize>) -> f64 {
+    if range.is_empty() {
+        return layout.hit_test_text_position(range.start).point.x;
+    }
+
+    layout
+        .rects_for_range(range)
+        .iter()
+        .map(|rect| rect.x0)
+        .fold(f64::INFINITY, f64::min)
+}
+
+/// Width of the text in `range` with `spacing` added after each character.
+fn text_width(layout: &PietTextLayout, range: Range<usize>, spacing: f64) -> f64 {
+    if range.is_empty() {
+        return 0.;
+    }
+
+    let chars = layout.text()[range.clone()].chars().count();
+
+    let (left, right) = layout
+        .rects_for_range(range)
+        .iter()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(l, r), rect| {
+            (l.min(rect.x0), r.max(rect.x1))
+        });
+
+    right - left + chars as f64 * spacing
+}
+
+/// Layout of the longest prefix of `text` followed by an ellipsis which fits
+/// in `max_width`.
+fn ellipsize(text: &str, max_width: f64, spacing: f64, make: &MakeLayout) -> PietTextLayout {
+    let make_prefix = |end: usize| {
+        let text = format!("{}{}", text[..end].trim_end(), ELLIPSIS);
+        make(&text, f64::INFINITY, TextAlignment::Start, &[])
+    };
+
+    let fits =
+        |layout: &PietTextLayout| text_width(layout, 0..layout.text().len(), spacing) <= max_width;
+
+    let ends = text
+        .char_indices()
+        .map(|(i, _)| i)
+        .skip(1)
+        .chain(std::iter::once(text.len()))
+        .collect::<Vec<_>>();
+
+    // Binary search for the longest prefix which fits.
+    let (mut low, mut high) = (0, ends.len());
+    let mut best = make_prefix(0);
+
+    while low < high {
+        let mid = (low + high) / 2;
+        let layout = make_prefix(ends[mid]);
+
+        if fits(&layout) {
+            best = layout;
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+
+    best
+}
+
+/// Colors of characters of `text` which fade out towards `max_width`.
+fn fade(
+    text: &str,
+    max_width: f64,
+    options: &ParagraphOptions,
+    make: &MakeLayout,
+) -> Vec<(Range<usize>, TextAttribute)> {
+    let plain = make(text, f64::INFINITY, TextAlignment::Start, &[]);
+
+    let fade_width = (options.font_size * 3.).min(max_width);
+    let fade_start = max_width - fade_width;
+
+    let mut attributes = Vec::new();
+
+    for (n, (i, c)) in text.char_indices().enumerate() {
+        let x = plain.hit_test_text_position(i).point.x + n as f64 * options.letter_spacing;
+
+        if x > fade_start {
+            let opacity = (1. - (x - fade_start) / fade_width).clamp(0., 1.);
+            let (r, g, b, a) = options.color.as_rgba();
+            let color = Color::rgba(r, g, b, a * opacity);
+
+            attributes.push((i..i + c.len_utf8(), TextAttribute::TextColor(color)));
+        }
+    }
+
+    attributes
+}
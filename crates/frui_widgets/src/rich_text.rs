@@ -0,0 +1,240 @@
+use std::ops::Range;
+
+use druid_shell::piet::{kurbo::Point, PietTextLayout, TextLayout};
+use frui::prelude::*;
+use frui::render::*;
+
+use crate::{DefaultTextStyle, TextStyle};
+
+/// Run of text with its own style. Children follow the text of their parent
+/// and inherit its style.
+///
+/// ```ignore
+/// TextSpan::new("Frui is ").children(vec![
+///     TextSpan::new("fast").style(TextStyle::default().weight(FontWeight::BOLD)),
+///     TextSpan::new(" and "),
+///     TextSpan::new("friendly").style(TextStyle::default().style(FontStyle::Italic)),
+/// ])
+/// ```
+#[derive(Debug, Clone, Default, Builder)]
+pub struct TextSpan {
+    pub text: String,
+    pub style: TextStyle,
+    pub children: Vec<TextSpan>,
+}
+
+impl TextSpan {
+    pub fn new(text: impl Into<String>) -> Self {
+        TextSpan {
+            text: text.into(),
+            style: TextStyle::default(),
+            children: Vec::new(),
+        }
+    }
+
+    /// Text of this span and all of its descendants.
+    pub fn to_plain_text(&self) -> String {
+        let mut text = String::new();
+        self.collect_runs(&TextStyle::default(), &mut text, &mut Vec::new());
+        text
+    }
+
+    /// Span (this one or a descendant) whose own text contains `offset` in
+    /// the text returned by [`TextSpan::to_plain_text`].
+    pub fn span_at_offset(&self, offset: usize) -> Option<&TextSpan> {
+        self.span_range_at(offset, &mut 0).map(|(_, span)| span)
+    }
+
+    /// Appends text of this span and its descendants to `text`, and their
+    /// ranges in `text` together with their resolved styles to `runs`.
+    pub(crate) fn collect_runs(
+        &self,
+        parent_style: &TextStyle,
+        text: &mut String,
+        runs: &mut Vec<(Range<usize>, TextStyle)>,
+    ) {
+        let style = parent_style.merge(&self.style);
+
+        if !self.text.is_empty() {
+            let start = text.len();
+            text.push_str(&self.text);
+            runs.push((start..text.len(), style.clone()));
+        }
+
+        for child in &self.children {
+            child.collect_runs(&style, text, runs);
+        }
+    }
+
+    /// `start` is the offset of this span, which is advanced past each visited
+    /// span.
+    fn span_range_at(&self, offset: usize, start: &mut usize) -> Option<(Range<usize>, &TextSpan)> {
+        let range = *start..*start + self.text.len();
+        *start = range.end;
+
+        if range.contains(&offset) {
+            return Some((range, self));
+        }
+
+        self.children
+            .iter()
+            .find_map(|child| child.span_range_at(offset, start))
+    }
+}
+
+/// Paragraph of text made of [`TextSpan`]s with different styles.
+///
+/// `on_tap` is called with the span which was tapped, which makes it possible
+/// to implement links:
+///
+/// ```ignore
+/// RichText::new(TextSpan::new("See ").children(vec![TextSpan::new("docs").style(link)]))
+///     .on_tap(|span| if span.text == "docs" { open_docs() })
+/// ```
+///
+/// Style of the root span is applied on top of the closest
+/// [`DefaultTextStyle`].
+#[derive(RenderWidget, Builder)]
+pub struct RichText<F: Fn(&TextSpan)> {
+    pub text: TextSpan,
+    pub on_tap: F,
+}
+
+impl RichText<fn(&TextSpan)> {
+    pub fn new(text: TextSpan) -> Self {
+        RichText {
+            text,
+            on_tap: |_| {},
+        }
+    }
+}
+
+impl<F: Fn(&TextSpan)> RichText<F> {
+    #[cfg(not(feature = "miri"))]
+    fn build_layout(&self, max_width: f64, style: &TextStyle) -> Option<PietTextLayout> {
+        use druid_shell::piet::{Text as TextExt, TextLayoutBuilder};
+
+        let mut text = String::new();
+        let mut runs = Vec::new();
+
+        self.text
+            .collect_runs(&TextStyle::default(), &mut text, &mut runs);
+
+        TEXT_FACTORY.with(|f| {
+            let mut builder = f.get().new_text_layout(text).max_width(max_width);
+
+            for attribute in style.attributes() {
+                builder = builder.default_attribute(attribute);
+            }
+
+            for (range, style) in runs {
+                for attribute in style.attributes() {
+                    builder = builder.range_attribute(range.clone(), attribute);
+                }
+            }
+
+            builder.build().ok()
+        })
+    }
+
+    #[cfg(feature = "miri")]
+    fn build_layout(&self, _: f64, _: &TextStyle) -> Option<PietTextLayout> {
+        None
+    }
+
+    /// Range and the span under `point`, local to this widget.
+    fn span_at(&self, layout: &PietTextLayout, point: Point) -> Option<(Range<usize>, &TextSpan)> {
+        let hit = layout.hit_test_point(point);
+
+        if !hit.is_inside {
+            return None;
+        }
+
+        // Hit test returns the closest grapheme boundary, which may be after
+        // the grapheme under the point.
+        let before = hit.idx.checked_sub(1);
+
+        [Some(hit.idx), before]
+            .into_iter()
+            .flatten()
+            .filter_map(|offset| self.text.span_range_at(offset, &mut 0))
+            .find(|(range, _)| {
+                layout
+                    .rects_for_range(range.clone())
+                    .iter()
+                    .any(|rect| rect.contains(point))
+            })
+    }
+}
+
+#[derive(Default)]
+pub struct RichTextState {
+    layout: Option<PietTextLayout>,
+    /// Range of the span on which the pointer was pressed.
+    pressed: Option<Range<usize>>,
+}
+
+impl<F: Fn(&TextSpan)> RenderState for RichText<F> {
+    type State = RichTextState;
+
+    fn create_state(&self) -> Self::State {
+        RichTextState::default()
+    }
+}
+
+impl<F: Fn(&TextSpan)> RenderWidget for RichText<F> {
+    fn build<'w>(&'w self, _: BuildCx<'w, Self>) -> Vec<Self::Widget<'w>> {
+        vec![] as Vec<()>
+    }
+
+    fn layout(&self, cx: &LayoutCx<Self>, constraints: Constraints) -> Size {
+        let style = TextStyle::fallback().merge(&DefaultTextStyle::of(cx));
+        let layout = self.build_layout(constraints.biggest().width, &style);
+
+        let size = match &layout {
+            Some(layout) => constraints.constrain(layout.size().into()),
+            None => constraints.smallest(),
+        };
+
+        cx.render_state_mut().layout = layout;
+
+        size
+    }
+
+    fn paint(&self, cx: &mut PaintCx<Self>, canvas: &mut Canvas, offset: &Offset) {
+        if let Some(layout) = &cx.render_state().layout {
+            RenderContext::draw_text(canvas, layout, Point::new(offset.x, offset.y));
+        }
+    }
+
+    fn baseline(&self, cx: &LayoutCx<Self>) -> Option<f64> {
+        let metric = cx.render_state().layout.as_ref()?.line_metric(0)?;
+
+        Some(metric.baseline)
+    }
+}
+
+impl<F: Fn(&TextSpan)> HitTest for RichText<F> {
+    fn handle_event(&self, cx: &mut HitTestCx<Self>, event: &PointerEvent) {
+        let mut state = cx.render_state_mut();
+
+        let span = match &state.layout {
+            Some(layout) => self.span_at(layout, event.pos()),
+            None => None,
+        };
+
+        match event {
+            PointerEvent::PointerDown(_) => state.pressed = span.map(|(range, _)| range),
+            PointerEvent::PointerUp(_) => {
+                let pressed = state.pressed.take();
+
+                if let Some((range, span)) = span {
+                    if pressed == Some(range) {
+                        (self.on_tap)(span);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
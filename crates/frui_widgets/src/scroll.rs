@@ -1,20 +1,54 @@
-//! This is a bad prototype.
-
+use druid_shell::MouseEvent;
+use frui::prelude::context::HitTestCxOS;
 use frui::prelude::*;
 use frui::render::*;
 
-#[derive(Debug, Clone, Copy)]
+/// Thickness of scrollbars.
+const SCROLLBAR_THICKNESS: f64 = 8.;
+
+/// Minimal length of a scrollbar thumb, so that it can be grabbed even if the
+/// content is very long.
+const MIN_THUMB_LENGTH: f64 = 24.;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ScrollDirection {
     Horizontal,
     Vertical,
-    // Todo: All,
+    All,
+}
+
+impl ScrollDirection {
+    fn scrolls(&self, axis: Axis) -> bool {
+        matches!(
+            (self, axis),
+            (ScrollDirection::All, _)
+                | (ScrollDirection::Horizontal, Axis::Horizontal)
+                | (ScrollDirection::Vertical, Axis::Vertical)
+        )
+    }
 }
 
-/// Todo: Finish implementation.
+/// Makes its child scrollable in given direction(s).
+///
+/// Child is laid out without a maximal size in the scroll direction and is
+/// scrolled by the mouse wheel or by dragging the scrollbar, which is drawn
+/// whenever the child doesn't fit.
+///
+/// ```ignore
+/// Scroll::builder()
+///     .scroll_direction(ScrollDirection::Vertical)
+///     .child(Column::builder().children(items))
+/// ```
+///
+/// Scroll offset can be read and changed from outside through a
+/// [`ScrollController`] passed to [`controller`].
+///
+/// [`controller`]: Scroll::controller
 #[derive(RenderWidget, Builder)]
 pub struct Scroll<W: Widget> {
     pub child: W,
     pub scroll_direction: ScrollDirection,
+    pub controller: Option<ScrollController>,
 }
 
 impl Scroll<()> {
@@ -22,13 +56,54 @@ impl Scroll<()> {
         Scroll {
             child: (),
             scroll_direction: ScrollDirection::Vertical,
+            controller: None,
         }
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Axis {
+    Horizontal,
+    Vertical,
+}
+
+impl Axis {
+    fn of(&self, v: Vec2) -> f64 {
+        match self {
+            Axis::Horizontal => v.x,
+            Axis::Vertical => v.y,
+        }
+    }
+
+    fn extent(&self, size: Size) -> f64 {
+        match self {
+            Axis::Horizontal => size.width,
+            Axis::Vertical => size.height,
+        }
+    }
+
+    fn with(&self, v: Vec2, value: f64) -> Vec2 {
+        match self {
+            Axis::Horizontal => Vec2::new(value, v.y),
+            Axis::Vertical => Vec2::new(v.x, value),
+        }
+    }
+}
+
+/// Scrollbar thumb being dragged.
+#[derive(Debug, Clone, Copy)]
+struct ScrollbarDrag {
+    axis: Axis,
+    /// Position of the pointer when it was pressed.
+    start_pos: f64,
+    /// Scroll offset when the pointer was pressed.
+    start_offset: f64,
+}
+
 #[doc(hidden)]
 pub struct ScrollState {
-    scroll_offset: Vec2,
+    controller: ScrollController,
+    drag: Option<ScrollbarDrag>,
 }
 
 impl<W: Widget> WidgetState for Scroll<W> {
@@ -36,9 +111,171 @@ impl<W: Widget> WidgetState for Scroll<W> {
 
     fn create_state(&self) -> Self::State {
         ScrollState {
-            scroll_offset: Vec2::new(0., 0.),
+            controller: self.controller.clone().unwrap_or_default(),
+            drag: None,
+        }
+    }
+
+    fn mount<'a>(&'a self, cx: BuildCx<'a, Self>) {
+        if let Some(controller) = &self.controller {
+            if *controller != cx.state().controller {
+                let mut state = cx.state_mut();
+
+                state.controller = controller.clone();
+                state.drag = None;
+            }
+        }
+
+        cx.state().controller.attach(cx);
+    }
+}
+
+/// Geometry of a scrollbar along one axis, local to the [`Scroll`].
+struct Scrollbar {
+    track: DruidRect,
+    thumb: DruidRect,
+    /// How much the scroll offset changes when the thumb moves by one unit.
+    ratio: f64,
+}
+
+impl<W: Widget> Scroll<W> {
+    /// Maximal scroll offset, which is how much the child overflows the
+    /// viewport in directions in which it is scrollable.
+    fn max_offset(&self, viewport: Size, content: Size) -> Vec2 {
+        let overflow = |axis: Axis| match self.scroll_direction.scrolls(axis) {
+            true => (axis.extent(content) - axis.extent(viewport)).max(0.),
+            false => 0.,
+        };
+
+        Vec2::new(overflow(Axis::Horizontal), overflow(Axis::Vertical))
+    }
+
+    fn scrollbar(
+        &self,
+        axis: Axis,
+        offset: Vec2,
+        viewport: Size,
+        content: Size,
+    ) -> Option<Scrollbar> {
+        let max = axis.of(self.max_offset(viewport, content));
+
+        if max <= 0. {
+            return None;
+        }
+
+        let (width, height) = (viewport.width, viewport.height);
+
+        let track = match axis {
+            Axis::Horizontal => DruidRect::new(0., height - SCROLLBAR_THICKNESS, width, height),
+            Axis::Vertical => DruidRect::new(width - SCROLLBAR_THICKNESS, 0., width, height),
+        };
+
+        let track_length = axis.extent(viewport);
+        let content_length = axis.extent(content);
+
+        let thumb_length = (track_length * track_length / content_length)
+            .max(MIN_THUMB_LENGTH)
+            .min(track_length);
+
+        let travel = track_length - thumb_length;
+        let start = axis.of(offset) / max * travel;
+
+        let thumb = match axis {
+            Axis::Horizontal => DruidRect::new(start, track.y0, start + thumb_length, track.y1),
+            Axis::Vertical => DruidRect::new(track.x0, start, track.x1, start + thumb_length),
+        };
+
+        Some(Scrollbar {
+            track,
+            thumb,
+            ratio: if travel > 0. { max / travel } else { 0. },
+        })
+    }
+
+    fn scrollbars(&self, offset: Vec2, viewport: Size, content: Size) -> Vec<(Axis, Scrollbar)> {
+        [Axis::Horizontal, Axis::Vertical]
+            .into_iter()
+            .filter_map(|axis| {
+                let scrollbar = self.scrollbar(axis, offset, viewport, content)?;
+                Some((axis, scrollbar))
+            })
+            .collect()
+    }
+
+    fn handle_scroll(&self, cx: &mut HitTestCx<Self>, event: &MouseEvent) {
+        let mut delta = event.wheel_delta;
+
+        // Mouse wheels without horizontal scrolling scroll horizontal views.
+        if self.scroll_direction == ScrollDirection::Horizontal && delta.x == 0. {
+            delta = Vec2::new(delta.y, 0.);
+        }
+
+        let controller = cx.widget_state().controller.clone();
+        controller.jump_by(delta);
+    }
+
+    fn handle_pointer_down(&self, cx: &mut HitTestCx<Self>, event: &MouseEvent) {
+        let (viewport, content) = match sizes(cx) {
+            Some(sizes) => sizes,
+            None => return,
+        };
+
+        let controller = cx.widget_state().controller.clone();
+        let offset = controller.offset();
+
+        for (axis, scrollbar) in self.scrollbars(offset, viewport, content) {
+            if !scrollbar.track.contains(event.pos) {
+                continue;
+            }
+
+            let pos = axis.of(event.pos.to_vec2());
+
+            if scrollbar.thumb.contains(event.pos) {
+                cx.widget_state_mut().drag = Some(ScrollbarDrag {
+                    axis,
+                    start_pos: pos,
+                    start_offset: axis.of(offset),
+                });
+            } else {
+                // Pressing the track outside of the thumb scrolls by a page
+                // towards the pointer.
+                let page = axis.extent(viewport);
+                let page = match pos < axis.of(scrollbar.thumb.origin().to_vec2()) {
+                    true => -page,
+                    false => page,
+                };
+
+                controller.jump_to(axis.with(offset, axis.of(offset) + page));
+            }
+
+            return;
         }
     }
+
+    fn handle_drag(&self, cx: &mut HitTestCx<Self>, event: &MouseEvent, drag: ScrollbarDrag) {
+        let (viewport, content) = match sizes(cx) {
+            Some(sizes) => sizes,
+            None => return,
+        };
+
+        let controller = cx.widget_state().controller.clone();
+        let offset = controller.offset();
+
+        let scrollbar = match self.scrollbar(drag.axis, offset, viewport, content) {
+            Some(scrollbar) => scrollbar,
+            None => return,
+        };
+
+        let moved = drag.axis.of(event.pos.to_vec2()) - drag.start_pos;
+        let target = drag.start_offset + moved * scrollbar.ratio;
+
+        controller.jump_to(drag.axis.with(offset, target));
+    }
+}
+
+/// Sizes of the viewport and of the child.
+fn sizes(cx: &HitTestCxOS) -> Option<(Size, Size)> {
+    Some((cx.layout_box(), cx.child(0)?.layout_box()))
 }
 
 impl<W: Widget> RenderWidget for Scroll<W> {
@@ -47,40 +284,125 @@ impl<W: Widget> RenderWidget for Scroll<W> {
     }
 
     fn layout(&self, cx: &LayoutCx<Self>, constraints: Constraints) -> Size {
-        let child_constraints = match self.scroll_direction {
-            ScrollDirection::Horizontal => Constraints {
-                min_width: 0.,
-                max_width: f64::INFINITY,
-                ..constraints
+        let mut child_constraints = constraints;
+
+        if self.scroll_direction.scrolls(Axis::Horizontal) {
+            child_constraints.min_width = 0.;
+            child_constraints.max_width = f64::INFINITY;
+        }
+
+        if self.scroll_direction.scrolls(Axis::Vertical) {
+            child_constraints.min_height = 0.;
+            child_constraints.max_height = f64::INFINITY;
+        }
+
+        let child_size = cx.child(0).layout(child_constraints);
+
+        // Take all available space, unless it's unbounded.
+        let biggest = constraints.biggest();
+
+        let size = constraints.constrain(Size::new(
+            match biggest.width.is_finite() {
+                true => biggest.width,
+                false => child_size.width,
             },
-            ScrollDirection::Vertical => Constraints {
-                min_height: 0.,
-                max_height: f64::INFINITY,
-                ..constraints
+            match biggest.height.is_finite() {
+                true => biggest.height,
+                false => child_size.height,
             },
-        };
+        ));
 
-        cx.child(0).layout(child_constraints);
+        let max_offset = self.max_offset(size, child_size);
+        cx.widget_state().controller.set_metrics(size, max_offset);
 
-        constraints.biggest()
+        size
     }
 
     fn paint(&self, cx: &mut PaintCx<Self>, canvas: &mut Canvas, offset: &Offset) {
+        let viewport = cx.size();
+        let content = cx.child(0).size();
+        let scroll_offset = cx.widget_state().controller.offset();
+
         if let Err(e) = canvas.save() {
             log::error!("saving render context failed: {:?}", e);
             return;
         }
 
-        let viewport = Rect::from_origin_size(*offset, cx.size());
-        canvas.clip(druid_shell::piet::kurbo::Rect::from(viewport));
-        canvas.transform(Affine::translate(-cx.widget_state().scroll_offset));
+        canvas.clip(DruidRect::from(Rect::from_origin_size(*offset, viewport)));
 
-        cx.child(0).paint(canvas, offset);
+        let child_offset = Offset {
+            x: offset.x - scroll_offset.x,
+            y: offset.y - scroll_offset.y,
+        };
+
+        cx.child(0).paint(canvas, &child_offset);
+
+        let origin = Vec2::new(offset.x, offset.y);
+        let dragged = cx.widget_state().drag.map(|drag| drag.axis);
+
+        for (axis, scrollbar) in self.scrollbars(scroll_offset, viewport, content) {
+            let alpha = if dragged == Some(axis) { 0xc0 } else { 0x80 };
+            let brush = canvas.solid_brush(Color::rgba8(0x80, 0x80, 0x80, alpha));
+
+            canvas.fill(scrollbar.thumb + origin, &brush);
+        }
 
         if let Err(e) = canvas.restore() {
             log::error!("restoring render context failed: {:?}", e);
         }
+    }
+}
+
+impl<W: Widget> HitTest for Scroll<W> {
+    fn hit_test<'a>(&'a self, cx: &'a mut HitTestCx<Self>, point: Point) -> bool {
+        if !cx.layout_box().contains(point) {
+            return false;
+        }
+
+        let (viewport, content) = match sizes(cx) {
+            Some(sizes) => sizes,
+            None => return true,
+        };
+
+        let offset = cx.widget_state().controller.offset();
+
+        // Scrollbars are painted over the child.
+        let on_scrollbar = self
+            .scrollbars(offset, viewport, content)
+            .iter()
+            .any(|(_, scrollbar)| scrollbar.track.contains(point));
 
-        // Todo: Draw scroll bar.
+        if !on_scrollbar {
+            for mut child in cx.children() {
+                if child.hit_test_with_transform(point, Affine::translate(offset)) {
+                    break;
+                }
+            }
+        }
+
+        true
+    }
+
+    fn handle_event(&self, cx: &mut HitTestCx<Self>, event: &PointerEvent) {
+        let drag = cx.widget_state().drag;
+
+        match event {
+            PointerEvent::PointerScroll(e) => self.handle_scroll(cx, &e.0),
+            PointerEvent::PointerDown(e) => self.handle_pointer_down(cx, &e.0),
+            PointerEvent::PointerMove(e) => {
+                if let Some(drag) = drag {
+                    match e.0.buttons.has_left() {
+                        true => self.handle_drag(cx, &e.0, drag),
+                        false => cx.widget_state_mut().drag = None,
+                    }
+                }
+            }
+            PointerEvent::PointerUp(_) => {
+                if drag.is_some() {
+                    cx.widget_state_mut().drag = None;
+                }
+            }
+            _ => {}
+        }
     }
 }
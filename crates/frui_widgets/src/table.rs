@@ -0,0 +1,431 @@
+use std::ops::{Deref, DerefMut};
+
+use frui::prelude::*;
+use frui::render::*;
+
+use crate::*;
+
+/// Describes how wide a column of a [`Table`] is.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TableColumnWidth {
+    /// Column is exactly this wide.
+    Fixed(f64),
+    /// Column takes this fraction of the maximal width of the table. Takes no
+    /// space if the width of the table is unbounded.
+    Fraction(f64),
+    /// Column takes a share of the space left by other columns, proportional
+    /// to this flex factor. If the width of the table is unbounded, it's as
+    /// wide as its widest cell instead.
+    Flex(f64),
+    /// Column is as wide as the maximal intrinsic width of its widest cell.
+    Intrinsic,
+}
+
+/// How a cell is placed vertically in its row of a [`Table`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TableCellVerticalAlignment {
+    Top,
+    Middle,
+    Bottom,
+    /// Cells are aligned by their baselines. Cells without a baseline are
+    /// placed at the top.
+    Baseline,
+    /// Cell is as tall as its row. It doesn't affect the height of the row.
+    Fill,
+}
+
+/// Row of cells of a [`Table`].
+#[derive(Builder)]
+pub struct TableRow<WL: WidgetList> {
+    pub children: WL,
+
+    /// Decoration painted behind cells of this row.
+    pub decoration: Option<DefaultBoxDecoration>,
+}
+
+impl TableRow<()> {
+    pub fn builder() -> Self {
+        Self {
+            children: (),
+            decoration: None,
+        }
+    }
+}
+
+/// Lays out its children in rows, such that cells of each column are equally
+/// wide.
+///
+/// Every row must have the same number of cells. Each row is as tall as its
+/// tallest cell.
+#[derive(RenderWidget, Builder)]
+pub struct Table<WL: WidgetList> {
+    pub rows: Vec<TableRow<WL>>,
+
+    /// Widths of subsequent columns. Columns which don't have a width
+    /// specified use `default_column_width`.
+    pub column_widths: Vec<TableColumnWidth>,
+
+    pub default_column_width: TableColumnWidth,
+
+    /// Vertical alignment of cells which aren't wrapped in a [`TableCell`]
+    /// specifying their own.
+    pub default_vertical_alignment: TableCellVerticalAlignment,
+
+    /// If `text_direction` is set to [`Rtl`](TextDirection::Rtl), columns are
+    /// placed from right to left.
+    ///
+    /// If `text_direction` is set to `None`, `Table` will use the value of its
+    /// ancestor [`Directionality`], or default if no ancestor found.
+    pub text_direction: Option<TextDirection>,
+}
+
+impl Table<()> {
+    pub fn builder() -> Self {
+        Self {
+            rows: Vec::new(),
+            column_widths: Vec::new(),
+            default_column_width: TableColumnWidth::Flex(1.0),
+            default_vertical_alignment: TableCellVerticalAlignment::Top,
+            text_direction: None,
+        }
+    }
+}
+
+impl<WL: WidgetList> Table<WL> {
+    fn column_count(&self) -> usize {
+        self.rows.first().map_or(0, |row| row.children.get().len())
+    }
+
+    fn column_width(&self, column: usize) -> TableColumnWidth {
+        self.column_widths
+            .get(column)
+            .copied()
+            .unwrap_or(self.default_column_width)
+    }
+
+    fn vertical_alignment(&self, cell: &LayoutCxOS) -> TableCellVerticalAlignment {
+        cell.try_parent_data::<TableCellData>()
+            .and_then(|d| d.vertical_alignment)
+            .unwrap_or(self.default_vertical_alignment)
+    }
+
+    /// Returns the largest intrinsic size of cells in given `column`.
+    fn max_cell_size(
+        &self,
+        cx: &LayoutCxOS,
+        column: usize,
+        dimension: IntrinsicDimension,
+        extent: f64,
+    ) -> f64 {
+        let columns = self.column_count();
+
+        (0..self.rows.len())
+            .map(|row| {
+                cx.child(row * columns + column)
+                    .intrinsic_size(dimension, extent)
+            })
+            .fold(0., f64::max)
+    }
+
+    /// Computes widths of columns of a table which can be at most `max_width`
+    /// wide.
+    fn compute_column_widths(&self, cx: &LayoutCxOS, max_width: f64) -> Vec<f64> {
+        let columns = self.column_count();
+        let bounded = max_width < f64::INFINITY;
+
+        let mut flexes = vec![0.; columns];
+
+        let mut widths = (0..columns)
+            .map(|column| match self.column_width(column) {
+                TableColumnWidth::Fixed(width) => width,
+                TableColumnWidth::Fraction(fraction) if bounded => fraction * max_width,
+                TableColumnWidth::Fraction(_) => 0.,
+                TableColumnWidth::Flex(flex) if bounded => {
+                    flexes[column] = flex;
+                    0.
+                }
+                TableColumnWidth::Flex(_) | TableColumnWidth::Intrinsic => {
+                    self.max_cell_size(cx, column, IntrinsicDimension::MaxWidth, f64::INFINITY)
+                }
+            })
+            .collect::<Vec<_>>();
+
+        //
+        // Distribute free space between flexible columns.
+
+        let total_flex = flexes.iter().sum::<f64>();
+
+        if total_flex > 0. {
+            let free_space = (max_width - widths.iter().sum::<f64>()).max(0.);
+
+            for (width, flex) in widths.iter_mut().zip(flexes) {
+                *width += free_space * flex / total_flex;
+            }
+        }
+
+        widths
+    }
+}
+
+impl<WL: WidgetList> RenderWidget for Table<WL> {
+    fn build<'w>(&'w self, _cx: BuildCx<'w, Self>) -> Vec<Self::Widget<'w>> {
+        let rows = self
+            .rows
+            .iter()
+            .map(|row| row.children.get())
+            .collect::<Vec<_>>();
+
+        assert!(
+            rows.windows(2).all(|rows| rows[0].len() == rows[1].len()),
+            "every table row must have the same number of cells"
+        );
+
+        rows.into_iter().flatten().collect()
+    }
+
+    fn layout(&self, cx: &LayoutCx<Self>, constraints: Constraints) -> Size {
+        let columns = self.column_count();
+        let text_direction = Directionality::unwrap_or_default(self.text_direction, cx);
+
+        self.ensure_parent_data(cx, TableCellData::default);
+
+        //
+        // Compute widths of columns.
+
+        let widths = self.compute_column_widths(cx, constraints.biggest().width);
+
+        let width = constraints.constrain_width(widths.iter().sum());
+
+        let lefts = widths
+            .iter()
+            .scan(0., |left, width| {
+                let column_left = *left;
+                *left += width;
+                Some(column_left)
+            })
+            .collect::<Vec<_>>();
+
+        //
+        // Layout and position cells, row by row.
+
+        let mut row_tops = Vec::with_capacity(self.rows.len() + 1);
+        let mut row_top = 0.;
+
+        for row in 0..self.rows.len() {
+            let cells = (0..columns)
+                .map(|column| cx.child(row * columns + column))
+                .collect::<Vec<_>>();
+
+            let alignments = cells
+                .iter()
+                .map(|cell| self.vertical_alignment(cell))
+                .collect::<Vec<_>>();
+
+            let mut baselines = vec![None; columns];
+            let mut row_height = 0f64;
+            let (mut above, mut below) = (0f64, 0f64);
+
+            for (column, cell) in cells.iter().enumerate() {
+                if alignments[column] == TableCellVerticalAlignment::Fill {
+                    continue;
+                }
+
+                let size = cell.layout(Constraints::new_tight_for(Some(widths[column]), None));
+
+                if alignments[column] == TableCellVerticalAlignment::Baseline {
+                    baselines[column] = cell.baseline();
+                }
+
+                match baselines[column] {
+                    Some(baseline) => {
+                        above = above.max(baseline);
+                        below = below.max(size.height - baseline);
+                    }
+                    None => row_height = row_height.max(size.height),
+                }
+            }
+
+            row_height = row_height.max(above + below);
+
+            for (column, cell) in cells.iter().enumerate() {
+                if alignments[column] == TableCellVerticalAlignment::Fill {
+                    cell.layout(Constraints::new_tight(Size::new(
+                        widths[column],
+                        row_height,
+                    )));
+                }
+
+                let free_space = row_height - cell.size().height;
+
+                let cell_top = match alignments[column] {
+                    TableCellVerticalAlignment::Top | TableCellVerticalAlignment::Fill => 0.,
+                    TableCellVerticalAlignment::Middle => free_space / 2.,
+                    TableCellVerticalAlignment::Bottom => free_space,
+                    TableCellVerticalAlignment::Baseline => {
+                        baselines[column].map_or(0., |baseline| above - baseline)
+                    }
+                };
+
+                let cell_left = match text_direction {
+                    TextDirection::Ltr => lefts[column],
+                    TextDirection::Rtl => width - lefts[column] - widths[column],
+                };
+
+                cell.try_parent_data_mut::<TableCellData>().unwrap().offset =
+                    Offset::new(cell_left, row_top + cell_top);
+            }
+
+            row_tops.push(row_top);
+            row_top += row_height;
+        }
+
+        row_tops.push(row_top);
+
+        cx.render_state_mut().row_tops = row_tops;
+
+        constraints.constrain(Size::new(width, row_top))
+    }
+
+    fn paint(&self, cx: &mut PaintCx<Self>, canvas: &mut Canvas, offset: &Offset) {
+        let width = cx.size().width;
+
+        {
+            let render_state = cx.render_state();
+
+            for (n, row) in self.rows.iter().enumerate() {
+                if let Some(decoration) = &row.decoration {
+                    let top = render_state.row_tops[n];
+                    let bottom = render_state.row_tops[n + 1];
+
+                    let row_offset = *offset + Offset::new(0., top);
+                    let rect = Rect::from_origin_size(&row_offset, Size::new(width, bottom - top));
+
+                    decoration.paint(canvas, rect, &row_offset);
+                }
+            }
+        }
+
+        for mut child in cx.children() {
+            let child_offset: Offset = child
+                .try_parent_data::<TableCellData>()
+                .map_or(*offset, |d| (*offset + d.offset));
+            child.paint(canvas, &child_offset);
+        }
+    }
+
+    fn intrinsic_size(
+        &self,
+        cx: &LayoutCx<Self>,
+        dimension: IntrinsicDimension,
+        extent: f64,
+    ) -> f64 {
+        let columns = self.column_count();
+
+        if dimension.is_width() {
+            // Fractional columns depend on the width of the table, so they
+            // don't add to its intrinsic width.
+            (0..columns)
+                .map(|column| match self.column_width(column) {
+                    TableColumnWidth::Fixed(width) => width,
+                    TableColumnWidth::Fraction(_) => 0.,
+                    _ => self.max_cell_size(cx, column, dimension, f64::INFINITY),
+                })
+                .sum()
+        } else {
+            let widths = self.compute_column_widths(cx, extent);
+
+            (0..self.rows.len())
+                .map(|row| {
+                    (0..columns)
+                        .map(|column| {
+                            cx.child(row * columns + column)
+                                .intrinsic_size(dimension, widths[column])
+                        })
+                        .fold(0., f64::max)
+                })
+                .sum()
+        }
+    }
+}
+
+#[doc(hidden)]
+pub struct TableRenderState {
+    /// Vertical offsets of edges of subsequent rows.
+    row_tops: Vec<f64>,
+}
+
+impl<WL: WidgetList> RenderState for Table<WL> {
+    type State = TableRenderState;
+
+    fn create_state(&self) -> Self::State {
+        TableRenderState {
+            row_tops: Vec::new(),
+        }
+    }
+}
+
+/// Used by [`TableCell`] to determine the vertical alignment of a cell.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TableCellData {
+    vertical_alignment: Option<TableCellVerticalAlignment>,
+    box_data: BoxLayoutData,
+}
+
+impl Deref for TableCellData {
+    type Target = BoxLayoutData;
+
+    fn deref(&self) -> &Self::Target {
+        &self.box_data
+    }
+}
+
+impl DerefMut for TableCellData {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.box_data
+    }
+}
+
+/// Overrides the vertical alignment of a cell of a [`Table`].
+#[derive(RenderWidget, Builder)]
+pub struct TableCell<W: Widget> {
+    pub vertical_alignment: Option<TableCellVerticalAlignment>,
+    pub child: W,
+}
+
+impl TableCell<()> {
+    pub fn builder() -> Self {
+        Self {
+            vertical_alignment: None,
+            child: (),
+        }
+    }
+}
+
+impl<W: Widget> ParentData for TableCell<W> {
+    type Data = TableCellData;
+
+    fn create_data(&self) -> Self::Data {
+        TableCellData {
+            vertical_alignment: self.vertical_alignment,
+            box_data: BoxLayoutData::default(),
+        }
+    }
+}
+
+impl<W: Widget> RenderWidget for TableCell<W> {
+    fn build<'w>(&'w self, _: BuildCx<'w, Self>) -> Vec<Self::Widget<'w>> {
+        vec![&self.child]
+    }
+
+    fn layout(&self, cx: &LayoutCx<Self>, constraints: Constraints) -> Size {
+        constraints.constrain(cx.child(0).layout(constraints))
+    }
+
+    fn paint(&self, cx: &mut PaintCx<Self>, canvas: &mut Canvas, offset: &Offset) {
+        cx.child(0).paint(canvas, offset)
+    }
+
+    fn baseline(&self, cx: &LayoutCx<Self>) -> Option<f64> {
+        cx.child(0).baseline()
+    }
+}
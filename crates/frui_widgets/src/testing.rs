@@ -54,4 +54,8 @@ impl<W: Widget> RenderWidget for DebugContainer<W> {
 
         canvas.stroke_styled(rect, brush, 2., &StrokeStyle::new().line_cap(LineCap::Butt));
     }
+
+    fn baseline(&self, cx: &LayoutCx<Self>) -> Option<f64> {
+        cx.child(0).baseline()
+    }
 }
@@ -1,10 +1,9 @@
 use frui::prelude::*;
 use frui::render::*;
 
-use druid_shell::piet::{
-    kurbo::Point, Color, FontFamily, FontWeight, PietTextLayout, Text as TextExt, TextLayout,
-    TextLayoutBuilder,
-};
+use druid_shell::piet::{kurbo::Point, Color, FontFamily, FontWeight};
+
+use crate::{paragraph::Paragraph, DefaultTextStyle, Directionality, TextStyle};
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum TextDirection {
@@ -24,103 +23,260 @@ pub trait Directional {
     fn resolve(&self, text_direction: &TextDirection) -> Self::Output;
 }
 
+/// Horizontal alignment of lines of text.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum TextAlign {
+    Left,
+    Right,
+    Center,
+    /// Stretches lines which end with a soft line break to the width of the
+    /// text. Other lines are aligned to the start.
+    Justify,
+    /// Left for left-to-right text and right for right-to-left text.
+    Start,
+    /// Right for left-to-right text and left for right-to-left text.
+    End,
+}
+
+impl Default for TextAlign {
+    fn default() -> Self {
+        TextAlign::Start
+    }
+}
+
+/// How text which doesn't fit in its widget, or which has more lines than
+/// [`Text::max_lines`], is displayed.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum TextOverflow {
+    /// Clips text to the size of the widget.
+    Clip,
+    /// Fades out the end of the last line.
+    Fade,
+    /// Ends the last line with an ellipsis.
+    Ellipsis,
+    /// Paints text outside of the widget.
+    Visible,
+}
+
+impl Default for TextOverflow {
+    fn default() -> Self {
+        TextOverflow::Clip
+    }
+}
+
+/// Paragraph of text with a single style.
+///
+/// Truncated single-line label:
+///
+/// ```ignore
+/// Text::new("A very long label")
+///     .max_lines(1)
+///     .overflow(TextOverflow::Ellipsis)
+/// ```
 #[derive(RenderWidget, Builder)]
 pub struct Text<S: AsRef<str>> {
     text: S,
-    size: f64,
-    color: Color,
-    weight: FontWeight,
-    family: FontFamily,
+    /// Fields which aren't set are taken from the closest [`DefaultTextStyle`].
+    size: Option<f64>,
+    color: Option<Color>,
+    weight: Option<FontWeight>,
+    family: Option<FontFamily>,
+    text_align: TextAlign,
+    /// Direction of the text. If not set, the direction of the closest
+    /// [`Directionality`] is used.
+    text_direction: Option<TextDirection>,
+    /// Maximal number of lines. Lines after it are hidden and the last
+    /// visible line overflows according to [`Text::overflow`].
+    max_lines: Option<usize>,
+    overflow: TextOverflow,
+    /// Height of each line as a multiple of the font size. If not set, lines
+    /// are as high as their font.
+    line_height: Option<f64>,
+    /// Space added after each character.
+    letter_spacing: f64,
 }
 
 impl<S: AsRef<str>> Text<S> {
     pub fn new(string: S) -> Self {
         Self {
             text: string,
-            size: 16.,
-            color: Color::WHITE,
-            weight: FontWeight::default(),
-            // Layout of `FontFamily::SYSTEM_UI` is incredibly slow. Other fonts
-            // seem to render just fine. This issue is related to Piet.
-            //
-            // For now, the default will be `FontFamily::MONOSPACE`.
-            family: FontFamily::MONOSPACE,
+            size: None,
+            color: None,
+            weight: None,
+            family: None,
+            text_align: TextAlign::default(),
+            text_direction: None,
+            max_lines: None,
+            overflow: TextOverflow::default(),
+            line_height: None,
+            letter_spacing: 0.,
         }
     }
-}
 
-#[cfg(not(feature = "miri"))]
-impl<S: AsRef<str>> RenderState for Text<S> {
-    type State = PietTextLayout;
+    /// Style of this text, with fields which aren't set taken from the closest
+    /// [`DefaultTextStyle`].
+    fn style(&self, cx: &LayoutCx<Self>) -> TextStyle {
+        let own_style = TextStyle {
+            family: self.family.clone(),
+            size: self.size,
+            weight: self.weight,
+            color: self.color.clone(),
+            ..TextStyle::default()
+        };
 
-    fn create_state(&self) -> Self::State {
-        TEXT_FACTORY.with(|f| f.get().new_text_layout("").build().unwrap())
+        TextStyle::fallback()
+            .merge(&DefaultTextStyle::of(cx))
+            .merge(&own_style)
     }
-}
 
-#[cfg(not(feature = "miri"))]
-impl<S: AsRef<str>> RenderWidget for Text<S> {
-    fn build<'w>(&'w self, _: BuildCx<'w, Self>) -> Vec<Self::Widget<'w>> {
-        vec![] as Vec<()>
-    }
+    #[cfg(not(feature = "miri"))]
+    fn build_paragraph(
+        &self,
+        max_width: f64,
+        direction: TextDirection,
+        style: &TextStyle,
+    ) -> Option<Paragraph> {
+        use crate::paragraph::ParagraphOptions;
+        use druid_shell::piet::{Text as TextExt, TextAlignment, TextAttribute, TextLayoutBuilder};
+        use std::ops::Range;
 
-    fn layout(&self, cx: &LayoutCx<Self>, constraints: Constraints) -> Size {
-        let max_width = constraints.biggest().width;
-
-        *cx.render_state_mut() = TEXT_FACTORY.with(|f| {
-            f.get()
-                .new_text_layout(self.text.as_ref().to_owned())
-                .font(self.family.clone(), self.size)
-                .text_color(self.color.clone())
-                .range_attribute(.., self.weight)
-                .max_width(max_width)
-                .build()
-                .unwrap()
-        });
+        let make = |text: &str,
+                    max_width: f64,
+                    alignment: TextAlignment,
+                    attributes: &[(Range<usize>, TextAttribute)]| {
+            TEXT_FACTORY.with(|f| {
+                let mut builder = f
+                    .get()
+                    .new_text_layout(text.to_owned())
+                    .alignment(alignment)
+                    .max_width(max_width);
 
-        let text_size = cx.render_state().size().into();
+                for attribute in style.attributes() {
+                    builder = builder.default_attribute(attribute);
+                }
 
-        constraints.constrain(text_size)
+                for (range, attribute) in attributes {
+                    builder = builder.range_attribute(range.clone(), attribute.clone());
+                }
+
+                builder.build().unwrap()
+            })
+        };
+
+        let options = ParagraphOptions {
+            align: self.text_align,
+            direction,
+            max_lines: self.max_lines,
+            overflow: self.overflow,
+            line_height: self.line_height,
+            letter_spacing: self.letter_spacing,
+            font_size: style.size.unwrap_or_default(),
+            color: style.color.clone().unwrap_or(Color::WHITE),
+        };
+
+        Some(Paragraph::new(
+            self.text.as_ref(),
+            max_width,
+            &options,
+            &make,
+        ))
     }
 
-    fn paint(&self, cx: &mut PaintCx<Self>, canvas: &mut Canvas, offset: &Offset) {
-        RenderContext::draw_text(
-            canvas,
-            &cx.render_state(),
-            Point {
-                x: offset.x,
-                y: offset.y,
-            },
-        );
+    #[cfg(feature = "miri")]
+    fn build_paragraph(&self, _: f64, _: TextDirection, _: &TextStyle) -> Option<Paragraph> {
+        None
     }
 }
 
-#[cfg(feature = "miri")]
-pub struct TextRenderState([u8; 30]);
+pub struct TextRenderState {
+    paragraph: Option<Paragraph>,
+}
 
-#[cfg(feature = "miri")]
 impl<S: AsRef<str>> RenderState for Text<S> {
     type State = TextRenderState;
 
     fn create_state(&self) -> Self::State {
-        TextRenderState([1; 30])
+        TextRenderState { paragraph: None }
     }
 }
 
-#[cfg(feature = "miri")]
 impl<S: AsRef<str>> RenderWidget for Text<S> {
     fn build<'w>(&'w self, _: BuildCx<'w, Self>) -> Vec<Self::Widget<'w>> {
         vec![] as Vec<()>
     }
 
     fn layout(&self, cx: &LayoutCx<Self>, constraints: Constraints) -> Size {
-        let _: &mut TextRenderState = &mut cx.render_state_mut();
+        let direction = Directionality::unwrap_or_default(self.text_direction, cx);
+        let style = self.style(cx);
+        let mut paragraph = self.build_paragraph(constraints.biggest().width, direction, &style);
+
+        let size = match &mut paragraph {
+            Some(paragraph) => {
+                let size = constraints.constrain(paragraph.size());
+                paragraph.align(size.width);
+                size
+            }
+            None => constraints.smallest(),
+        };
+
+        cx.render_state_mut().paragraph = paragraph;
+
+        size
+    }
+
+    fn paint(&self, cx: &mut PaintCx<Self>, canvas: &mut Canvas, offset: &Offset) {
+        let size = cx.size();
+        let state = cx.render_state();
+
+        let paragraph = match &state.paragraph {
+            Some(paragraph) => paragraph,
+            None => return,
+        };
 
-        Size {
-            width: constraints.smallest().width,
-            height: constraints.smallest().height,
+        let origin = Point::new(offset.x, offset.y);
+
+        if self.overflow == TextOverflow::Visible {
+            return paragraph.paint(canvas, origin);
+        }
+
+        let r = canvas.with_save(|canvas| {
+            canvas.clip(DruidRect::from(Rect::from_origin_size(*offset, size)));
+            paragraph.paint(canvas, origin);
+            Ok(())
+        });
+
+        if let Err(e) = r {
+            log::error!("painting text failed: {:?}", e);
         }
     }
 
-    fn paint(&self, _: &mut PaintCx<Self>, _: &mut Canvas, _: &Offset) {}
+    fn baseline(&self, cx: &LayoutCx<Self>) -> Option<f64> {
+        cx.render_state().paragraph.as_ref()?.first_baseline()
+    }
+
+    fn intrinsic_size(
+        &self,
+        cx: &LayoutCx<Self>,
+        dimension: IntrinsicDimension,
+        extent: f64,
+    ) -> f64 {
+        let direction = Directionality::unwrap_or_default(self.text_direction, cx);
+        let style = self.style(cx);
+
+        let max_width = match dimension {
+            // Every word is placed on its own line.
+            IntrinsicDimension::MinWidth => 0.,
+            IntrinsicDimension::MaxWidth => f64::INFINITY,
+            IntrinsicDimension::MinHeight | IntrinsicDimension::MaxHeight => extent,
+        };
+
+        let size = self
+            .build_paragraph(max_width, direction, &style)
+            .map_or(Size::ZERO, |paragraph| paragraph.size());
+
+        match dimension.is_width() {
+            true => size.width,
+            false => size.height,
+        }
+    }
 }
@@ -0,0 +1,412 @@
+use druid_shell::{
+    keyboard_types::Key,
+    piet::{
+        kurbo::{Point, Rect as DruidRect, Vec2},
+        FontFamily, PietTextLayout, TextLayout,
+    },
+    text::{Action, Selection},
+    KeyEvent, KeyState,
+};
+use frui::{
+    prelude::*,
+    render::*,
+    text_input::{TextEditingController, TextInput},
+};
+
+use crate::Focus;
+
+/// Width of the caret.
+const CARET_WIDTH: f64 = 1.;
+
+/// Editable text.
+///
+/// Text is typed into the text field once it's focused, either by tapping on
+/// it or by Tab traversal. Selected text can be copied, cut and pasted with
+/// `Ctrl+C`, `Ctrl+X` and `Ctrl+V` (`Cmd` on macOS), and edits can be undone
+/// and redone with `Ctrl+Z` and `Ctrl+Shift+Z` / `Ctrl+Y`.
+///
+/// Text of the text field is read and changed through its [`controller`]:
+///
+/// ```ignore
+/// TextField::builder()
+///     .controller(cx.state().controller.clone())
+///     .multiline(true)
+/// ```
+///
+/// [`controller`]: TextField::controller
+#[derive(ViewWidget, Builder)]
+pub struct TextField {
+    /// Controller of the text. If not set, text field creates its own.
+    pub controller: Option<TextEditingController>,
+    /// Focus node of the text field. If not set, text field creates its own.
+    pub focus_node: Option<FocusNode>,
+    /// Whether Enter inserts a line break. Multi-line text is wrapped to the
+    /// width of the text field, while single-line text scrolls horizontally.
+    pub multiline: bool,
+    /// Whether to focus this text field when it's built, if nothing else is
+    /// focused.
+    pub autofocus: bool,
+    pub size: f64,
+    pub color: Color,
+    pub family: FontFamily,
+    pub cursor_color: Color,
+    pub selection_color: Color,
+}
+
+impl TextField {
+    pub fn builder() -> Self {
+        TextField {
+            controller: None,
+            focus_node: None,
+            multiline: false,
+            autofocus: false,
+            size: 16.,
+            color: Color::WHITE,
+            family: FontFamily::MONOSPACE,
+            cursor_color: Color::WHITE,
+            selection_color: Color::rgba8(0x3d, 0x8b, 0xfd, 0x80),
+        }
+    }
+
+    /// Handles keyboard shortcuts. Other keys are delivered to the text input.
+    fn handle_key(&self, controller: &TextEditingController, event: &KeyEvent) -> KeyEventResult {
+        let shortcut = event.mods.ctrl() || event.mods.meta();
+
+        if event.state != KeyState::Down || !shortcut {
+            return KeyEventResult::Ignored;
+        }
+
+        let key = match &event.key {
+            Key::Character(key) => key.to_lowercase(),
+            _ => return KeyEventResult::Ignored,
+        };
+
+        match key.as_str() {
+            "a" => controller.select_all(),
+            "c" => controller.copy(),
+            "x" => controller.cut(),
+            "v" => controller.paste(),
+            "z" if event.mods.shift() => {
+                controller.redo();
+            }
+            "z" => {
+                controller.undo();
+            }
+            "y" => {
+                controller.redo();
+            }
+            _ => return KeyEventResult::Ignored,
+        }
+
+        KeyEventResult::Handled
+    }
+}
+
+pub struct TextFieldState {
+    controller: TextEditingController,
+    focus_node: FocusNode,
+    text_input: TextInput,
+    has_focus: bool,
+}
+
+impl TextFieldState {
+    pub fn controller(&self) -> &TextEditingController {
+        &self.controller
+    }
+
+    pub fn has_focus(&self) -> bool {
+        self.has_focus
+    }
+}
+
+impl WidgetState for TextField {
+    type State = TextFieldState;
+
+    fn create_state(&self) -> Self::State {
+        let controller = self.controller.clone().unwrap_or_default();
+
+        TextFieldState {
+            text_input: TextInput::new(controller.input_handler()),
+            controller,
+            focus_node: FocusNode::new(),
+            has_focus: false,
+        }
+    }
+
+    fn mount<'a>(&'a self, cx: BuildCx<'a, Self>) {
+        if let Some(controller) = &self.controller {
+            if *controller != cx.state().controller {
+                let mut state = cx.state_mut();
+                let text_input = TextInput::new(controller.input_handler());
+
+                if state.text_input.is_focused() {
+                    text_input.focus();
+                }
+
+                state.controller = controller.clone();
+                state.text_input = text_input;
+            }
+        }
+
+        let state = cx.state();
+
+        state.controller.attach(cx);
+        state.controller.set_multiline(self.multiline);
+    }
+}
+
+impl ViewWidget for TextField {
+    fn build<'w>(&'w self, cx: BuildCx<'w, Self>) -> Self::Widget<'w> {
+        let state = cx.state();
+
+        let focus_node = match &self.focus_node {
+            Some(focus_node) => focus_node.clone(),
+            None => state.focus_node.clone(),
+        };
+
+        Focus::builder()
+            .focus_node(focus_node.clone())
+            .autofocus(self.autofocus)
+            .on_key(|event| {
+                let controller = cx.state().controller.clone();
+                self.handle_key(&controller, event)
+            })
+            .on_focus_change(|has_focus| {
+                let mut state = cx.state_mut();
+
+                match has_focus {
+                    true => state.text_input.focus(),
+                    false => state.text_input.unfocus(),
+                }
+
+                state.has_focus = has_focus;
+            })
+            .child(EditableText {
+                controller: state.controller.clone(),
+                focus_node,
+                has_focus: state.has_focus,
+                multiline: self.multiline,
+                size: self.size,
+                color: self.color.clone(),
+                family: self.family.clone(),
+                cursor_color: self.cursor_color.clone(),
+                selection_color: self.selection_color.clone(),
+            })
+    }
+}
+
+/// Displays text of a [`TextEditingController`] together with its selection
+/// and places the caret where it's tapped.
+#[derive(RenderWidget)]
+struct EditableText {
+    controller: TextEditingController,
+    focus_node: FocusNode,
+    has_focus: bool,
+    multiline: bool,
+    size: f64,
+    color: Color,
+    family: FontFamily,
+    cursor_color: Color,
+    selection_color: Color,
+}
+
+#[derive(Default)]
+struct EditableTextState {
+    layout: Option<PietTextLayout>,
+    /// Horizontal scroll of single-line text, which keeps the caret visible.
+    scroll: f64,
+    /// Whether selection is being extended by dragging the pointer.
+    dragging: bool,
+}
+
+impl EditableText {
+    #[cfg(not(feature = "miri"))]
+    fn build_layout(&self, max_width: f64) -> Option<PietTextLayout> {
+        use druid_shell::piet::{Text as TextExt, TextAttribute, TextLayoutBuilder};
+
+        let text = self.controller.text();
+
+        TEXT_FACTORY.with(|f| {
+            let mut builder = f
+                .get()
+                .new_text_layout(text)
+                .font(self.family.clone(), self.size)
+                .text_color(self.color.clone())
+                .max_width(max_width);
+
+            if let Some(range) = self.controller.composition_range() {
+                builder = builder.range_attribute(range, TextAttribute::Underline(true));
+            }
+
+            builder.build().ok()
+        })
+    }
+
+    #[cfg(feature = "miri")]
+    fn build_layout(&self, _: f64) -> Option<PietTextLayout> {
+        None
+    }
+
+    /// Offset in the text at `point` local to this widget.
+    fn offset_at(&self, state: &EditableTextState, point: Point) -> usize {
+        self.controller
+            .offset_at(point + Vec2::new(state.scroll, 0.))
+    }
+
+    /// Caret rectangle at `offset`, in coordinates of the layout.
+    fn caret_rect(layout: &PietTextLayout, offset: usize) -> Option<DruidRect> {
+        let position = layout.hit_test_text_position(offset);
+        let metric = layout.line_metric(position.line)?;
+        let x = position.point.x;
+
+        Some(DruidRect::new(
+            x,
+            metric.y_offset,
+            x + CARET_WIDTH,
+            metric.y_offset + metric.height,
+        ))
+    }
+}
+
+impl RenderState for EditableText {
+    type State = EditableTextState;
+
+    fn create_state(&self) -> Self::State {
+        EditableTextState::default()
+    }
+}
+
+impl RenderWidget for EditableText {
+    fn build<'w>(&'w self, _: BuildCx<'w, Self>) -> Vec<Self::Widget<'w>> {
+        vec![] as Vec<()>
+    }
+
+    fn layout(&self, cx: &LayoutCx<Self>, constraints: Constraints) -> Size {
+        let max_width = match self.multiline {
+            true => constraints.biggest().width,
+            false => f64::INFINITY,
+        };
+
+        let layout = self.build_layout(max_width);
+        self.controller.set_layout(layout.clone());
+
+        let text_size = match &layout {
+            Some(layout) => Size::new(layout.size().width + CARET_WIDTH, layout.size().height),
+            None => constraints.smallest(),
+        };
+
+        // Text field takes all available width, so that it can be tapped
+        // even if it's empty.
+        let width = match constraints.biggest().width.is_finite() {
+            true => constraints.biggest().width,
+            false => text_size.width,
+        };
+
+        let size = constraints.constrain(Size::new(width, text_size.height));
+
+        let mut state = cx.render_state_mut();
+
+        state.scroll = match (&layout, self.multiline) {
+            (Some(layout), false) => {
+                let caret = layout
+                    .hit_test_text_position(self.controller.selection().active)
+                    .point
+                    .x;
+
+                let max_scroll = (text_size.width - size.width).max(0.);
+
+                state
+                    .scroll
+                    .max(caret + CARET_WIDTH - size.width)
+                    .min(caret)
+                    .clamp(0., max_scroll)
+            }
+            _ => 0.,
+        };
+
+        state.layout = layout;
+
+        size
+    }
+
+    fn paint(&self, cx: &mut PaintCx<Self>, canvas: &mut Canvas, offset: &Offset) {
+        let size = cx.size();
+        let state = cx.render_state();
+
+        let layout = match &state.layout {
+            Some(layout) => layout,
+            None => return,
+        };
+
+        let selection = self.controller.selection();
+        let origin = Point::new(offset.x - state.scroll, offset.y);
+
+        let r = canvas.with_save(|canvas| {
+            canvas.clip(DruidRect::from(Rect::from_origin_size(*offset, size)));
+
+            if self.has_focus && !selection.is_caret() {
+                let brush = canvas.solid_brush(self.selection_color.clone());
+
+                for rect in layout.rects_for_range(selection.range()) {
+                    canvas.fill(rect + origin.to_vec2(), &brush);
+                }
+            }
+
+            canvas.draw_text(layout, origin);
+
+            if self.has_focus && selection.is_caret() {
+                if let Some(rect) = Self::caret_rect(layout, selection.active) {
+                    let brush = canvas.solid_brush(self.cursor_color.clone());
+                    canvas.fill(rect + origin.to_vec2(), &brush);
+                }
+            }
+
+            Ok(())
+        });
+
+        if let Err(e) = r {
+            log::error!("painting text field failed: {:?}", e);
+        }
+    }
+}
+
+impl HitTest for EditableText {
+    fn handle_event(&self, cx: &mut HitTestCx<Self>, event: &PointerEvent) {
+        let mut state = cx.render_state_mut();
+
+        match event {
+            PointerEvent::PointerDown(e) => {
+                self.focus_node.request_focus();
+
+                let offset = self.offset_at(&state, e.0.pos);
+                let anchor = self.controller.selection().anchor;
+
+                state.dragging = true;
+
+                match e.0.count {
+                    1 if e.0.mods.shift() => self
+                        .controller
+                        .set_selection(Selection::new(anchor, offset)),
+                    1 => self.controller.set_selection(Selection::caret(offset)),
+                    2 => {
+                        self.controller.set_selection(Selection::caret(offset));
+                        self.controller.handle_action(Action::SelectWord);
+                    }
+                    _ => {
+                        self.controller.set_selection(Selection::caret(offset));
+                        self.controller.handle_action(Action::SelectParagraph);
+                    }
+                }
+            }
+            PointerEvent::PointerMove(e) if state.dragging && e.0.buttons.has_left() => {
+                let offset = self.offset_at(&state, e.0.pos);
+                let anchor = self.controller.selection().anchor;
+
+                self.controller
+                    .set_selection(Selection::new(anchor, offset));
+            }
+            PointerEvent::PointerUp(_) => state.dragging = false,
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,160 @@
+use druid_shell::piet::{FontFamily, FontStyle, FontWeight, TextAttribute};
+use frui::prelude::*;
+use frui::render::*;
+
+/// Style of a run of text. Fields which aren't set are inherited from the
+/// enclosing style (see [`TextStyle::merge`]).
+///
+/// ```ignore
+/// TextStyle::default().size(24.).weight(FontWeight::BOLD)
+/// ```
+#[derive(Debug, Clone, Default, Builder)]
+pub struct TextStyle {
+    pub family: Option<FontFamily>,
+    pub size: Option<f64>,
+    pub weight: Option<FontWeight>,
+    pub style: Option<FontStyle>,
+    pub color: Option<Color>,
+    pub underline: Option<bool>,
+    pub strikethrough: Option<bool>,
+}
+
+impl TextStyle {
+    /// Returns this style with fields set in `other` replacing its own.
+    pub fn merge(&self, other: &TextStyle) -> TextStyle {
+        TextStyle {
+            family: other.family.clone().or_else(|| self.family.clone()),
+            size: other.size.or(self.size),
+            weight: other.weight.or(self.weight),
+            style: other.style.or(self.style),
+            color: other.color.clone().or_else(|| self.color.clone()),
+            underline: other.underline.or(self.underline),
+            strikethrough: other.strikethrough.or(self.strikethrough),
+        }
+    }
+
+    /// Style used for fields which aren't set by any enclosing style, which is
+    /// white, 16 points large monospace font.
+    pub(crate) fn fallback() -> TextStyle {
+        TextStyle {
+            // Layout of `FontFamily::SYSTEM_UI` is incredibly slow. Other fonts
+            // seem to render just fine. This issue is related to Piet.
+            //
+            // For now, the default will be `FontFamily::MONOSPACE`.
+            family: Some(FontFamily::MONOSPACE),
+            size: Some(16.),
+            weight: Some(FontWeight::REGULAR),
+            color: Some(Color::WHITE),
+            ..TextStyle::default()
+        }
+    }
+
+    /// Text layout attributes of fields which are set.
+    pub(crate) fn attributes(&self) -> Vec<TextAttribute> {
+        let mut attributes = Vec::new();
+
+        if let Some(family) = &self.family {
+            attributes.push(TextAttribute::FontFamily(family.clone()));
+        }
+
+        if let Some(size) = self.size {
+            attributes.push(TextAttribute::FontSize(size));
+        }
+
+        if let Some(weight) = self.weight {
+            attributes.push(TextAttribute::Weight(weight));
+        }
+
+        if let Some(style) = self.style {
+            attributes.push(TextAttribute::Style(style));
+        }
+
+        if let Some(color) = &self.color {
+            attributes.push(TextAttribute::TextColor(color.clone()));
+        }
+
+        if let Some(underline) = self.underline {
+            attributes.push(TextAttribute::Underline(underline));
+        }
+
+        if let Some(strikethrough) = self.strikethrough {
+            attributes.push(TextAttribute::Strikethrough(strikethrough));
+        }
+
+        attributes
+    }
+}
+
+/// Style of text in its subtree, which is used for fields that text widgets
+/// don't set themselves.
+///
+/// Nested default text styles are merged, so that the inner one only needs to
+/// set fields which it changes:
+///
+/// ```ignore
+/// DefaultTextStyle {
+///     style: TextStyle::default().color(Color::BLACK).size(14.),
+///     child: Column::builder().children((
+///         Text::new("Black, 14 points"),
+///         DefaultTextStyle {
+///             style: TextStyle::default().weight(FontWeight::BOLD),
+///             child: Text::new("Black, 14 points and bold"),
+///         },
+///     )),
+/// }
+/// ```
+#[derive(ViewWidget, Builder)]
+pub struct DefaultTextStyle<W: Widget> {
+    pub style: TextStyle,
+    pub child: W,
+}
+
+impl DefaultTextStyle<()> {
+    /// Style of the closest [`DefaultTextStyle`], merged with styles of all
+    /// of its ancestors. Fields which none of them set aren't set.
+    pub fn of<T>(cx: &LayoutCx<T>) -> TextStyle {
+        match cx.depend_on_inherited_widget::<InheritedTextStyle<()>>() {
+            Some(state) => state.as_ref().clone(),
+            None => TextStyle::default(),
+        }
+    }
+}
+
+impl<W: Widget> ViewWidget for DefaultTextStyle<W> {
+    fn build<'w>(&'w self, cx: BuildCx<'w, Self>) -> Self::Widget<'w> {
+        let style = match cx.depend_on_inherited_widget::<InheritedTextStyle<()>>() {
+            Some(state) => state.as_ref().merge(&self.style),
+            None => self.style.clone(),
+        };
+
+        InheritedTextStyle {
+            style,
+            child: &self.child,
+        }
+    }
+}
+
+/// Provides style of the [`DefaultTextStyle`] merged with enclosing styles.
+#[derive(InheritedWidget)]
+struct InheritedTextStyle<W: Widget> {
+    style: TextStyle,
+    child: W,
+}
+
+impl<W: Widget> WidgetState for InheritedTextStyle<W> {
+    type State = TextStyle;
+
+    fn create_state(&self) -> Self::State {
+        self.style.clone()
+    }
+
+    fn mount<'a>(&'a self, cx: BuildCx<'a, Self>) {
+        *cx.state_mut() = self.style.clone();
+    }
+}
+
+impl<W: Widget> InheritedWidget for InheritedTextStyle<W> {
+    fn build<'w>(&'w self) -> Self::Widget<'w> {
+        &self.child
+    }
+}
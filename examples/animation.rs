@@ -0,0 +1,181 @@
+//! Box which grows and changes its color back and forth, driven by an
+//! [`AnimationController`].
+
+#![feature(type_alias_impl_trait)]
+
+use std::time::Duration;
+
+use frui::prelude::*;
+
+#[derive(ViewWidget)]
+struct Pulse;
+
+impl WidgetState for Pulse {
+    type State = AnimationController;
+
+    fn create_state(&self) -> Self::State {
+        AnimationController::new(Duration::from_millis(1200))
+    }
+
+    fn mount<'a>(&'a self, cx: BuildCx<'a, Self>) {
+        cx.state().attach(cx);
+        cx.state().repeat(true);
+    }
+}
+
+impl ViewWidget for Pulse {
+    fn build<'w>(&'w self, cx: BuildCx<'w, Self>) -> Self::Widget<'w> {
+        let controller = cx.state();
+
+        let size = Tween::new(Size::new(100.0, 100.0), Size::new(200.0, 150.0))
+            .evaluate_curved(&controller, &Curves::EASE_IN_OUT);
+        let color = Tween::new(Color::rgb8(40, 198, 168), Color::rgb8(252, 105, 0))
+            .evaluate_curved(&controller, &Curves::EASE_IN_OUT);
+
+        Center::child(SizedBox::from_size(ColoredBox { color, child: () }, size))
+    }
+}
+
+fn main() {
+    run_app(Pulse);
+}
+
+#[cfg(test)]
+mod test {
+    use std::{cell::RefCell, rc::Rc};
+
+    use super::*;
+    use frui::app::runner::headless::{Finder, TestHarness};
+
+    /// Grows from nothing to 100x50 in 100ms after being mounted.
+    #[derive(ViewWidget)]
+    struct Grow;
+
+    impl WidgetState for Grow {
+        type State = AnimationController;
+
+        fn create_state(&self) -> Self::State {
+            AnimationController::new(Duration::from_millis(100))
+        }
+
+        fn mount<'a>(&'a self, cx: BuildCx<'a, Self>) {
+            cx.state().attach(cx);
+            cx.state().forward();
+        }
+    }
+
+    impl ViewWidget for Grow {
+        fn build<'w>(&'w self, cx: BuildCx<'w, Self>) -> Self::Widget<'w> {
+            let size = Tween::new(Size::ZERO, Size::new(100.0, 50.0)).evaluate(&cx.state());
+
+            SizedBox::from_size((), size)
+        }
+    }
+
+    #[track_caller]
+    fn assert_box_size(harness: &TestHarness, width: f64, height: f64) {
+        let size = harness
+            .find_one(&Finder::by_type::<ConstrainedBox<()>>())
+            .size();
+
+        assert!(
+            (size.width - width).abs() < 1e-6 && (size.height - height).abs() < 1e-6,
+            "expected box of size {width}x{height}, found {size}"
+        );
+    }
+
+    fn controller(harness: &TestHarness) -> AnimationController {
+        harness
+            .find_one(&Finder::by_type::<Grow>())
+            .state::<AnimationController>()
+            .unwrap()
+            .clone()
+    }
+
+    #[test]
+    pub fn animation_follows_fake_clock() {
+        let mut harness = TestHarness::new(Center::child(Grow));
+        let controller = controller(&harness);
+
+        assert_eq!(controller.status(), AnimationStatus::Forward);
+        assert_box_size(&harness, 0.0, 0.0);
+
+        harness.advance_time(Duration::from_millis(25));
+
+        assert_box_size(&harness, 25.0, 12.5);
+
+        harness.advance_time(Duration::from_millis(75));
+
+        assert_box_size(&harness, 100.0, 50.0);
+        assert_eq!(controller.status(), AnimationStatus::Completed);
+        assert!(!controller.is_animating());
+
+        let statuses = Rc::new(RefCell::new(Vec::new()));
+
+        controller.add_status_listener({
+            let statuses = statuses.clone();
+            move |status| statuses.borrow_mut().push(status)
+        });
+
+        controller.reverse();
+        harness.pump_until_settled();
+
+        assert_box_size(&harness, 0.0, 0.0);
+        assert_eq!(
+            *statuses.borrow(),
+            [AnimationStatus::Reverse, AnimationStatus::Dismissed]
+        );
+    }
+
+    #[test]
+    pub fn animation_rests_at_target_between_ends() {
+        let mut harness = TestHarness::new(Center::child(Grow));
+        let controller = controller(&harness);
+
+        harness.pump_until_settled();
+        controller.animate_to(0.5);
+        harness.pump_until_settled();
+
+        assert_box_size(&harness, 50.0, 25.0);
+        assert_eq!(controller.status(), AnimationStatus::Reverse);
+        assert!(!controller.is_completed() && !controller.is_animating());
+
+        controller.animate_to(0.75);
+        harness.pump_until_settled();
+
+        assert_box_size(&harness, 75.0, 37.5);
+        assert_eq!(controller.status(), AnimationStatus::Forward);
+    }
+
+    #[test]
+    pub fn curves_map_endpoints() {
+        let curves: [&dyn Curve; 4] = [
+            &Curves::LINEAR,
+            &Curves::EASE,
+            &Curves::FAST_OUT_SLOW_IN,
+            &Curves::BOUNCE_OUT,
+        ];
+
+        for curve in curves {
+            assert_eq!(curve.transform(0.0), 0.0);
+            assert!((curve.transform(1.0) - 1.0).abs() < 1e-9);
+        }
+
+        assert!(Curves::EASE_IN.transform(0.25) < 0.25);
+        assert!(Curves::EASE_OUT.transform(0.25) > 0.25);
+    }
+
+    #[test]
+    pub fn elapsed_time_is_measured_from_start() {
+        let mut harness = TestHarness::new(Center::child(Grow));
+        let controller = controller(&harness);
+
+        harness.pump_until_settled();
+
+        // Started between frames, so the first tick comes 25ms after that.
+        controller.reverse();
+        harness.advance_time(Duration::from_millis(25));
+
+        assert_box_size(&harness, 75.0, 37.5);
+    }
+}
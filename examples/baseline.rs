@@ -0,0 +1,103 @@
+//! Labels of different font sizes aligned by their baselines in a [`Row`].
+//!
+//! Baseline of the middle label is found through the [`Padding`] around it.
+
+#![feature(type_alias_impl_trait)]
+
+use frui::prelude::*;
+
+#[derive(ViewWidget)]
+struct App;
+
+impl ViewWidget for App {
+    fn build<'w>(&'w self, _: BuildCx<'w, Self>) -> Self::Widget<'w> {
+        Center::child(
+            Row::builder()
+                .space_between(10.0)
+                .cross_axis_alignment(CrossAxisAlignment::Baseline)
+                .children((
+                    Text::new("Large").size(48.),
+                    Padding::builder()
+                        .padding(EdgeInsets::from_ltrb(0., 10., 0., 0.))
+                        .child(Text::new("small").size(14.)),
+                    Text::new("Medium").size(24.),
+                )),
+        )
+    }
+}
+
+fn main() {
+    run_app(App);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use frui::app::runner::headless::{Finder, TestHarness};
+
+    #[cfg(not(feature = "miri"))]
+    #[test]
+    pub fn labels_share_baseline() {
+        let harness = TestHarness::new(App);
+        let texts = harness.find_all(&Finder::by_type::<Text<&str>>());
+
+        let baselines = texts
+            .iter()
+            .map(|text| text.global_offset().y + text.baseline().unwrap())
+            .collect::<Vec<_>>();
+
+        assert_eq!(baselines.len(), 3);
+
+        for baseline in &baselines {
+            assert!((baseline - baselines[0]).abs() < 1e-6);
+        }
+
+        // Largest label touches the top of the row.
+        let row = harness.find_one(&Finder::by_type::<Flex<()>>());
+
+        assert_eq!(texts[0].global_offset().y, row.global_offset().y);
+        assert!((row.baseline().unwrap() - texts[0].baseline().unwrap()).abs() < 1e-6);
+    }
+
+    #[cfg(not(feature = "miri"))]
+    #[test]
+    pub fn padding_moves_baseline() {
+        let harness = TestHarness::new(App);
+
+        let padding = harness.find_one(&Finder::by_type::<Padding<(), EdgeInsets>>());
+        let text = &padding.children()[0];
+
+        assert_eq!(padding.baseline(), Some(10. + text.baseline().unwrap()));
+    }
+
+    /// Row aligned by baselines of children which have none.
+    #[derive(ViewWidget)]
+    struct Boxes;
+
+    impl ViewWidget for Boxes {
+        fn build<'w>(&'w self, _: BuildCx<'w, Self>) -> Self::Widget<'w> {
+            Center::child(
+                Row::builder()
+                    .cross_axis_alignment(CrossAxisAlignment::Baseline)
+                    .children((
+                        SizedBox::new((), Some(20.), Some(20.)),
+                        SizedBox::new((), Some(20.), Some(40.)),
+                    )),
+            )
+        }
+    }
+
+    #[test]
+    pub fn children_without_baseline_are_placed_at_start() {
+        let harness = TestHarness::new(Boxes);
+
+        let row = harness.find_one(&Finder::by_type::<Flex<()>>());
+
+        assert_eq!(row.size(), Size::new(40., 40.));
+        assert_eq!(row.baseline(), None);
+
+        for child in row.children() {
+            assert_eq!(child.local_offset().y, 0.);
+        }
+    }
+}
@@ -104,3 +104,236 @@ fn main() {
         child: App,
     });
 }
+
+#[cfg(test)]
+mod test {
+    use std::cell::Cell;
+
+    use super::*;
+    use frui::{
+        app::runner::headless::{Finder, TestHarness},
+        druid_shell::keyboard_types::Key,
+        render::*,
+    };
+
+    /// Counts how many times it was laid out.
+    #[derive(RenderWidget)]
+    struct LayoutCounter<W: Widget>(W);
+
+    impl<W: Widget> WidgetState for LayoutCounter<W> {
+        type State = Cell<usize>;
+
+        fn create_state(&self) -> Self::State {
+            Cell::new(0)
+        }
+    }
+
+    impl<W: Widget> RenderWidget for LayoutCounter<W> {
+        fn build<'w>(&'w self, _: BuildCx<'w, Self>) -> Vec<Self::Widget<'w>> {
+            vec![&self.0]
+        }
+
+        fn layout(&self, cx: &LayoutCx<Self>, constraints: Constraints) -> Size {
+            let count = cx.widget_state().get();
+            cx.widget_state().set(count + 1);
+
+            cx.child(0).layout(constraints)
+        }
+
+        fn paint(&self, cx: &mut PaintCx<Self>, canvas: &mut Canvas, offset: &Offset) {
+            cx.child(0).paint(canvas, offset)
+        }
+    }
+
+    /// Flips its text on every key press.
+    #[derive(ViewWidget)]
+    struct Toggle;
+
+    impl WidgetState for Toggle {
+        type State = bool;
+
+        fn create_state(&self) -> Self::State {
+            false
+        }
+    }
+
+    impl ViewWidget for Toggle {
+        fn build<'w>(&'w self, cx: BuildCx<'w, Self>) -> Self::Widget<'w> {
+            KeyboardEventDetector {
+                on_event: |_| {
+                    let value = !*cx.state();
+                    *cx.state_mut() = value;
+                },
+                child: SizedBox::from_size(
+                    Text::new(cx.state().to_string()),
+                    Size::new(50.0, 50.0),
+                ),
+            }
+        }
+    }
+
+    /// Counts how many times it was painted.
+    #[derive(RenderWidget)]
+    struct PaintCounter<W: Widget>(W);
+
+    impl<W: Widget> WidgetState for PaintCounter<W> {
+        type State = Cell<usize>;
+
+        fn create_state(&self) -> Self::State {
+            Cell::new(0)
+        }
+    }
+
+    impl<W: Widget> RenderWidget for PaintCounter<W> {
+        fn build<'w>(&'w self, _: BuildCx<'w, Self>) -> Vec<Self::Widget<'w>> {
+            vec![&self.0]
+        }
+
+        fn layout(&self, cx: &LayoutCx<Self>, constraints: Constraints) -> Size {
+            cx.child(0).layout(constraints)
+        }
+
+        fn paint(&self, cx: &mut PaintCx<Self>, canvas: &mut Canvas, offset: &Offset) {
+            let count = cx.widget_state().get();
+            cx.widget_state().set(count + 1);
+
+            cx.child(0).paint(canvas, offset)
+        }
+    }
+
+    fn layout_counts(harness: &TestHarness) -> Vec<usize> {
+        harness
+            .find_all(&Finder::by_type::<LayoutCounter<()>>())
+            .iter()
+            .map(|n| n.state::<Cell<usize>>().unwrap().get())
+            .collect()
+    }
+
+    #[test]
+    pub fn layout_stops_at_relayout_boundary() {
+        let mut harness = TestHarness::new(LayoutCounter(Column::builder().children((
+            LayoutCounter(()),
+            RelayoutBoundary::child(LayoutCounter(Toggle)),
+        ))));
+
+        assert_eq!(layout_counts(&harness), [1, 1, 1]);
+
+        // Nothing changed.
+        harness.pump();
+
+        assert_eq!(layout_counts(&harness), [1, 1, 1]);
+
+        harness.press_key(Key::Character(" ".into()));
+
+        let text = harness.find_one(&Finder::by_type::<Text<String>>());
+
+        let toggle = harness.find_one(&Finder::by_type::<Toggle>());
+
+        assert!(*toggle.state::<bool>().unwrap());
+        assert!(text.is_laid_out());
+
+        // Only the subtree below the boundary was laid out again.
+        assert_eq!(layout_counts(&harness), [1, 1, 2]);
+
+        // Resizing the window lays out everything.
+        harness.set_size(Size::new(300.0, 300.0));
+
+        assert_eq!(layout_counts(&harness), [2, 2, 3]);
+    }
+
+    thread_local! {
+        static SHOW_DEFERRED: Cell<bool> = Cell::new(false);
+    }
+
+    /// Lays out (and paints) its child only once `SHOW_DEFERRED` is set.
+    #[derive(RenderWidget)]
+    struct Deferred<W: Widget>(W);
+
+    impl<W: Widget> RenderState for Deferred<W> {
+        /// Whether the child was laid out.
+        type State = bool;
+
+        fn create_state(&self) -> Self::State {
+            false
+        }
+    }
+
+    impl<W: Widget> RenderWidget for Deferred<W> {
+        fn build<'w>(&'w self, _: BuildCx<'w, Self>) -> Vec<Self::Widget<'w>> {
+            vec![&self.0]
+        }
+
+        fn layout(&self, cx: &LayoutCx<Self>, constraints: Constraints) -> Size {
+            let show = SHOW_DEFERRED.with(|show| show.get());
+            *cx.render_state_mut() = show;
+
+            match show {
+                true => cx.child(0).layout(constraints),
+                false => constraints.smallest(),
+            }
+        }
+
+        fn paint(&self, cx: &mut PaintCx<Self>, canvas: &mut Canvas, offset: &Offset) {
+            if *cx.render_state() {
+                cx.child(0).paint(canvas, offset)
+            }
+        }
+    }
+
+    #[test]
+    pub fn layout_passes_through_nodes_never_laid_out() {
+        let mut harness = TestHarness::new(LayoutCounter(Deferred(LayoutCounter(Toggle))));
+
+        assert_eq!(layout_counts(&harness), [1, 0]);
+
+        // Toggle, which was never laid out, still marks its ancestors.
+        SHOW_DEFERRED.with(|show| show.set(true));
+        harness.press_key(Key::Character(" ".into()));
+
+        let text = harness.find_one(&Finder::by_type::<Text<String>>());
+
+        assert!(text.is_laid_out());
+        assert_eq!(layout_counts(&harness), [2, 1]);
+    }
+
+    fn paint_counts(harness: &TestHarness) -> Vec<usize> {
+        harness
+            .find_all(&Finder::by_type::<PaintCounter<()>>())
+            .iter()
+            .map(|n| n.state::<Cell<usize>>().unwrap().get())
+            .collect()
+    }
+
+    #[test]
+    #[cfg(not(feature = "miri"))]
+    pub fn repaint_boundary_reuses_layer() {
+        let mut harness = TestHarness::new(Column::builder().children((
+            RepaintBoundary::child(PaintCounter(ColoredBox {
+                color: Color::RED,
+                child: Toggle,
+            })),
+            PaintCounter(()),
+        )));
+
+        assert_eq!(paint_counts(&harness), [1, 1]);
+
+        // Layer is reused, but still painted to the window.
+        harness.pump();
+
+        let colored_box = harness.find_one(&Finder::by_type::<ColoredBox<()>>());
+        let rect = colored_box.global_rect();
+
+        assert_eq!(paint_counts(&harness), [1, 2]);
+        assert_eq!(
+            harness
+                .frame()
+                .pixel(rect.left as usize + 2, rect.top as usize + 2),
+            [255, 0, 0, 255]
+        );
+
+        // Changes inside of the boundary repaint its layer.
+        harness.press_key(Key::Character(" ".into()));
+
+        assert_eq!(paint_counts(&harness), [2, 3]);
+    }
+}
@@ -0,0 +1,152 @@
+//! Badges clipped by [`ClipOval`], [`ClipRRect`] and [`ClipPath`]. Clicking a
+//! badge outside of its shape doesn't register.
+
+#![feature(type_alias_impl_trait)]
+
+use frui::{prelude::*, render::kurbo::BezPath};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Badge {
+    Oval,
+    Rounded,
+    Triangle,
+}
+
+/// Triangle pointing up, spanning the whole area.
+struct TriangleClipper;
+
+impl Clipper for TriangleClipper {
+    fn clip(&self, size: Size) -> BezPath {
+        let mut path = BezPath::new();
+
+        path.move_to((size.width / 2., 0.));
+        path.line_to((size.width, size.height));
+        path.line_to((0., size.height));
+        path.close_path();
+
+        path
+    }
+
+    fn should_reclip(&self, _: &Self) -> bool {
+        false
+    }
+}
+
+#[derive(ViewWidget)]
+struct App;
+
+impl WidgetState for App {
+    type State = Vec<Badge>;
+
+    fn create_state(&self) -> Self::State {
+        Vec::new()
+    }
+}
+
+impl ViewWidget for App {
+    fn build<'w>(&'w self, cx: BuildCx<'w, Self>) -> Self::Widget<'w> {
+        let badge = move |badge: Badge, color: Color| {
+            LocalKey::new(
+                badge,
+                PointerListener::builder()
+                    .on_pointer_down(move |_| cx.state_mut().push(badge))
+                    .child(Container::builder().color(color).width(100.).height(100.)),
+            )
+        };
+
+        Center::child(
+            Column::builder()
+                .space_between(20.)
+                .main_axis_size(MainAxisSize::Min)
+                .cross_axis_alignment(CrossAxisAlignment::Center)
+                .children((
+                    Row::builder()
+                        .space_between(20.)
+                        .main_axis_size(MainAxisSize::Min)
+                        .children((
+                            ClipOval::child(badge(Badge::Oval, Color::NAVY)),
+                            ClipRRect::builder()
+                                .border_radius(BorderRadius::circular(25.))
+                                .child(badge(Badge::Rounded, Color::GREEN)),
+                            ClipPath::new(TriangleClipper, badge(Badge::Triangle, Color::RED)),
+                        )),
+                    Text::new(match cx.state().last() {
+                        Some(badge) => format!("Tapped: {:?}", badge),
+                        None => "Tap a badge".to_string(),
+                    }),
+                )),
+        )
+    }
+}
+
+fn main() {
+    run_app(App);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use frui::{
+        app::runner::headless::{Finder, TestHarness},
+        render::Point,
+    };
+
+    /// Taps `badge` at given position relative to its top left corner.
+    fn tap_badge(harness: &mut TestHarness, badge: Badge, x: f64, y: f64) {
+        let rect = harness.find_one(&Finder::by_key(badge)).global_rect();
+
+        harness.tap_at(Point::new(rect.left + x, rect.top + y));
+    }
+
+    fn taps(harness: &TestHarness) -> Vec<Badge> {
+        harness
+            .find_one(&Finder::by_type::<App>())
+            .state::<Vec<Badge>>()
+            .unwrap()
+            .to_vec()
+    }
+
+    #[test]
+    pub fn taps_outside_of_clip_are_ignored() {
+        let mut harness = TestHarness::new(App);
+
+        // Corners of each badge lie outside of its shape.
+        tap_badge(&mut harness, Badge::Oval, 5., 5.);
+        tap_badge(&mut harness, Badge::Rounded, 3., 3.);
+        tap_badge(&mut harness, Badge::Triangle, 5., 5.);
+
+        assert!(taps(&harness).is_empty());
+
+        tap_badge(&mut harness, Badge::Oval, 50., 50.);
+        tap_badge(&mut harness, Badge::Rounded, 50., 3.);
+        tap_badge(&mut harness, Badge::Triangle, 50., 90.);
+
+        assert_eq!(
+            taps(&harness),
+            [Badge::Oval, Badge::Rounded, Badge::Triangle]
+        );
+    }
+
+    #[test]
+    #[cfg(not(feature = "miri"))]
+    pub fn children_are_painted_within_clip() {
+        let harness = TestHarness::new(App);
+
+        let pixel = |badge: Badge, x: f64, y: f64| {
+            let rect = harness.find_one(&Finder::by_key(badge)).global_rect();
+
+            harness
+                .frame()
+                .pixel((rect.left + x) as usize, (rect.top + y) as usize)
+        };
+
+        assert_eq!(pixel(Badge::Oval, 50., 50.), [0, 0, 128, 255]);
+        assert_ne!(pixel(Badge::Oval, 5., 5.), [0, 0, 128, 255]);
+
+        assert_eq!(pixel(Badge::Rounded, 50., 3.), [0, 128, 0, 255]);
+        assert_ne!(pixel(Badge::Rounded, 3., 3.), [0, 128, 0, 255]);
+
+        assert_eq!(pixel(Badge::Triangle, 50., 90.), [255, 0, 0, 255]);
+        assert_ne!(pixel(Badge::Triangle, 5., 5.), [255, 0, 0, 255]);
+    }
+}
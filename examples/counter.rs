@@ -53,3 +53,26 @@ impl ViewWidget for Counter {
 fn main() {
     run_app(Counter);
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use frui::app::runner::headless::{Finder, TestHarness};
+
+    #[test]
+    pub fn buttons_change_the_counter() {
+        let mut harness = TestHarness::new(Counter);
+
+        let buttons = harness.find_all(&Finder::by_type::<Button<(), fn()>>());
+
+        assert_eq!(buttons.len(), 2);
+
+        let (plus, minus) = (buttons[0].global_rect(), buttons[1].global_rect());
+
+        harness.tap_at(plus.center().into());
+        harness.tap_at(plus.center().into());
+        harness.tap_at(minus.center().into());
+
+        assert_eq!(*harness.root().state::<isize>().unwrap(), 1);
+    }
+}
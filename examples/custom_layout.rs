@@ -0,0 +1,213 @@
+//! Dashboard with a sidebar, a header and a body, positioned by a custom
+//! [`MultiChildLayoutDelegate`].
+//!
+//! Press left and right arrow keys to resize the sidebar, and any other key to
+//! highlight the body (which doesn't lay the dashboard out again).
+
+#![feature(type_alias_impl_trait)]
+
+use frui::{druid_shell::keyboard_types::Key, prelude::*};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Slot {
+    Sidebar,
+    Header,
+    Body,
+}
+
+struct DashboardLayout {
+    sidebar_width: f64,
+    header_height: f64,
+}
+
+impl MultiChildLayoutDelegate for DashboardLayout {
+    fn layout(&self, cx: &MultiChildLayoutCx, size: Size) {
+        let sidebar_width = self.sidebar_width.min(size.width);
+        let content_width = size.width - sidebar_width;
+
+        cx.layout_child(
+            Slot::Sidebar,
+            Constraints::new_tight(Size::new(sidebar_width, size.height)),
+        );
+        cx.position_child(Slot::Sidebar, Offset::new(0., 0.));
+
+        let mut header_height = 0.;
+
+        if cx.has_child(Slot::Header) {
+            header_height = cx
+                .layout_child(
+                    Slot::Header,
+                    Constraints::new_tight(Size::new(content_width, self.header_height)),
+                )
+                .height;
+            cx.position_child(Slot::Header, Offset::new(sidebar_width, 0.));
+        }
+
+        cx.layout_child(
+            Slot::Body,
+            Constraints::new_tight(Size::new(content_width, size.height - header_height)),
+        );
+        cx.position_child(Slot::Body, Offset::new(sidebar_width, header_height));
+    }
+
+    fn should_relayout(&self, old: &Self) -> bool {
+        self.sidebar_width != old.sidebar_width || self.header_height != old.header_height
+    }
+}
+
+fn dashboard(delegate: impl MultiChildLayoutDelegate, highlighted: bool) -> impl Widget {
+    CustomMultiChildLayout::new(
+        delegate,
+        (
+            LocalKey::new(Slot::Sidebar, Container::builder().color(Color::NAVY)),
+            LocalKey::new(Slot::Header, Container::builder().color(Color::GRAY)),
+            LocalKey::new(
+                Slot::Body,
+                Container::builder().color(match highlighted {
+                    true => Color::AQUA,
+                    false => Color::WHITE,
+                }),
+            ),
+        ),
+    )
+}
+
+#[derive(ViewWidget)]
+struct App;
+
+struct AppState {
+    sidebar_width: f64,
+    highlighted: bool,
+}
+
+impl WidgetState for App {
+    type State = AppState;
+
+    fn create_state(&self) -> Self::State {
+        AppState {
+            sidebar_width: 100.,
+            highlighted: false,
+        }
+    }
+}
+
+impl ViewWidget for App {
+    fn build<'w>(&'w self, cx: BuildCx<'w, Self>) -> Self::Widget<'w> {
+        KeyboardEventDetector {
+            on_event: move |event| {
+                let mut state = cx.state_mut();
+
+                match event.key {
+                    Key::ArrowLeft => state.sidebar_width -= 20.,
+                    Key::ArrowRight => state.sidebar_width += 20.,
+                    _ => state.highlighted = !state.highlighted,
+                }
+            },
+            child: dashboard(
+                DashboardLayout {
+                    sidebar_width: cx.state().sidebar_width,
+                    header_height: 50.,
+                },
+                cx.state().highlighted,
+            ),
+        }
+    }
+}
+
+fn main() {
+    run_app(App);
+}
+
+#[cfg(test)]
+mod test {
+    use std::cell::Cell;
+
+    use super::*;
+    use frui::app::runner::headless::{Finder, TestHarness};
+
+    fn slot_rect(harness: &TestHarness, slot: Slot) -> (f64, f64, f64, f64) {
+        let rect = harness.find_one(&Finder::by_key(slot)).global_rect();
+
+        (rect.left, rect.top, rect.width(), rect.height())
+    }
+
+    #[test]
+    pub fn delegate_positions_children() {
+        let harness = TestHarness::new(App);
+
+        assert_eq!(slot_rect(&harness, Slot::Sidebar), (0., 0., 100., 400.));
+        assert_eq!(slot_rect(&harness, Slot::Header), (100., 0., 400., 50.));
+        assert_eq!(slot_rect(&harness, Slot::Body), (100., 50., 400., 350.));
+    }
+
+    #[test]
+    pub fn changed_delegate_relayouts_children() {
+        let mut harness = TestHarness::new(App);
+
+        harness.press_key(Key::ArrowRight);
+
+        assert_eq!(slot_rect(&harness, Slot::Sidebar), (0., 0., 120., 400.));
+        assert_eq!(slot_rect(&harness, Slot::Header), (120., 0., 380., 50.));
+        assert_eq!(slot_rect(&harness, Slot::Body), (120., 50., 380., 350.));
+    }
+
+    thread_local! {
+        static LAYOUT_COUNT: Cell<usize> = Cell::new(0);
+    }
+
+    /// Counts layouts performed by the dashboard delegate.
+    struct CountingLayout(DashboardLayout);
+
+    impl MultiChildLayoutDelegate for CountingLayout {
+        fn layout(&self, cx: &MultiChildLayoutCx, size: Size) {
+            LAYOUT_COUNT.with(|count| count.set(count.get() + 1));
+
+            self.0.layout(cx, size)
+        }
+
+        fn should_relayout(&self, old: &Self) -> bool {
+            self.0.should_relayout(&old.0)
+        }
+    }
+
+    /// Dashboard which body is highlighted on every key press.
+    #[derive(ViewWidget)]
+    struct CountedDashboard;
+
+    impl WidgetState for CountedDashboard {
+        type State = bool;
+
+        fn create_state(&self) -> Self::State {
+            false
+        }
+    }
+
+    impl ViewWidget for CountedDashboard {
+        fn build<'w>(&'w self, cx: BuildCx<'w, Self>) -> Self::Widget<'w> {
+            KeyboardEventDetector {
+                on_event: move |_| {
+                    let mut highlighted = cx.state_mut();
+                    *highlighted = !*highlighted;
+                },
+                child: dashboard(
+                    CountingLayout(DashboardLayout {
+                        sidebar_width: 100.,
+                        header_height: 50.,
+                    }),
+                    *cx.state(),
+                ),
+            }
+        }
+    }
+
+    #[test]
+    pub fn equal_delegate_skips_layout() {
+        let mut harness = TestHarness::new(CountedDashboard);
+        let layouts = LAYOUT_COUNT.with(|count| count.get());
+
+        harness.press_key(Key::Enter);
+
+        assert_eq!(LAYOUT_COUNT.with(|count| count.get()), layouts);
+        assert_eq!(slot_rect(&harness, Slot::Body), (100., 50., 400., 350.));
+    }
+}
@@ -0,0 +1,263 @@
+//! Gauge drawn by [`CustomPaint`] painters: a dial behind a hub and a level
+//! bar in front of it.
+//!
+//! Press up and down arrow keys to change the level.
+
+#![feature(type_alias_impl_trait)]
+
+use frui::{
+    druid_shell::keyboard_types::Key,
+    prelude::*,
+    render::{kurbo::Circle, *},
+};
+
+/// Round dial, which is hit only within its circle.
+struct Dial {
+    color: Color,
+}
+
+impl Dial {
+    fn circle(size: Size) -> Circle {
+        let radius = size.width.min(size.height) / 2.;
+
+        Circle::new((size.width / 2., size.height / 2.), radius)
+    }
+}
+
+impl Painter for Dial {
+    fn paint(&self, canvas: &mut Canvas, size: Size) {
+        canvas.fill(Self::circle(size), &self.color);
+    }
+
+    fn should_repaint(&self, old: &Self) -> bool {
+        self.color != old.color
+    }
+
+    fn hit_test(&self, point: Point, size: Size) -> Option<bool> {
+        let circle = Self::circle(size);
+
+        Some(point.distance(circle.center) <= circle.radius)
+    }
+}
+
+/// Horizontal bar across the middle, as long as `value` of the width.
+struct Level {
+    value: f64,
+}
+
+impl Painter for Level {
+    fn paint(&self, canvas: &mut Canvas, size: Size) {
+        let y = size.height / 2.;
+        let bar = DruidRect::new(0., y - 5., size.width * self.value, y + 5.);
+
+        canvas.fill(bar, &Color::RED);
+    }
+
+    fn should_repaint(&self, old: &Self) -> bool {
+        self.value != old.value
+    }
+}
+
+fn gauge(value: f64) -> impl Widget {
+    SizedBox::from_size(
+        CustomPaint::builder()
+            .painter(Dial { color: Color::NAVY })
+            .foreground_painter(Level { value })
+            .child(Center::child(
+                Container::builder()
+                    .color(Color::WHITE)
+                    .width(20.)
+                    .height(20.),
+            )),
+        Size::new(100., 100.),
+    )
+}
+
+#[derive(ViewWidget)]
+struct App;
+
+impl WidgetState for App {
+    type State = f64;
+
+    fn create_state(&self) -> Self::State {
+        0.75
+    }
+}
+
+impl ViewWidget for App {
+    fn build<'w>(&'w self, cx: BuildCx<'w, Self>) -> Self::Widget<'w> {
+        KeyboardEventDetector {
+            on_event: move |event| {
+                let value = *cx.state();
+
+                match event.key {
+                    Key::ArrowUp => *cx.state_mut() = (value + 0.25).min(1.),
+                    Key::ArrowDown => *cx.state_mut() = (value - 0.25).max(0.),
+                    _ => {}
+                }
+            },
+            child: Center::child(RepaintBoundary::child(gauge(*cx.state()))),
+        }
+    }
+}
+
+fn main() {
+    run_app(App);
+}
+
+#[cfg(test)]
+mod test {
+    use std::cell::Cell;
+
+    use super::*;
+    use frui::app::runner::headless::{Finder, TestHarness};
+
+    thread_local! {
+        static PAINT_COUNT: Cell<usize> = Cell::new(0);
+        static TAP_COUNT: Cell<usize> = Cell::new(0);
+    }
+
+    fn gauge_rect(harness: &TestHarness) -> Rect {
+        harness
+            .find_one(&Finder::by_type::<CustomPaint<(), (), ()>>())
+            .global_rect()
+    }
+
+    #[test]
+    #[cfg(not(feature = "miri"))]
+    pub fn painters_paint_around_child() {
+        let harness = TestHarness::new(App);
+
+        let rect = gauge_rect(&harness);
+        let pixel = |x: f64, y: f64| {
+            harness
+                .frame()
+                .pixel((rect.left + x) as usize, (rect.top + y) as usize)
+        };
+
+        // Dial, then the hub, then the level bar.
+        assert_eq!(pixel(50., 20.), [0, 0, 128, 255]);
+        assert_eq!(pixel(50., 42.), [255, 255, 255, 255]);
+        assert_eq!(pixel(50., 50.), [255, 0, 0, 255]);
+        assert_eq!(pixel(20., 50.), [255, 0, 0, 255]);
+        assert_eq!(pixel(90., 50.), [0, 0, 128, 255]);
+
+        // Outside of the dial's circle.
+        assert_ne!(pixel(2., 2.), [0, 0, 128, 255]);
+    }
+
+    /// Dial which counts how many times it was painted.
+    struct CountingDial;
+
+    impl Painter for CountingDial {
+        fn paint(&self, canvas: &mut Canvas, size: Size) {
+            PAINT_COUNT.with(|count| count.set(count.get() + 1));
+
+            Dial { color: Color::NAVY }.paint(canvas, size)
+        }
+
+        fn should_repaint(&self, _: &Self) -> bool {
+            false
+        }
+    }
+
+    /// Gauge which level is changed by arrow keys, and which is rebuilt with
+    /// the same level by other keys.
+    #[derive(ViewWidget)]
+    struct CountedGauge;
+
+    impl WidgetState for CountedGauge {
+        type State = f64;
+
+        fn create_state(&self) -> Self::State {
+            0.5
+        }
+    }
+
+    impl ViewWidget for CountedGauge {
+        fn build<'w>(&'w self, cx: BuildCx<'w, Self>) -> Self::Widget<'w> {
+            KeyboardEventDetector {
+                on_event: move |event| {
+                    let mut value = cx.state_mut();
+
+                    if event.key == Key::ArrowUp {
+                        *value += 0.25;
+                    }
+                },
+                child: Center::child(RepaintBoundary::child(
+                    CustomPaint::builder()
+                        .painter(CountingDial)
+                        .foreground_painter(Level { value: *cx.state() })
+                        .size(Size::new(100., 100.)),
+                )),
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(not(feature = "miri"))]
+    pub fn equal_painters_skip_repaint() {
+        let mut harness = TestHarness::new(CountedGauge);
+        let paints = PAINT_COUNT.with(|count| count.get());
+
+        // Rebuilt with equal painters, so the layer is reused.
+        harness.press_key(Key::Enter);
+
+        assert_eq!(PAINT_COUNT.with(|count| count.get()), paints);
+
+        // Level changed.
+        harness.press_key(Key::ArrowUp);
+
+        assert_eq!(PAINT_COUNT.with(|count| count.get()), paints + 1);
+        assert_eq!(gauge_rect(&harness).width(), 100.);
+    }
+
+    #[test]
+    pub fn hit_test_follows_dial() {
+        let mut harness = TestHarness::new(Center::child(
+            Stack::builder().children((
+                PointerListener::builder()
+                    .on_pointer_down(|_| TAP_COUNT.with(|count| count.set(count.get() + 1)))
+                    .child(SizedBox::from_size((), Size::new(100., 100.))),
+                CustomPaint::builder()
+                    .painter(Dial { color: Color::NAVY })
+                    .size(Size::new(100., 100.)),
+            )),
+        ));
+
+        let rect = gauge_rect(&harness);
+
+        // Dial is hit, so the listener below it isn't.
+        harness.tap_at(Point::new(rect.left + 50., rect.top + 50.));
+
+        assert_eq!(TAP_COUNT.with(|count| count.get()), 0);
+
+        // Corner outside of the dial's circle.
+        harness.tap_at(Point::new(rect.left + 3., rect.top + 3.));
+
+        assert_eq!(TAP_COUNT.with(|count| count.get()), 1);
+    }
+
+    #[test]
+    pub fn stretched_empty_child_is_not_hit() {
+        // Expanded stack stretches the dial, and its empty child, to 100x100.
+        let mut harness = TestHarness::new(Center::child(SizedBox::from_size(
+            Stack::builder().fit(StackFit::Expand).children((
+                PointerListener::builder()
+                    .on_pointer_down(|_| TAP_COUNT.with(|count| count.set(count.get() + 1)))
+                    .child(()),
+                CustomPaint::builder().painter(Dial { color: Color::NAVY }),
+            )),
+            Size::new(100., 100.),
+        )));
+
+        let rect = gauge_rect(&harness);
+
+        assert_eq!(rect.width(), 100.);
+
+        // Corner outside of the dial's circle, but inside of the empty child.
+        harness.tap_at(Point::new(rect.left + 3., rect.top + 3.));
+
+        assert_eq!(TAP_COUNT.with(|count| count.get()), 1);
+    }
+}
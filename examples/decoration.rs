@@ -2,49 +2,109 @@
 
 use frui::prelude::*;
 
+#[derive(ViewWidget)]
+struct App;
+
+impl ViewWidget for App {
+    fn build<'w>(&'w self, _: BuildCx<'w, Self>) -> Self::Widget<'w> {
+        ColoredBox {
+            color: Color::WHITE,
+            child: Row::builder()
+                .main_axis_size(MainAxisSize::Max)
+                .cross_axis_size(CrossAxisSize::Max)
+                .main_axis_alignment(MainAxisAlignment::SpaceEvenly)
+                .cross_axis_alignment(CrossAxisAlignment::Center)
+                .children((
+                    SizedBox::from_size(
+                        DecoratedBox::builder()
+                            .position(DecorationPosition::Background)
+                            .decoration(
+                                BoxDecoration::builder()
+                                    .color(Color::Rgba32(0x28C6A8FF))
+                                    .border_radius(BorderRadius::circular(10.0))
+                                    .border(BoxBorder::all(
+                                        Color::Rgba32(0x000000FF),
+                                        2.0,
+                                        BorderStyle::Dash(vec![10.0, 5.0], 0.0),
+                                    ))
+                                    .box_shadow(vec![BoxShadow {
+                                        color: Color::BLACK.with_alpha(0.3),
+                                        offset: Offset::new(5.0, 2.0),
+                                        blur_radius: 10.0,
+                                        spread_radius: 0.0,
+                                        blur_style: BlurStyle::Normal,
+                                    }]),
+                            )
+                            .child(Center::child(Text::new("Hello, world!"))),
+                        Size::new(100.0, 100.0),
+                    ),
+                    SizedBox::from_size(
+                        DecoratedBox::builder()
+                            .position(DecorationPosition::Background)
+                            .decoration(
+                                BoxDecoration::builder()
+                                    .color(Color::Rgba32(0xFC6900FF))
+                                    .shape(BoxShape::Circle),
+                            )
+                            .child(Center::child(Text::new("+").size(60.0))),
+                        Size::new(100.0, 100.0),
+                    ),
+                )),
+        }
+    }
+}
+
 fn main() {
-    run_app(ColoredBox {
-        color: Color::WHITE,
-        child: Row::builder()
-            .main_axis_size(MainAxisSize::Max)
-            .cross_axis_size(CrossAxisSize::Max)
-            .main_axis_alignment(MainAxisAlignment::SpaceEvenly)
-            .cross_axis_alignment(CrossAxisAlignment::Center)
-            .children((
-                SizedBox::from_size(
-                    DecoratedBox::builder()
-                        .position(DecorationPosition::Background)
-                        .decoration(
-                            BoxDecoration::builder()
-                                .color(Color::Rgba32(0x28C6A8FF))
-                                .border_radius(BorderRadius::circular(10.0))
-                                .border(BoxBorder::all(
-                                    Color::Rgba32(0x000000FF),
-                                    2.0,
-                                    BorderStyle::Dash(vec![10.0, 5.0], 0.0),
-                                ))
-                                .box_shadow(vec![BoxShadow {
-                                    color: Color::BLACK.with_alpha(0.3),
-                                    offset: Offset::new(5.0, 2.0),
-                                    blur_radius: 10.0,
-                                    spread_radius: 0.0,
-                                    blur_style: BlurStyle::Normal,
-                                }]),
-                        )
-                        .child(Center::child(Text::new("Hello, world!"))),
-                    Size::new(100.0, 100.0),
-                ),
-                SizedBox::from_size(
-                    DecoratedBox::builder()
-                        .position(DecorationPosition::Background)
-                        .decoration(
-                            BoxDecoration::builder()
-                                .color(Color::Rgba32(0xFC6900FF))
-                                .shape(BoxShape::Circle),
-                        )
-                        .child(Center::child(Text::new("+").size(60.0))),
-                    Size::new(100.0, 100.0),
-                ),
-            )),
-    });
+    run_app(App);
+}
+
+#[cfg(all(test, not(feature = "miri")))]
+mod test {
+    use super::*;
+    use frui::app::runner::headless::TestHarness;
+
+    #[test]
+    pub fn paints_decorations() {
+        let harness = TestHarness::with_size(App, Size::new(500.0, 400.0));
+        let frame = harness.frame();
+
+        // Background.
+        assert_eq!(frame.pixel(10, 10), [255, 255, 255, 255]);
+        // Inside of the rounded box, next to its corner.
+        assert_eq!(frame.pixel(106, 156), [0x28, 0xC6, 0xA8, 255]);
+        // Shadow to the right of the rounded box.
+        assert!(frame.pixel(203, 200)[0] < 255);
+        // Inside of the circle, but outside of its bounding box corner.
+        assert_eq!(frame.pixel(320, 200), [0xFC, 0x69, 0x00, 255]);
+        assert_eq!(frame.pixel(303, 153), [255, 255, 255, 255]);
+    }
+
+    #[test]
+    pub fn decoration_matches_golden() {
+        // Edges of the box and of its border lie on whole pixels, so only the
+        // shadow is anti-aliased.
+        let harness = TestHarness::with_size(
+            ColoredBox {
+                color: Color::WHITE,
+                child: Center::child(SizedBox::from_size(
+                    DecoratedBox::builder().decoration(
+                        BoxDecoration::builder()
+                            .color(Color::Rgba32(0x28C6A8FF))
+                            .border(BoxBorder::all(Color::BLACK, 2.0, BorderStyle::Solid))
+                            .box_shadow(vec![BoxShadow {
+                                color: Color::BLACK.with_alpha(0.3),
+                                offset: Offset::new(4.0, 4.0),
+                                blur_radius: 3.0,
+                                spread_radius: 0.0,
+                                blur_style: BlurStyle::Normal,
+                            }]),
+                    ),
+                    Size::new(32.0, 20.0),
+                )),
+            },
+            Size::new(64.0, 48.0),
+        );
+
+        harness.assert_matches_golden("decoration.png");
+    }
 }
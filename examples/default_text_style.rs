@@ -0,0 +1,95 @@
+//! Styling all text of the app from one place with [`DefaultTextStyle`].
+//!
+//! Press any key to make the text larger. Configurations of widgets displaying
+//! that text don't change, but they are laid out again with the new style,
+//! since they depend on [`DefaultTextStyle`].
+
+#![feature(type_alias_impl_trait)]
+
+use frui::{druid_shell::piet::FontStyle, prelude::*};
+
+#[derive(ViewWidget)]
+struct App;
+
+impl WidgetState for App {
+    type State = bool;
+
+    fn create_state(&self) -> Self::State {
+        false
+    }
+}
+
+impl ViewWidget for App {
+    fn build<'w>(&'w self, cx: BuildCx<'w, Self>) -> Self::Widget<'w> {
+        let size = if *cx.state() { 32. } else { 16. };
+
+        KeyboardEventDetector {
+            on_event: |_| {
+                let mut large = cx.state_mut();
+                *large = !*large;
+            },
+            child: DefaultTextStyle {
+                style: TextStyle::default()
+                    .size(size)
+                    .color(Color::rgb8(0xee, 0xdd, 0x88)),
+                child: Center::child(Column::builder().space_between(10.0).children((
+                    Text::new("Inherited style"),
+                    DefaultTextStyle {
+                        style: TextStyle::default().style(FontStyle::Italic),
+                        child: Text::new("Merged with italic style"),
+                    },
+                    Text::new("Own size").size(12.),
+                ))),
+            },
+        }
+    }
+}
+
+fn main() {
+    run_app(App);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use frui::{
+        app::runner::headless::{Finder, TestHarness},
+        druid_shell::keyboard_types::Key,
+    };
+
+    #[allow(dead_code)]
+    fn text_heights(harness: &TestHarness) -> Vec<f64> {
+        harness
+            .find_all(&Finder::by_type::<Text<&str>>())
+            .iter()
+            .map(|text| text.size().height)
+            .collect()
+    }
+
+    #[cfg(not(feature = "miri"))]
+    #[test]
+    pub fn nested_styles_are_merged() {
+        let harness = TestHarness::new(App);
+        let heights = text_heights(&harness);
+
+        // Size is inherited through both default text styles.
+        assert_eq!(heights[0], heights[1]);
+        assert!(heights[2] < heights[0]);
+    }
+
+    #[cfg(not(feature = "miri"))]
+    #[test]
+    pub fn changing_style_updates_text() {
+        let mut harness = TestHarness::new(App);
+        let before = text_heights(&harness);
+
+        harness.press_key(Key::Enter);
+
+        let after = text_heights(&harness);
+
+        assert!(after[0] > before[0]);
+        assert!(after[1] > before[1]);
+        // Text which sets its own size isn't affected.
+        assert_eq!(after[2], before[2]);
+    }
+}
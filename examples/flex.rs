@@ -74,3 +74,120 @@ fn main() {
         child: App,
     });
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use frui::{
+        app::runner::headless::{Finder, TestHarness},
+        druid_shell::keyboard_types::Key,
+    };
+
+    #[derive(ViewWidget)]
+    struct CenteredRow;
+
+    impl ViewWidget for CenteredRow {
+        fn build<'w>(&'w self, _: BuildCx<'w, Self>) -> Self::Widget<'w> {
+            Center::child(Row::builder().space_between(10.0).children((
+                SizedBox::from_size((), Size::new(100.0, 50.0)),
+                SizedBox::from_size((), Size::new(50.0, 50.0)),
+            )))
+        }
+    }
+
+    #[test]
+    pub fn lays_out_row_in_headless_window() {
+        let harness = TestHarness::with_size(CenteredRow, Size::new(500.0, 400.0));
+
+        let row = harness.find_one(&Finder::by_type::<Flex<()>>());
+
+        assert_eq!(row.size(), Size::new(160.0, 50.0));
+        assert_eq!(row.global_offset(), Offset::new(170.0, 175.0));
+
+        let boxes = harness.find_all(&Finder::by_type::<ConstrainedBox<()>>());
+
+        assert_eq!(boxes.len(), 2);
+        assert_eq!(boxes[0].size(), Size::new(100.0, 50.0));
+        assert_eq!(boxes[0].global_offset(), Offset::new(170.0, 175.0));
+        assert_eq!(boxes[1].global_offset(), Offset::new(280.0, 175.0));
+        assert_eq!(boxes[1].local_offset(), Offset::new(110.0, 0.0));
+    }
+
+    #[test]
+    pub fn parent_of_root_child_is_root() {
+        let harness = TestHarness::new(CenteredRow);
+
+        let center = harness.find_one(&Finder::by_type::<Center<()>>());
+
+        assert!(center.parent().unwrap().is::<CenteredRow>());
+        assert!(harness.root().parent().is_none());
+    }
+
+    /// Row of two expanded children, where the flex factor of the first one
+    /// is increased on every key press, and a loose flexible child.
+    #[derive(ViewWidget)]
+    struct FlexibleRow;
+
+    impl WidgetState for FlexibleRow {
+        type State = usize;
+
+        fn create_state(&self) -> Self::State {
+            1
+        }
+    }
+
+    impl ViewWidget for FlexibleRow {
+        fn build<'w>(&'w self, cx: BuildCx<'w, Self>) -> Self::Widget<'w> {
+            KeyboardEventDetector {
+                on_event: move |_| *cx.state_mut() += 1,
+                child: Center::child(SizedBox::new(
+                    Row::builder().children((
+                        Expanded::new(SizedBox::new((), None, Some(50.0))).flex(*cx.state()),
+                        Expanded::new(SizedBox::new((), None, Some(50.0))),
+                        Flexible::new(SizedBox::new((), Some(20.0), Some(50.0))),
+                    )),
+                    Some(320.0),
+                    Some(50.0),
+                )),
+            }
+        }
+    }
+
+    /// Offsets and widths of children of the row.
+    fn child_rects(harness: &TestHarness) -> Vec<(f64, f64)> {
+        let row = harness.find_one(&Finder::by_type::<Flex<()>>());
+        let x0 = row.global_rect().left;
+
+        row.children()
+            .iter()
+            .map(|child| {
+                let rect = child.global_rect();
+                (rect.left - x0, rect.width())
+            })
+            .collect()
+    }
+
+    #[test]
+    pub fn expanded_children_share_free_space() {
+        let harness = TestHarness::new(FlexibleRow);
+
+        // Loose child takes only what it needs, leaving the rest of the space
+        // to the expanded children.
+        assert_eq!(
+            child_rects(&harness),
+            vec![(0.0, 150.0), (150.0, 150.0), (300.0, 20.0)]
+        );
+    }
+
+    #[test]
+    pub fn changed_flex_factor_is_applied() {
+        let mut harness = TestHarness::new(FlexibleRow);
+
+        harness.press_key(Key::Enter);
+
+        assert_eq!(
+            child_rects(&harness),
+            vec![(0.0, 200.0), (200.0, 100.0), (300.0, 20.0)]
+        );
+    }
+}
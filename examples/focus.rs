@@ -0,0 +1,167 @@
+//! Boxes which can be focused with Tab / Shift+Tab. Focused box is colored
+//! and counts presses of Enter.
+//!
+//! Last two boxes are grouped in a [`FocusScope`], so once focus gets into
+//! the scope, Tab cycles only between them. Escape isn't handled by any of
+//! the boxes, so it bubbles up to the [`Focus`] widget of the [`App`].
+
+#![feature(type_alias_impl_trait)]
+
+use frui::{
+    druid_shell::{keyboard_types::Key, KeyState},
+    prelude::*,
+};
+
+#[derive(ViewWidget)]
+struct App;
+
+impl WidgetState for App {
+    /// Number of times Escape was pressed.
+    type State = usize;
+
+    fn create_state(&self) -> Self::State {
+        0
+    }
+}
+
+impl ViewWidget for App {
+    fn build<'w>(&'w self, cx: BuildCx<'w, Self>) -> Self::Widget<'w> {
+        Focus::builder()
+            .can_request_focus(false)
+            .on_key(|event| match (&event.key, event.state) {
+                (Key::Escape, KeyState::Down) => {
+                    *cx.state_mut() += 1;
+                    KeyEventResult::Handled
+                }
+                _ => KeyEventResult::Ignored,
+            })
+            .child(Center::child(Row::builder().space_between(20.0).children(
+                (
+                    FocusableBox { autofocus: true },
+                    FocusableBox { autofocus: false },
+                    FocusScope::builder().child(Row::builder().space_between(20.0).children((
+                        FocusableBox { autofocus: false },
+                        FocusableBox { autofocus: false },
+                    ))),
+                ),
+            )))
+    }
+}
+
+#[derive(ViewWidget)]
+struct FocusableBox {
+    autofocus: bool,
+}
+
+#[derive(Default)]
+struct FocusableBoxState {
+    has_focus: bool,
+    presses: usize,
+}
+
+impl WidgetState for FocusableBox {
+    type State = FocusableBoxState;
+
+    fn create_state(&self) -> Self::State {
+        FocusableBoxState::default()
+    }
+}
+
+impl ViewWidget for FocusableBox {
+    fn build<'w>(&'w self, cx: BuildCx<'w, Self>) -> Self::Widget<'w> {
+        let color = match cx.state().has_focus {
+            true => Color::rgb8(40, 198, 168),
+            false => Color::rgb8(80, 80, 80),
+        };
+
+        Focus::builder()
+            .autofocus(self.autofocus)
+            .on_key(|event| match (&event.key, event.state) {
+                (Key::Enter, KeyState::Down) => {
+                    cx.state_mut().presses += 1;
+                    KeyEventResult::Handled
+                }
+                _ => KeyEventResult::Ignored,
+            })
+            .on_focus_change(|has_focus| cx.state_mut().has_focus = has_focus)
+            .child(Container::builder().width(80.0).height(80.0).color(color))
+    }
+}
+
+fn main() {
+    run_app(App);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use frui::{
+        app::runner::headless::{Finder, TestHarness},
+        druid_shell::Modifiers,
+    };
+
+    /// Focus and number of Enter presses of each box, in tree order.
+    fn boxes(harness: &TestHarness) -> Vec<(bool, usize)> {
+        harness
+            .find_all(&Finder::by_type::<FocusableBox>())
+            .iter()
+            .map(|node| {
+                let state = node.state::<FocusableBoxState>().unwrap();
+                (state.has_focus, state.presses)
+            })
+            .collect()
+    }
+
+    #[test]
+    pub fn key_events_go_to_focused_widget() {
+        let mut harness = TestHarness::new(App);
+
+        assert_eq!(
+            boxes(&harness),
+            [(true, 0), (false, 0), (false, 0), (false, 0)]
+        );
+
+        harness.press_key(Key::Enter);
+        harness.press_key(Key::Tab);
+        harness.press_key(Key::Enter);
+        harness.press_key(Key::Enter);
+
+        assert_eq!(
+            boxes(&harness),
+            [(false, 1), (true, 2), (false, 0), (false, 0)]
+        );
+    }
+
+    #[test]
+    pub fn unhandled_events_bubble_up() {
+        let mut harness = TestHarness::new(App);
+
+        harness.press_key(Key::Escape);
+
+        let app = harness.find_one(&Finder::by_type::<App>());
+
+        assert_eq!(*app.state::<usize>().unwrap(), 1);
+    }
+
+    #[test]
+    pub fn tab_traversal_stays_in_scope() {
+        let mut harness = TestHarness::new(App);
+
+        let focused =
+            |harness: &TestHarness| boxes(harness).iter().position(|(has_focus, _)| *has_focus);
+
+        harness.press_key_with(Key::Tab, Modifiers::SHIFT);
+
+        // Scope is entered through its first box.
+        assert_eq!(focused(&harness), Some(2));
+
+        for expected in [3, 2, 3] {
+            harness.press_key(Key::Tab);
+            assert_eq!(focused(&harness), Some(expected));
+        }
+
+        harness.press_key_with(Key::Tab, Modifiers::SHIFT);
+
+        assert_eq!(focused(&harness), Some(2));
+    }
+}
@@ -0,0 +1,120 @@
+//! Tiles laid out in a [`GridView`] with as many columns as fit, given that
+//! no column may be wider than 100 pixels.
+
+#![feature(type_alias_impl_trait)]
+
+use frui::prelude::*;
+
+const TILE_COUNT: usize = 8;
+
+fn tiles() -> Vec<LocalKey<usize, Container<()>>> {
+    (0..TILE_COUNT)
+        .map(|n| LocalKey::new(n, Container::builder().color(Color::AQUA)))
+        .collect()
+}
+
+#[derive(ViewWidget)]
+struct App;
+
+impl ViewWidget for App {
+    fn build<'w>(&'w self, _: BuildCx<'w, Self>) -> Self::Widget<'w> {
+        Center::child(SizedBox::new(
+            GridView::builder()
+                .delegate(MaxCrossAxisExtent(100.))
+                .main_axis_spacing(10.)
+                .cross_axis_spacing(10.)
+                .child_aspect_ratio(2.)
+                .children(tiles()),
+            Some(320.),
+            None,
+        ))
+    }
+}
+
+fn main() {
+    run_app(App);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use frui::app::runner::headless::{Finder, TestHarness};
+
+    /// Rectangle of a tile relative to the `GridView`.
+    fn tile_rect(harness: &TestHarness, n: usize) -> (f64, f64, f64, f64) {
+        let grid = harness
+            .find_one(&Finder::by_type::<GridView<(), FixedCrossAxisCount>>())
+            .global_rect();
+        let tile = harness.find_one(&Finder::by_key(n)).global_rect();
+
+        (
+            tile.left - grid.left,
+            tile.top - grid.top,
+            tile.width(),
+            tile.height(),
+        )
+    }
+
+    #[test]
+    pub fn max_extent_fits_columns() {
+        let harness = TestHarness::new(App);
+
+        // Three columns of 100 pixels and two gaps of 10 pixels.
+        assert_eq!(tile_rect(&harness, 0), (0., 0., 100., 50.));
+        assert_eq!(tile_rect(&harness, 2), (220., 0., 100., 50.));
+        assert_eq!(tile_rect(&harness, 3), (0., 60., 100., 50.));
+        assert_eq!(tile_rect(&harness, 7), (110., 120., 100., 50.));
+
+        let grid = harness.find_one(&Finder::by_type::<GridView<(), FixedCrossAxisCount>>());
+
+        assert_eq!(grid.size(), Size::new(320., 170.));
+    }
+
+    /// Grid of four columns with tiles of different heights.
+    #[derive(ViewWidget)]
+    struct FixedGrid {
+        text_direction: TextDirection,
+    }
+
+    impl ViewWidget for FixedGrid {
+        fn build<'w>(&'w self, _: BuildCx<'w, Self>) -> Self::Widget<'w> {
+            let tiles = (0..TILE_COUNT)
+                .map(|n| LocalKey::new(n, Container::builder().height(10. * (n + 1) as f64)))
+                .collect::<Vec<_>>();
+
+            Center::child(SizedBox::new(
+                GridView::builder()
+                    .delegate(FixedCrossAxisCount(4))
+                    .text_direction(self.text_direction)
+                    .cross_axis_alignment(CrossAxisAlignment::Start)
+                    .main_axis_alignment(MainAxisAlignment::End)
+                    .children(tiles),
+                Some(400.),
+                None,
+            ))
+        }
+    }
+
+    #[test]
+    pub fn rows_fit_tallest_tile() {
+        let harness = TestHarness::new(FixedGrid {
+            text_direction: TextDirection::Ltr,
+        });
+
+        // Tiles without width take none, and are aligned to the bottom of the
+        // tallest tile in their row.
+        assert_eq!(tile_rect(&harness, 0), (0., 30., 0., 10.));
+        assert_eq!(tile_rect(&harness, 3), (300., 0., 0., 40.));
+        assert_eq!(tile_rect(&harness, 4), (0., 70., 0., 50.));
+    }
+
+    #[test]
+    pub fn rtl_places_columns_from_right() {
+        let harness = TestHarness::new(FixedGrid {
+            text_direction: TextDirection::Rtl,
+        });
+
+        assert_eq!(tile_rect(&harness, 0), (400., 30., 0., 10.));
+        assert_eq!(tile_rect(&harness, 3), (100., 0., 0., 40.));
+    }
+}
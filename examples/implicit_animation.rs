@@ -0,0 +1,115 @@
+//! Pressing any key toggles size, color and opacity of a box, which smoothly
+//! animate to their new values.
+
+#![feature(type_alias_impl_trait)]
+
+use std::time::Duration;
+
+use frui::prelude::*;
+
+#[derive(ViewWidget)]
+struct App;
+
+impl WidgetState for App {
+    type State = bool;
+
+    fn create_state(&self) -> Self::State {
+        false
+    }
+}
+
+impl ViewWidget for App {
+    fn build<'w>(&'w self, cx: BuildCx<'w, Self>) -> Self::Widget<'w> {
+        let expanded = *cx.state();
+
+        KeyboardEventDetector {
+            on_event: |_| {
+                let expanded = !*cx.state();
+                *cx.state_mut() = expanded;
+            },
+            child: Center::child(
+                AnimatedOpacity::builder()
+                    .opacity(if expanded { 1.0 } else { 0.5 })
+                    .duration(Duration::from_millis(100))
+                    .child(
+                        AnimatedContainer::builder()
+                            .width(if expanded { 300.0 } else { 100.0 })
+                            .height(100.0)
+                            .color(if expanded { Color::RED } else { Color::BLUE })
+                            .duration(Duration::from_millis(100))
+                            .curve(&Curves::EASE_IN_OUT),
+                    ),
+            ),
+        }
+    }
+}
+
+fn main() {
+    run_app(App);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use frui::{
+        app::runner::headless::{Finder, TestHarness},
+        druid_shell::keyboard_types::Key,
+    };
+
+    fn container_width(harness: &TestHarness) -> f64 {
+        harness
+            .find_one(&Finder::by_type::<Container<()>>())
+            .size()
+            .width
+    }
+
+    #[test]
+    pub fn container_animates_to_new_width() {
+        let mut harness = TestHarness::new(App);
+
+        assert_eq!(container_width(&harness), 100.0);
+
+        harness.press_key(Key::Enter);
+
+        // Animation starts in the frame the new configuration is built in.
+        assert_eq!(container_width(&harness), 100.0);
+
+        harness.advance_time(Duration::from_millis(50));
+
+        // Ease-in-out is symmetric, so it's in the middle at half the time.
+        assert!((container_width(&harness) - 200.0).abs() < 1.0);
+
+        harness.advance_time(Duration::from_millis(50));
+
+        assert_eq!(container_width(&harness), 300.0);
+
+        // Reversing in the middle of an animation continues from there.
+        harness.press_key(Key::Enter);
+        harness.advance_time(Duration::from_millis(50));
+        harness.press_key(Key::Enter);
+
+        let width = container_width(&harness);
+
+        assert!(width > 100.0 && width < 300.0);
+
+        harness.pump_until_settled();
+
+        assert_eq!(container_width(&harness), 300.0);
+    }
+
+    #[test]
+    #[cfg(not(feature = "miri"))]
+    pub fn opacity_blends_with_background() {
+        let mut harness = TestHarness::new(App);
+
+        // Blue box at half opacity over dark background.
+        let [r, g, b, _] = harness.frame().pixel(250, 200);
+
+        assert!(r < 40 && g < 40 && (100..160).contains(&b));
+
+        harness.press_key(Key::Enter);
+        harness.pump_until_settled();
+
+        assert_eq!(harness.frame().pixel(250, 200), [255, 0, 0, 255]);
+    }
+}
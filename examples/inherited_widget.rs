@@ -150,3 +150,190 @@ mod test {
         }
     }
 }
+
+#[cfg(test)]
+mod headless_test {
+    use std::cell::Cell;
+
+    use super::*;
+    use frui::{
+        app::runner::headless::{Finder, TestHarness},
+        druid_shell::keyboard_types::Key,
+    };
+
+    /// Depends on [`InheritedSwitch`] and passes its value to [`Inner`], which
+    /// depends on the same widget.
+    #[derive(ViewWidget)]
+    struct Outer;
+
+    impl ViewWidget for Outer {
+        fn build<'w>(&'w self, cx: BuildCx<'w, Self>) -> Self::Widget<'w> {
+            Inner(*InheritedSwitch::of(cx))
+        }
+    }
+
+    #[derive(ViewWidget)]
+    struct Inner(bool);
+
+    impl WidgetState for Inner {
+        type State = Cell<usize>;
+
+        fn create_state(&self) -> Self::State {
+            Cell::new(0)
+        }
+    }
+
+    impl ViewWidget for Inner {
+        fn build<'w>(&'w self, cx: BuildCx<'w, Self>) -> Self::Widget<'w> {
+            cx.state().set(cx.state().get() + 1);
+
+            Text::new(InheritedSwitch::of(cx).to_string())
+        }
+    }
+
+    #[test]
+    pub fn dependents_are_rebuilt_once() {
+        let mut harness = TestHarness::new(InheritedSwitch {
+            child: Column::builder().children((Outer, InheritedSwitchDispatcher)),
+        });
+
+        let inner = harness.find_one(&Finder::by_type::<Inner>());
+
+        assert_eq!(inner.state::<Cell<usize>>().unwrap().get(), 1);
+
+        for n in 2..5 {
+            harness.press_key(Key::Character(" ".into()));
+
+            // Inner is rebuilt by Outer, so it must not be rebuilt again even
+            // though it depends on InheritedSwitch itself.
+            assert_eq!(inner.state::<Cell<usize>>().unwrap().get(), n);
+        }
+    }
+
+    /// Provides `value` to its subtree. Its state is taken from its
+    /// configuration when it's mounted.
+    #[derive(InheritedWidget)]
+    struct InheritedValue<W: Widget> {
+        value: usize,
+        child: W,
+    }
+
+    impl<W: Widget> WidgetState for InheritedValue<W> {
+        type State = usize;
+
+        fn create_state(&self) -> Self::State {
+            self.value
+        }
+
+        fn mount<'a>(&'a self, cx: BuildCx<'a, Self>) {
+            *cx.state_mut() = self.value;
+        }
+    }
+
+    impl<W: Widget> InheritedWidget for InheritedValue<W> {
+        fn build<'w>(&'w self) -> Self::Widget<'w> {
+            &self.child
+        }
+    }
+
+    /// Records the value of [`InheritedValue`] it was last built with.
+    #[derive(ViewWidget)]
+    struct ValueConsumer;
+
+    impl WidgetState for ValueConsumer {
+        type State = Cell<usize>;
+
+        fn create_state(&self) -> Self::State {
+            Cell::new(0)
+        }
+    }
+
+    impl ViewWidget for ValueConsumer {
+        fn build<'w>(&'w self, cx: BuildCx<'w, Self>) -> Self::Widget<'w> {
+            let state = cx
+                .depend_on_inherited_widget::<InheritedValue<()>>()
+                .unwrap();
+            cx.state().set(*state.as_ref());
+        }
+    }
+
+    /// Copies its configuration to its state when it's mounted, and records
+    /// the value of that state it was last built with.
+    #[derive(ViewWidget)]
+    struct Mirror(usize);
+
+    impl WidgetState for Mirror {
+        type State = (usize, Cell<usize>);
+
+        fn create_state(&self) -> Self::State {
+            (self.0, Cell::new(0))
+        }
+
+        fn mount<'a>(&'a self, cx: BuildCx<'a, Self>) {
+            cx.state_mut().0 = self.0;
+        }
+    }
+
+    impl ViewWidget for Mirror {
+        fn build<'w>(&'w self, cx: BuildCx<'w, Self>) -> Self::Widget<'w> {
+            let state = cx.state();
+            state.1.set(state.0);
+        }
+    }
+
+    /// Passes the number of key presses to [`Mirror`] and [`InheritedValue`].
+    #[derive(ViewWidget)]
+    struct Presses;
+
+    impl WidgetState for Presses {
+        type State = usize;
+
+        fn create_state(&self) -> Self::State {
+            0
+        }
+    }
+
+    impl ViewWidget for Presses {
+        fn build<'w>(&'w self, cx: BuildCx<'w, Self>) -> Self::Widget<'w> {
+            let presses = *cx.state();
+
+            KeyboardEventDetector {
+                on_event: |_| *cx.state_mut() += 1,
+                child: Column::builder().children((
+                    Mirror(presses),
+                    InheritedValue {
+                        value: presses,
+                        child: ValueConsumer,
+                    },
+                )),
+            }
+        }
+    }
+
+    #[test]
+    pub fn updated_widget_is_mounted_before_build() {
+        let mut harness = TestHarness::new(Presses);
+
+        harness.press_key(Key::Character(" ".into()));
+
+        let mirror = harness.find_one(&Finder::by_type::<Mirror>());
+
+        // State was updated in `mount` before `Mirror` was built.
+        assert_eq!(mirror.state::<(usize, Cell<usize>)>().unwrap().1.get(), 1);
+    }
+
+    #[test]
+    pub fn dependents_are_rebuilt_when_inherited_widget_is_updated() {
+        let mut harness = TestHarness::new(Presses);
+
+        let consumer = harness.find_one(&Finder::by_type::<ValueConsumer>());
+
+        for n in 1..4 {
+            harness.press_key(Key::Character(" ".into()));
+
+            // Configuration of the consumer didn't change, but it's rebuilt
+            // since it depends on the updated `InheritedValue`.
+            assert_eq!(consumer.state::<Cell<usize>>().unwrap().get(), n);
+        }
+    }
+}
@@ -0,0 +1,115 @@
+//! Children of a [`Column`] made as wide as the widest of them with
+//! [`IntrinsicWidth`], and children of a [`Row`] made as tall as the tallest of
+//! them with [`IntrinsicHeight`].
+
+#![feature(type_alias_impl_trait)]
+
+use frui::prelude::*;
+
+#[derive(ViewWidget)]
+struct App;
+
+impl ViewWidget for App {
+    fn build<'w>(&'w self, _: BuildCx<'w, Self>) -> Self::Widget<'w> {
+        Center::child(
+            Column::builder().space_between(20.).children((
+                IntrinsicWidth::child(
+                    Column::builder()
+                        .space_between(10.)
+                        .cross_axis_alignment(CrossAxisAlignment::Stretch)
+                        .children((
+                            Container::builder()
+                                .color(Color::AQUA)
+                                .width(50.)
+                                .height(30.),
+                            Container::builder()
+                                .color(Color::GREEN)
+                                .width(100.)
+                                .height(30.),
+                        )),
+                ),
+                IntrinsicHeight::child(
+                    Row::builder()
+                        .space_between(10.)
+                        .cross_axis_alignment(CrossAxisAlignment::Stretch)
+                        .children((
+                            Container::builder()
+                                .color(Color::FUCHSIA)
+                                .width(40.)
+                                .height(60.),
+                            Container::builder().color(Color::YELLOW).width(40.),
+                        )),
+                ),
+            )),
+        )
+    }
+}
+
+fn main() {
+    run_app(App);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use frui::app::runner::headless::{Finder, TestHarness};
+
+    fn child_sizes(harness: &TestHarness, finder: &Finder) -> Vec<Size> {
+        harness.find_one(finder).children()[0]
+            .children()
+            .iter()
+            .map(|child| child.size())
+            .collect()
+    }
+
+    #[test]
+    pub fn column_children_are_as_wide_as_widest() {
+        let harness = TestHarness::new(App);
+        let finder = Finder::by_type::<IntrinsicWidth<()>>();
+
+        assert_eq!(harness.find_one(&finder).size(), Size::new(100., 70.));
+        assert_eq!(
+            child_sizes(&harness, &finder),
+            vec![Size::new(100., 30.), Size::new(100., 30.)]
+        );
+    }
+
+    #[test]
+    pub fn row_children_are_as_tall_as_tallest() {
+        let harness = TestHarness::new(App);
+        let finder = Finder::by_type::<IntrinsicHeight<()>>();
+
+        assert_eq!(harness.find_one(&finder).size(), Size::new(90., 60.));
+        assert_eq!(
+            child_sizes(&harness, &finder),
+            vec![Size::new(40., 60.), Size::new(40., 60.)]
+        );
+    }
+
+    /// Padded text sized to its intrinsic width.
+    #[derive(ViewWidget)]
+    struct PaddedText;
+
+    impl ViewWidget for PaddedText {
+        fn build<'w>(&'w self, _: BuildCx<'w, Self>) -> Self::Widget<'w> {
+            Center::child(IntrinsicWidth::child(
+                Padding::builder()
+                    .padding(EdgeInsets::all(10.))
+                    .child(Text::new("Hello, intrinsic world!")),
+            ))
+        }
+    }
+
+    #[cfg(not(feature = "miri"))]
+    #[test]
+    pub fn text_is_not_wrapped_in_intrinsic_width() {
+        let harness = TestHarness::new(PaddedText);
+
+        let intrinsic = harness.find_one(&Finder::by_type::<IntrinsicWidth<()>>());
+        let text = harness.find_one(&Finder::by_type::<Text<&str>>());
+
+        assert!(text.size().width > 0.);
+        assert_eq!(intrinsic.size().width, text.size().width + 20.);
+        assert_eq!(intrinsic.size().height, text.size().height + 20.);
+    }
+}
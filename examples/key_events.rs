@@ -0,0 +1,101 @@
+//! Displays keys which are currently held down, the way games usually track
+//! keyboard input.
+//!
+//! [`RawKeyboardListener`] receives every key press and release, as well as
+//! changes of modifier keys, regardless of which widget is focused.
+
+#![feature(type_alias_impl_trait)]
+
+use frui::{
+    druid_shell::{keyboard_types::Key, Modifiers},
+    prelude::*,
+};
+
+#[derive(ViewWidget)]
+struct App;
+
+#[derive(Default)]
+struct HeldKeys {
+    keys: Vec<Key>,
+    modifiers: Modifiers,
+}
+
+impl WidgetState for App {
+    type State = HeldKeys;
+
+    fn create_state(&self) -> Self::State {
+        HeldKeys::default()
+    }
+}
+
+impl ViewWidget for App {
+    fn build<'w>(&'w self, cx: BuildCx<'w, Self>) -> Self::Widget<'w> {
+        let text = format!(
+            "Held: {:?} (shift: {})",
+            cx.state().keys,
+            cx.state().modifiers.shift()
+        );
+
+        RawKeyboardListener {
+            on_event: |event| match event {
+                RawKeyEvent::KeyDown(event) => {
+                    let mut state = cx.state_mut();
+
+                    if !state.keys.contains(&event.key) {
+                        state.keys.push(event.key);
+                    }
+                }
+                RawKeyEvent::KeyUp(event) => cx.state_mut().keys.retain(|k| *k != event.key),
+                RawKeyEvent::ModifiersChanged(modifiers) => cx.state_mut().modifiers = modifiers,
+            },
+            child: Center::child(Text::new(text)),
+        }
+    }
+}
+
+fn main() {
+    run_app(App);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use frui::app::runner::headless::{Finder, TestHarness};
+
+    fn held_keys(harness: &TestHarness) -> (Vec<Key>, bool) {
+        let app = harness.find_one(&Finder::by_type::<App>());
+        let state = app.state::<HeldKeys>().unwrap();
+
+        (state.keys.clone(), state.modifiers.shift())
+    }
+
+    #[test]
+    pub fn tracks_pressed_and_released_keys() {
+        let mut harness = TestHarness::new(App);
+
+        harness.press_key(Key::ArrowLeft);
+        harness.press_key(Key::ArrowUp);
+
+        assert_eq!(
+            held_keys(&harness),
+            (vec![Key::ArrowLeft, Key::ArrowUp], false)
+        );
+
+        harness.release_key(Key::ArrowLeft);
+
+        assert_eq!(held_keys(&harness), (vec![Key::ArrowUp], false));
+    }
+
+    #[test]
+    pub fn reports_modifier_changes() {
+        let mut harness = TestHarness::new(App);
+
+        harness.press_key_with(Key::Shift, Modifiers::SHIFT);
+
+        assert_eq!(held_keys(&harness), (vec![Key::Shift], true));
+
+        harness.release_key(Key::Shift);
+
+        assert_eq!(held_keys(&harness), (vec![], false));
+    }
+}
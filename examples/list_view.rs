@@ -0,0 +1,235 @@
+//! List of a hundred thousand rows displayed with [`ListView`].
+//!
+//! Only rows which are visible (or close to being visible) are built, laid out
+//! and painted. Since all rows have the same height, the list is given that
+//! height, so it doesn't need to measure rows to find where they are.
+//!
+//! Tapping a row selects it. Press `End` to jump to the middle of the list.
+
+#![feature(type_alias_impl_trait)]
+
+use frui::{
+    druid_shell::{keyboard_types::Key, kurbo::Vec2},
+    prelude::*,
+};
+
+const ROW_COUNT: usize = 100_000;
+const ROW_HEIGHT: f64 = 40.;
+
+#[derive(ViewWidget)]
+struct App;
+
+struct AppState {
+    selected: Option<usize>,
+    controller: ScrollController,
+}
+
+impl WidgetState for App {
+    type State = AppState;
+
+    fn create_state(&self) -> Self::State {
+        AppState {
+            selected: None,
+            controller: ScrollController::new(),
+        }
+    }
+}
+
+impl ViewWidget for App {
+    fn build<'w>(&'w self, cx: BuildCx<'w, Self>) -> Self::Widget<'w> {
+        let state = cx.state();
+        let selected = state.selected;
+        let controller = state.controller.clone();
+
+        KeyboardEventDetector {
+            on_event: move |event| {
+                if event.key == Key::End {
+                    let middle = (ROW_COUNT / 2) as f64 * ROW_HEIGHT;
+                    let controller = cx.state().controller.clone();
+                    controller.jump_to(Vec2::new(0., middle));
+                }
+            },
+            child: Center::child(SizedBox::new(
+                ListView::builder(ROW_COUNT, move |_, n| {
+                    let label = match selected == Some(n) {
+                        true => format!("Row {} (selected)", n),
+                        false => format!("Row {}", n),
+                    };
+
+                    PointerListener::builder()
+                        .on_pointer_down(move |_| cx.state_mut().selected = Some(n))
+                        .child(SizedBox::new(Text::new(label), None, Some(ROW_HEIGHT)))
+                })
+                .item_extent(ROW_HEIGHT)
+                .controller(controller),
+                Some(300.),
+                Some(200.),
+            )),
+        }
+    }
+}
+
+fn main() {
+    run_app(App);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use frui::{
+        app::runner::headless::{Finder, TestHarness},
+        druid_shell::kurbo::Point,
+        render::*,
+    };
+
+    fn viewport(harness: &TestHarness) -> Rect {
+        harness
+            .find_one(&Finder::by_type::<Center<()>>())
+            .child(0)
+            .unwrap()
+            .global_rect()
+    }
+
+    fn selected(harness: &TestHarness) -> Option<usize> {
+        harness
+            .find_one(&Finder::by_type::<App>())
+            .state::<AppState>()
+            .unwrap()
+            .selected
+    }
+
+    fn built_rows(harness: &TestHarness) -> usize {
+        harness.find_all(&Finder::by_type::<Text<&str>>()).len()
+    }
+
+    #[test]
+    pub fn only_rows_near_viewport_are_built() {
+        let harness = TestHarness::new(App);
+
+        // Viewport of 200 and cache extents of 250 on both sides.
+        assert_eq!(built_rows(&harness), 12);
+    }
+
+    #[test]
+    pub fn wheel_scrolls_content() {
+        let mut harness = TestHarness::new(App);
+        let viewport = viewport(&harness);
+
+        for _ in 0..10 {
+            harness.scroll_at(viewport.center().into(), Vec2::new(0., 100. * ROW_HEIGHT));
+        }
+
+        harness.tap_at(Point::new(viewport.left + 10., viewport.top + 10.));
+
+        assert_eq!(selected(&harness), Some(1000));
+        assert!(built_rows(&harness) <= 20);
+    }
+
+    #[test]
+    pub fn controller_jumps_to_far_rows() {
+        let mut harness = TestHarness::new(App);
+        let viewport = viewport(&harness);
+
+        harness.press_key(Key::End);
+        harness.tap_at(Point::new(viewport.left + 10., viewport.top + 10.));
+
+        assert_eq!(selected(&harness), Some(ROW_COUNT / 2));
+        assert!(built_rows(&harness) <= 20);
+    }
+
+    /// List whose rows have different heights: 20, 40 and 60.
+    #[derive(ViewWidget)]
+    struct VariableList;
+
+    impl ViewWidget for VariableList {
+        fn build<'w>(&'w self, _: BuildCx<'w, Self>) -> Self::Widget<'w> {
+            Center::child(SizedBox::new(
+                ListView::builder(1000, |_, n| {
+                    SizedBox::new(
+                        Text::new(n.to_string()),
+                        None,
+                        Some(20. * (n % 3 + 1) as f64),
+                    )
+                }),
+                Some(300.),
+                Some(200.),
+            ))
+        }
+    }
+
+    #[test]
+    pub fn rows_of_different_heights_are_measured() {
+        let mut harness = TestHarness::new(VariableList);
+        let viewport = viewport(&harness);
+
+        // Rows 0..12 have an average height of 40.
+        assert_eq!(built_rows(&harness), 12);
+
+        harness.scroll_at(viewport.center().into(), Vec2::new(0., 1e9));
+
+        // Last row is aligned with the bottom of the viewport once the rows
+        // near it were measured.
+        let last = harness.find_one(&Finder::by_key(999usize)).global_rect();
+
+        assert!((last.bottom - viewport.bottom).abs() < 1e-6);
+        assert_eq!(last.height(), 20.);
+    }
+
+    #[test]
+    pub fn scrolling_is_clamped() {
+        let mut harness = TestHarness::new(App);
+        let viewport = viewport(&harness);
+
+        harness.scroll_at(viewport.center().into(), Vec2::new(0., 1e9));
+        harness.tap_at(Point::new(viewport.left + 10., viewport.bottom - 10.));
+
+        assert_eq!(selected(&harness), Some(ROW_COUNT - 1));
+    }
+
+    /// Marks itself as dirty every time it's laid out, so it never settles.
+    /// Its state counts those layouts.
+    #[derive(RenderWidget)]
+    struct Restless;
+
+    impl WidgetState for Restless {
+        type State = usize;
+
+        fn create_state(&self) -> Self::State {
+            0
+        }
+    }
+
+    impl RenderWidget for Restless {
+        fn build<'w>(&'w self, _: BuildCx<'w, Self>) -> Vec<Self::Widget<'w>> {
+            Vec::<()>::new()
+        }
+
+        fn layout(&self, cx: &LayoutCx<Self>, constraints: Constraints) -> Size {
+            *cx.widget_state_mut() += 1;
+
+            constraints.biggest()
+        }
+
+        fn paint(&self, _: &mut PaintCx<Self>, _: &mut Canvas, _: &Offset) {}
+    }
+
+    fn layouts(harness: &TestHarness) -> usize {
+        *harness
+            .find_one(&Finder::by_type::<Restless>())
+            .state::<usize>()
+            .unwrap()
+    }
+
+    #[test]
+    pub fn layout_passes_are_limited() {
+        let mut harness = TestHarness::new(Restless);
+        let before = layouts(&harness);
+
+        // Frame is painted after 8 passes, even though the widget is still
+        // dirty.
+        harness.pump();
+
+        assert_eq!(layouts(&harness) - before, 8);
+        assert_eq!(harness.rebuilds_last_frame(), 8);
+    }
+}
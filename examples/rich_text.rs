@@ -0,0 +1,117 @@
+//! Paragraph of text with mixed styles and a link, which counts how many
+//! times it was clicked.
+
+#![feature(type_alias_impl_trait)]
+
+use frui::{
+    druid_shell::piet::{FontStyle, FontWeight},
+    prelude::*,
+};
+
+#[derive(ViewWidget)]
+struct App;
+
+impl WidgetState for App {
+    /// Number of times the link was clicked.
+    type State = usize;
+
+    fn create_state(&self) -> Self::State {
+        0
+    }
+}
+
+impl ViewWidget for App {
+    fn build<'w>(&'w self, cx: BuildCx<'w, Self>) -> Self::Widget<'w> {
+        let link = TextStyle::default()
+            .color(Color::rgb8(0x3d, 0x8b, 0xfd))
+            .underline(true);
+
+        let text = TextSpan::new("Frui is ").children(vec![
+            TextSpan::new("fast").style(TextStyle::default().weight(FontWeight::BOLD)),
+            TextSpan::new(", "),
+            TextSpan::new("friendly").style(TextStyle::default().style(FontStyle::Italic)),
+            TextSpan::new(" and "),
+            TextSpan::new("open source").style(link),
+            TextSpan::new(format!(" (clicked {} times)", cx.state())).style(
+                TextStyle::default()
+                    .color(Color::rgb8(0x80, 0x80, 0x80))
+                    .strikethrough(*cx.state() == 0),
+            ),
+        ]);
+
+        Center::child(RichText::new(text).on_tap(|span| {
+            if span.text == "open source" {
+                *cx.state_mut() += 1;
+            }
+        }))
+    }
+}
+
+fn main() {
+    run_app(App);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use frui::{
+        app::runner::headless::{Finder, TestHarness},
+        druid_shell::kurbo::Point,
+    };
+
+    #[test]
+    pub fn spans_are_found_by_offset() {
+        let text = TextSpan::new("ab").children(vec![
+            TextSpan::new("cd").children(vec![TextSpan::new("ef")]),
+            TextSpan::new("gh"),
+        ]);
+
+        assert_eq!(text.to_plain_text(), "abcdefgh");
+        assert_eq!(text.span_at_offset(1).unwrap().text, "ab");
+        assert_eq!(text.span_at_offset(5).unwrap().text, "ef");
+        assert_eq!(text.span_at_offset(6).unwrap().text, "gh");
+        assert!(text.span_at_offset(8).is_none());
+    }
+
+    #[test]
+    pub fn styles_are_inherited() {
+        let parent = TextStyle::default().size(20.).underline(true);
+        let child = TextStyle::default().size(12.);
+
+        let style = parent.merge(&child);
+
+        assert_eq!(style.size, Some(12.));
+        assert_eq!(style.underline, Some(true));
+        assert_eq!(style.weight, None);
+    }
+
+    #[cfg(not(feature = "miri"))]
+    #[test]
+    pub fn tapping_link_calls_on_tap() {
+        // Window is wide enough for the text to fit in a single line.
+        let mut harness = TestHarness::with_size(App, Size::new(800., 400.));
+
+        let clicks = |harness: &TestHarness| {
+            let app = harness.find_one(&Finder::by_type::<App>());
+            let clicks = *app.state::<usize>().unwrap();
+            clicks
+        };
+
+        let rect = harness
+            .find_one(&Finder::by_type::<RichText<fn(&TextSpan)>>())
+            .global_rect();
+
+        // All characters of a monospace font are equally wide.
+        let text = "Frui is fast, friendly and open source (clicked 0 times)";
+        let char_width = rect.width() / text.len() as f64;
+        let y = rect.top + 8.;
+
+        // Tap on "fast".
+        harness.tap_at(Point::new(rect.left + char_width * 9.5, y));
+        assert_eq!(clicks(&harness), 0);
+
+        // Tap on "open source".
+        harness.tap_at(Point::new(rect.left + char_width * 30.5, y));
+        assert_eq!(clicks(&harness), 1);
+    }
+}
@@ -0,0 +1,125 @@
+//! Long list of rows in a [`Scroll`] view.
+//!
+//! List is scrolled with the mouse wheel or by dragging its scrollbar. Tapping
+//! a row selects it.
+
+#![feature(type_alias_impl_trait)]
+
+use frui::prelude::*;
+
+const ROW_COUNT: usize = 50;
+const ROW_HEIGHT: f64 = 40.;
+
+#[derive(ViewWidget)]
+struct App;
+
+impl WidgetState for App {
+    type State = Option<usize>;
+
+    fn create_state(&self) -> Self::State {
+        None
+    }
+}
+
+impl ViewWidget for App {
+    fn build<'w>(&'w self, cx: BuildCx<'w, Self>) -> Self::Widget<'w> {
+        let selected = *cx.state();
+
+        let rows = (0..ROW_COUNT)
+            .map(|n| {
+                let label = match selected == Some(n) {
+                    true => format!("Row {} (selected)", n),
+                    false => format!("Row {}", n),
+                };
+
+                PointerListener::builder()
+                    .on_pointer_down(move |_| *cx.state_mut() = Some(n))
+                    .child(SizedBox::new(
+                        Text::new(label),
+                        Some(300.),
+                        Some(ROW_HEIGHT),
+                    ))
+            })
+            .collect::<Vec<_>>();
+
+        Center::child(SizedBox::new(
+            Scroll::builder()
+                .scroll_direction(ScrollDirection::Vertical)
+                .child(
+                    Column::builder()
+                        .main_axis_size(MainAxisSize::Min)
+                        .children(rows),
+                ),
+            Some(300.),
+            Some(200.),
+        ))
+    }
+}
+
+fn main() {
+    run_app(App);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use frui::{
+        app::runner::headless::{Finder, TestHarness},
+        druid_shell::kurbo::{Point, Vec2},
+        render::Rect,
+    };
+
+    fn viewport(harness: &TestHarness) -> Rect {
+        harness
+            .find_one(&Finder::by_type::<Scroll<()>>())
+            .global_rect()
+    }
+
+    fn selected(harness: &TestHarness) -> Option<usize> {
+        *harness
+            .find_one(&Finder::by_type::<App>())
+            .state::<Option<usize>>()
+            .unwrap()
+    }
+
+    #[test]
+    pub fn wheel_scrolls_content() {
+        let mut harness = TestHarness::new(App);
+        let viewport = viewport(&harness);
+
+        harness.scroll_at(viewport.center().into(), Vec2::new(0., 3. * ROW_HEIGHT));
+        harness.tap_at(Point::new(viewport.left + 10., viewport.top + 10.));
+
+        assert_eq!(selected(&harness), Some(3));
+    }
+
+    #[test]
+    pub fn scrolling_is_clamped() {
+        let mut harness = TestHarness::new(App);
+        let viewport = viewport(&harness);
+
+        harness.scroll_at(viewport.center().into(), Vec2::new(0., -100.));
+        harness.tap_at(Point::new(viewport.left + 10., viewport.top + 10.));
+
+        assert_eq!(selected(&harness), Some(0));
+
+        harness.scroll_at(viewport.center().into(), Vec2::new(0., 1e6));
+        harness.tap_at(Point::new(viewport.left + 10., viewport.bottom - 10.));
+
+        assert_eq!(selected(&harness), Some(ROW_COUNT - 1));
+    }
+
+    #[test]
+    pub fn dragging_scrollbar_scrolls_content() {
+        let mut harness = TestHarness::new(App);
+        let viewport = viewport(&harness);
+
+        // Scrollbar thumb is at the top of the right edge of the viewport.
+        let thumb = Point::new(viewport.right - 4., viewport.top + 5.);
+
+        harness.drag(thumb, Point::new(thumb.x, viewport.bottom + 100.), 5);
+        harness.tap_at(Point::new(viewport.left + 10., viewport.bottom - 10.));
+
+        assert_eq!(selected(&harness), Some(ROW_COUNT - 1));
+    }
+}
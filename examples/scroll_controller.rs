@@ -0,0 +1,159 @@
+//! Reading and changing scroll offset of a [`Scroll`] view from outside of it
+//! through a [`ScrollController`].
+//!
+//! Press `End` to jump to the bottom of the list and `Home` to animate back to
+//! its top. List starts scrolled to its fifth row.
+
+#![feature(type_alias_impl_trait)]
+
+use std::time::Duration;
+
+use frui::{
+    druid_shell::{keyboard_types::Key, kurbo::Vec2},
+    prelude::*,
+};
+
+const ROW_COUNT: usize = 50;
+const ROW_HEIGHT: f64 = 40.;
+
+const SCROLL_DURATION: Duration = Duration::from_millis(300);
+
+#[derive(ViewWidget)]
+struct App;
+
+impl WidgetState for App {
+    type State = ScrollController;
+
+    fn create_state(&self) -> Self::State {
+        ScrollController::with_offset(Vec2::new(0., 5. * ROW_HEIGHT))
+    }
+
+    fn mount<'a>(&'a self, cx: BuildCx<'a, Self>) {
+        // Rebuild to display the current offset.
+        cx.state().attach(cx);
+    }
+}
+
+impl ViewWidget for App {
+    fn build<'w>(&'w self, cx: BuildCx<'w, Self>) -> Self::Widget<'w> {
+        let controller = cx.state().clone();
+        let offset = controller.offset();
+
+        let rows = (0..ROW_COUNT)
+            .map(|n| {
+                SizedBox::new(
+                    Text::new(format!("Row {}", n)),
+                    Some(300.),
+                    Some(ROW_HEIGHT),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        KeyboardEventDetector {
+            on_event: move |event| match event.key {
+                Key::Home => {
+                    controller.animate_to(Vec2::ZERO, SCROLL_DURATION, Curves::EASE_IN_OUT)
+                }
+                Key::End => controller.jump_to(Vec2::new(0., f64::INFINITY)),
+                _ => {}
+            },
+            child: Center::child(
+                Column::builder().space_between(10.0).children((
+                    Text::new(format!("Offset: {:.0}", offset.y)),
+                    SizedBox::new(
+                        Scroll::builder().controller(cx.state().clone()).child(
+                            Column::builder()
+                                .main_axis_size(MainAxisSize::Min)
+                                .children(rows),
+                        ),
+                        Some(300.),
+                        Some(200.),
+                    ),
+                )),
+            ),
+        }
+    }
+}
+
+fn main() {
+    run_app(App);
+}
+
+#[cfg(test)]
+mod test {
+    use std::{cell::Cell, rc::Rc};
+
+    use super::*;
+    use frui::app::runner::headless::{Finder, TestHarness};
+
+    fn controller(harness: &TestHarness) -> ScrollController {
+        harness
+            .find_one(&Finder::by_type::<App>())
+            .state::<ScrollController>()
+            .unwrap()
+            .clone()
+    }
+
+    #[test]
+    pub fn initial_offset_is_restored() {
+        let harness = TestHarness::new(App);
+
+        assert_eq!(
+            controller(&harness).offset(),
+            Vec2::new(0., 5. * ROW_HEIGHT)
+        );
+    }
+
+    #[test]
+    pub fn jump_is_clamped_to_content() {
+        let mut harness = TestHarness::new(App);
+
+        harness.press_key(Key::End);
+
+        let max = (ROW_COUNT as f64 - 5.) * ROW_HEIGHT;
+        let controller = controller(&harness);
+
+        assert_eq!(controller.max_offset(), Some(Vec2::new(0., max)));
+        assert_eq!(controller.offset(), Vec2::new(0., max));
+    }
+
+    #[test]
+    pub fn animation_reaches_target() {
+        let mut harness = TestHarness::new(App);
+
+        harness.press_key(Key::Home);
+        harness.pump_for(SCROLL_DURATION / 2);
+
+        let controller = controller(&harness);
+
+        assert!(controller.is_animating());
+        assert!(controller.offset().y > 0.);
+
+        harness.pump_for(SCROLL_DURATION);
+
+        assert!(!controller.is_animating());
+        assert_eq!(controller.offset(), Vec2::ZERO);
+    }
+
+    #[test]
+    pub fn wheel_notifies_listeners() {
+        let mut harness = TestHarness::new(App);
+
+        let calls = Rc::new(Cell::new(0));
+        let controller = controller(&harness);
+
+        controller.add_listener({
+            let calls = calls.clone();
+            move || calls.set(calls.get() + 1)
+        });
+
+        let viewport = harness
+            .find_one(&Finder::by_type::<Scroll<()>>())
+            .global_rect();
+
+        harness.scroll_at(viewport.center().into(), Vec2::new(0., ROW_HEIGHT));
+
+        assert_eq!(calls.get(), 1);
+        assert_eq!(controller.offset(), Vec2::new(0., 6. * ROW_HEIGHT));
+    }
+}
@@ -0,0 +1,130 @@
+//! Cells laid out in a [`Table`] with columns of fixed, fractional, flexible
+//! and intrinsic widths, and cells aligned vertically within their rows.
+
+#![feature(type_alias_impl_trait)]
+
+use frui::prelude::*;
+
+#[derive(ViewWidget)]
+struct App {
+    text_direction: TextDirection,
+}
+
+impl ViewWidget for App {
+    fn build<'w>(&'w self, _: BuildCx<'w, Self>) -> Self::Widget<'w> {
+        let header = TableRow::builder()
+            .decoration(BoxDecoration::builder().color(Color::SILVER))
+            .children(wlist![
+                Container::builder().height(20.),
+                Container::builder().height(20.),
+                Container::builder().height(20.),
+                Container::builder().width(60.).height(20.),
+            ]);
+
+        let row = TableRow::builder().children(wlist![
+            Container::builder().color(Color::AQUA).height(40.),
+            TableCell::builder()
+                .vertical_alignment(TableCellVerticalAlignment::Middle)
+                .child(Container::builder().color(Color::GREEN).height(10.)),
+            TableCell::builder()
+                .vertical_alignment(TableCellVerticalAlignment::Bottom)
+                .child(Container::builder().color(Color::FUCHSIA).height(10.)),
+            TableCell::builder()
+                .vertical_alignment(TableCellVerticalAlignment::Fill)
+                .child(Container::builder().color(Color::YELLOW).width(40.)),
+        ]);
+
+        Center::child(SizedBox::new(
+            Table::builder()
+                .column_widths(vec![
+                    TableColumnWidth::Fixed(100.),
+                    TableColumnWidth::Fraction(0.25),
+                    TableColumnWidth::Flex(1.),
+                    TableColumnWidth::Intrinsic,
+                ])
+                .text_direction(self.text_direction)
+                .rows(vec![header, row]),
+            Some(400.),
+            None,
+        ))
+    }
+}
+
+fn main() {
+    run_app(App {
+        text_direction: TextDirection::Ltr,
+    });
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use frui::app::runner::headless::{Finder, TestHarness};
+
+    /// Rectangles of cells relative to the `Table`, row by row.
+    fn cell_rects(harness: &TestHarness) -> Vec<(f64, f64, f64, f64)> {
+        let table = harness.find_one(&Finder::by_type::<Table<()>>());
+        let origin = table.global_rect();
+
+        table
+            .children()
+            .iter()
+            .map(|cell| {
+                let rect = cell.global_rect();
+
+                (
+                    rect.left - origin.left,
+                    rect.top - origin.top,
+                    rect.width(),
+                    rect.height(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    pub fn columns_follow_width_specs() {
+        let harness = TestHarness::new(App {
+            text_direction: TextDirection::Ltr,
+        });
+
+        let table = harness.find_one(&Finder::by_type::<Table<()>>());
+        let rects = cell_rects(&harness);
+
+        assert_eq!(table.size(), Size::new(400., 60.));
+
+        // Fixed, a quarter of the width, what's left, and the widest cell.
+        assert_eq!(rects[0], (0., 0., 100., 20.));
+        assert_eq!(rects[1], (100., 0., 100., 20.));
+        assert_eq!(rects[2], (200., 0., 140., 20.));
+        assert_eq!(rects[3], (340., 0., 60., 20.));
+    }
+
+    #[test]
+    pub fn cells_are_aligned_within_rows() {
+        let harness = TestHarness::new(App {
+            text_direction: TextDirection::Ltr,
+        });
+
+        let rects = cell_rects(&harness);
+
+        // Second row is as tall as its tallest cell.
+        assert_eq!(rects[4], (0., 20., 100., 40.));
+        assert_eq!(rects[5], (100., 35., 100., 10.));
+        assert_eq!(rects[6], (200., 50., 140., 10.));
+        assert_eq!(rects[7], (340., 20., 60., 40.));
+    }
+
+    #[test]
+    pub fn rtl_places_columns_from_right() {
+        let harness = TestHarness::new(App {
+            text_direction: TextDirection::Rtl,
+        });
+
+        let rects = cell_rects(&harness);
+
+        assert_eq!(rects[0], (300., 0., 100., 20.));
+        assert_eq!(rects[3], (0., 0., 60., 20.));
+        assert_eq!(rects[6], (60., 50., 140., 10.));
+    }
+}
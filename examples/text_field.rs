@@ -0,0 +1,223 @@
+//! Single-line and multi-line text fields.
+//!
+//! Text of the single-line field is kept in a [`TextEditingController`] owned
+//! by the [`App`], which displays that text below both fields and rebuilds
+//! every time it's edited.
+
+#![feature(type_alias_impl_trait)]
+
+use frui::prelude::*;
+
+#[derive(ViewWidget)]
+struct App;
+
+struct Controllers {
+    name: TextEditingController,
+    notes: TextEditingController,
+}
+
+impl WidgetState for App {
+    type State = Controllers;
+
+    fn create_state(&self) -> Self::State {
+        Controllers {
+            name: TextEditingController::new(),
+            notes: TextEditingController::with_text("Notes"),
+        }
+    }
+
+    fn mount<'a>(&'a self, cx: BuildCx<'a, Self>) {
+        cx.state().name.attach(cx);
+    }
+}
+
+impl ViewWidget for App {
+    fn build<'w>(&'w self, cx: BuildCx<'w, Self>) -> Self::Widget<'w> {
+        let state = cx.state();
+
+        Center::child(
+            Column::builder().space_between(20.0).children((
+                SizedBox::new(
+                    TextField::builder()
+                        .controller(state.name.clone())
+                        .autofocus(true),
+                    Some(300.),
+                    None,
+                ),
+                SizedBox::new(
+                    TextField::builder()
+                        .controller(state.notes.clone())
+                        .multiline(true),
+                    Some(300.),
+                    Some(100.),
+                ),
+                Text::new(format!("Hello, {}!", state.name.text_ref())),
+            )),
+        )
+    }
+}
+
+fn main() {
+    run_app(App);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use frui::{
+        app::runner::headless::{Finder, TestHarness},
+        druid_shell::{keyboard_types::Key, kurbo::Point, Modifiers},
+    };
+
+    fn controllers(harness: &TestHarness) -> (TextEditingController, TextEditingController) {
+        let app = harness.find_one(&Finder::by_type::<App>());
+        let state = app.state::<Controllers>().unwrap();
+
+        (state.name.clone(), state.notes.clone())
+    }
+
+    fn press_shortcut(harness: &mut TestHarness, key: &str, mods: Modifiers) {
+        harness.press_key_with(Key::Character(key.into()), Modifiers::CONTROL | mods);
+    }
+
+    #[test]
+    pub fn typing_edits_text() {
+        let mut harness = TestHarness::new(App);
+        let (name, _) = controllers(&harness);
+
+        harness.type_text("Hello");
+        harness.press_key(Key::Backspace);
+
+        assert_eq!(name.text(), "Hell");
+
+        harness.press_key_with(Key::ArrowLeft, Modifiers::SHIFT);
+        harness.press_key_with(Key::ArrowLeft, Modifiers::SHIFT);
+
+        assert_eq!(name.selected_text(), "ll");
+
+        harness.type_text("y");
+
+        assert_eq!(name.text(), "Hey");
+    }
+
+    #[test]
+    pub fn undo_and_redo_typed_text() {
+        let mut harness = TestHarness::new(App);
+        let (name, _) = controllers(&harness);
+
+        harness.type_text("Hello");
+        harness.press_key(Key::Home);
+        harness.type_text("Oh ");
+
+        assert_eq!(name.text(), "Oh Hello");
+
+        press_shortcut(&mut harness, "z", Modifiers::empty());
+        assert_eq!(name.text(), "Hello");
+
+        press_shortcut(&mut harness, "z", Modifiers::empty());
+        assert_eq!(name.text(), "");
+
+        press_shortcut(&mut harness, "z", Modifiers::SHIFT);
+        assert_eq!(name.text(), "Hello");
+    }
+
+    #[test]
+    pub fn copy_and_paste() {
+        let mut harness = TestHarness::new(App);
+        let (name, _) = controllers(&harness);
+
+        harness.type_text("copy");
+        press_shortcut(&mut harness, "a", Modifiers::empty());
+        press_shortcut(&mut harness, "c", Modifiers::empty());
+        harness.press_key(Key::ArrowRight);
+        press_shortcut(&mut harness, "v", Modifiers::empty());
+
+        assert_eq!(name.text(), "copycopy");
+
+        press_shortcut(&mut harness, "a", Modifiers::empty());
+        press_shortcut(&mut harness, "x", Modifiers::empty());
+
+        assert_eq!(name.text(), "");
+    }
+
+    #[cfg(not(feature = "miri"))]
+    #[test]
+    pub fn parent_changes_text_through_controller() {
+        let mut harness = TestHarness::new(App);
+        let (name, _) = controllers(&harness);
+
+        let greeting_width = |harness: &TestHarness| {
+            let text = harness.find_one(&Finder::by_type::<Text<String>>());
+            text.size().width
+        };
+
+        let width = greeting_width(&harness);
+
+        name.set_text("Frui");
+        harness.pump();
+
+        // Greeting was rebuilt with the new text.
+        assert!(greeting_width(&harness) > width);
+
+        harness.type_text("!");
+
+        assert_eq!(name.text(), "Frui!");
+    }
+
+    #[test]
+    pub fn input_method_composes_text() {
+        let mut harness = TestHarness::new(App);
+        let (name, _) = controllers(&harness);
+
+        harness.edit_text_input(|input| {
+            input.replace_range(0..0, "に");
+            input.set_composition_range(Some(0..3));
+        });
+
+        assert_eq!(name.composition_range(), Some(0..3));
+
+        harness.edit_text_input(|input| {
+            input.replace_range(0..3, "日本");
+            input.set_composition_range(None);
+        });
+
+        assert_eq!(name.text(), "日本");
+        assert_eq!(name.composition_range(), None);
+    }
+
+    #[test]
+    pub fn multiline_field_inserts_line_breaks() {
+        let mut harness = TestHarness::new(App);
+        let (_, notes) = controllers(&harness);
+
+        // Focus the second text field.
+        harness.press_key(Key::Tab);
+
+        harness.press_key(Key::Enter);
+        harness.type_text("b");
+
+        assert_eq!(notes.text(), "Notes\nb");
+
+        harness.press_key(Key::ArrowUp);
+
+        assert_eq!(notes.selection().active, 1);
+    }
+
+    #[cfg(not(feature = "miri"))]
+    #[test]
+    pub fn tapping_places_caret() {
+        let mut harness = TestHarness::new(App);
+        let (_, notes) = controllers(&harness);
+
+        let fields = harness.find_all(&Finder::by_type::<TextField>());
+        let rect = fields[1].global_rect();
+
+        harness.tap_at(Point::new(rect.left + 1., rect.top + 5.));
+
+        assert_eq!(notes.selection().active, 0);
+
+        harness.type_text("My ");
+
+        assert_eq!(notes.text(), "My Notes");
+    }
+}
@@ -0,0 +1,99 @@
+//! Alignment, maximal number of lines, overflow and spacing of [`Text`].
+
+#![feature(type_alias_impl_trait)]
+
+use frui::prelude::*;
+
+const FRUI: &str = "Frui is a developer-friendly UI framework that makes building user \
+                    interfaces easy and productive. It's inspired by Flutter architecture \
+                    and is written in Rust!";
+
+#[derive(ViewWidget)]
+struct App;
+
+impl ViewWidget for App {
+    fn build<'w>(&'w self, _: BuildCx<'w, Self>) -> Self::Widget<'w> {
+        Center::child(
+            Column::builder().space_between(20.0).children((
+                SizedBox::new(
+                    Text::new(FRUI).text_align(TextAlign::Justify),
+                    Some(300.),
+                    None,
+                ),
+                SizedBox::new(
+                    Text::new(FRUI)
+                        .max_lines(2)
+                        .overflow(TextOverflow::Ellipsis),
+                    Some(300.),
+                    None,
+                ),
+                SizedBox::new(
+                    Text::new(FRUI).max_lines(1).overflow(TextOverflow::Fade),
+                    Some(300.),
+                    None,
+                ),
+                SizedBox::new(
+                    Text::new("Centered").text_align(TextAlign::Center),
+                    Some(300.),
+                    None,
+                ),
+                SizedBox::new(
+                    Text::new("Spaced out").letter_spacing(4.).line_height(2.),
+                    Some(300.),
+                    None,
+                ),
+            )),
+        )
+    }
+}
+
+fn main() {
+    run_app(App);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use frui::app::runner::headless::{Finder, TestHarness};
+
+    #[allow(dead_code)]
+    fn text_heights(harness: &TestHarness) -> Vec<f64> {
+        harness
+            .find_all(&Finder::by_type::<Text<&str>>())
+            .iter()
+            .map(|text| text.size().height)
+            .collect()
+    }
+
+    #[cfg(not(feature = "miri"))]
+    #[test]
+    pub fn max_lines_limit_height() {
+        let harness = TestHarness::new(App);
+        let heights = text_heights(&harness);
+
+        let one_line = heights[2];
+
+        assert!(one_line > 0.);
+        assert!((heights[1] - 2. * one_line).abs() < 1.);
+        assert!(heights[0] > heights[1]);
+    }
+
+    #[cfg(not(feature = "miri"))]
+    #[test]
+    pub fn faded_text_is_single_line() {
+        let harness = TestHarness::new(App);
+        let heights = text_heights(&harness);
+
+        // Same height as a short label.
+        assert_eq!(heights[2], heights[3]);
+    }
+
+    #[cfg(not(feature = "miri"))]
+    #[test]
+    pub fn line_height_is_multiple_of_font_size() {
+        let harness = TestHarness::new(App);
+        let heights = text_heights(&harness);
+
+        assert!((heights[4] - 32.).abs() < 0.001);
+    }
+}
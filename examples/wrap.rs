@@ -0,0 +1,103 @@
+//! Tiles which flow onto new runs once they don't fit in the width of a
+//! [`Wrap`].
+
+#![feature(type_alias_impl_trait)]
+
+use frui::prelude::*;
+
+const TILE_COUNT: usize = 10;
+
+#[derive(ViewWidget)]
+struct App {
+    alignment: MainAxisAlignment,
+    text_direction: TextDirection,
+}
+
+impl ViewWidget for App {
+    fn build<'w>(&'w self, _: BuildCx<'w, Self>) -> Self::Widget<'w> {
+        let tiles = (0..TILE_COUNT)
+            .map(|n| {
+                LocalKey::new(
+                    n,
+                    Container::builder()
+                        .color(Color::AQUA)
+                        .width(80.)
+                        .height(40.),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        Center::child(SizedBox::new(
+            Wrap::builder()
+                .spacing(10.)
+                .run_spacing(10.)
+                .alignment(self.alignment)
+                .text_direction(self.text_direction)
+                .children(tiles),
+            Some(300.),
+            None,
+        ))
+    }
+}
+
+fn main() {
+    run_app(App {
+        alignment: MainAxisAlignment::Center,
+        text_direction: TextDirection::Ltr,
+    });
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use frui::app::runner::headless::{Finder, TestHarness};
+
+    fn harness(alignment: MainAxisAlignment, text_direction: TextDirection) -> TestHarness {
+        TestHarness::new(App {
+            alignment,
+            text_direction,
+        })
+    }
+
+    /// Offset of a tile relative to the `Wrap`.
+    fn tile_offset(harness: &TestHarness, n: usize) -> (f64, f64) {
+        let wrap = harness
+            .find_one(&Finder::by_type::<Wrap<()>>())
+            .global_rect();
+        let tile = harness.find_one(&Finder::by_key(n)).global_rect();
+
+        (tile.left - wrap.left, tile.top - wrap.top)
+    }
+
+    #[test]
+    pub fn tiles_flow_onto_new_runs() {
+        let harness = harness(MainAxisAlignment::Start, TextDirection::Ltr);
+
+        // Three tiles fit in each run.
+        assert_eq!(tile_offset(&harness, 0), (0., 0.));
+        assert_eq!(tile_offset(&harness, 2), (180., 0.));
+        assert_eq!(tile_offset(&harness, 3), (0., 50.));
+        assert_eq!(tile_offset(&harness, 9), (0., 150.));
+
+        let wrap = harness.find_one(&Finder::by_type::<Wrap<()>>());
+
+        assert_eq!(wrap.size(), Size::new(300., 190.));
+    }
+
+    #[test]
+    pub fn runs_are_aligned() {
+        let harness = harness(MainAxisAlignment::Center, TextDirection::Ltr);
+
+        assert_eq!(tile_offset(&harness, 0), (20., 0.));
+        assert_eq!(tile_offset(&harness, 9), (110., 150.));
+    }
+
+    #[test]
+    pub fn rtl_places_tiles_from_right() {
+        let harness = harness(MainAxisAlignment::Start, TextDirection::Rtl);
+
+        assert_eq!(tile_offset(&harness, 0), (220., 0.));
+        assert_eq!(tile_offset(&harness, 2), (40., 0.));
+        assert_eq!(tile_offset(&harness, 9), (220., 150.));
+    }
+}